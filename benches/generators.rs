@@ -0,0 +1,279 @@
+//! Benchmarks comparing the generation speed and memory usage of all algorithms
+//!
+//! Run with `cargo bench --bench generators`.
+//! Before the timing measurements, the generation time and peak heap usage of every algorithm
+//! are measured once more and printed together with a recommendation as a markdown section.
+//! If the environment variable `MAZE_BENCH_UPDATE_DOCS` is set to `1`, that section is also
+//! written to `docs/choosing-an-algorithm.md`, which is included in the crate documentation,
+//! and into the *Choosing an algorithm* section of the README.
+//! Pass a filter which matches no benchmark, e.g.
+//! `MAZE_BENCH_UPDATE_DOCS=1 cargo bench --bench generators -- none`, to only regenerate that
+//! section.
+//! Afterwards the peak heap usage of streaming Eller's algorithm for increasing heights, which
+//! stays flat, is printed.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use criterion::{BenchmarkId, Criterion};
+
+use maze_generator::api::*;
+
+/// Allocator which keeps track of the highest amount of simultaneously allocated memory
+struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        self.peak.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+const SEED: Seed = [42; 32];
+
+/// Edge lengths of the square mazes that are generated
+const SIZES: [i32; 5] = [10, 50, 100, 500, 1000];
+
+/// How often every maze of the generated section is generated, the median time is reported
+const SECTION_RUNS: usize = 5;
+
+/// Markers between which the generated section is placed in the README
+const README_MARKERS: (&str, &str) = ("<!-- benchmarks:start -->", "<!-- benchmarks:end -->");
+
+/// Environment variable which has to be set to `1` for the generated section to be written
+const UPDATE_DOCS_VAR: &str = "MAZE_BENCH_UPDATE_DOCS";
+
+type Factory = fn() -> Box<dyn Generator>;
+
+/// All benchmarked algorithms
+fn algorithms() -> Vec<(&'static str, Factory)> {
+    vec![
+        ("ellers", || Box::new(EllersGenerator::new(Some(SEED)))),
+        ("growing_tree", || {
+            Box::new(GrowingTreeGenerator::new(Some(SEED)))
+        }),
+        ("prims", || Box::new(PrimsGenerator::new(Some(SEED)))),
+        ("recursive_backtracking", || {
+            Box::new(RbGenerator::new(Some(SEED)))
+        }),
+    ]
+}
+
+/// Format a duration with a precision that suits its magnitude
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis < 1.0 {
+        format!("{:.0} µs", millis * 1000.0)
+    } else if millis < 1000.0 {
+        format!("{:.1} ms", millis)
+    } else {
+        format!("{:.2} s", millis / 1000.0)
+    }
+}
+
+/// Format an amount of bytes with a unit that suits its magnitude
+fn format_bytes(bytes: usize) -> String {
+    let kibibytes = bytes as f64 / 1024.0;
+    if kibibytes < 1024.0 {
+        format!("{:.1} KiB", kibibytes)
+    } else {
+        format!("{:.1} MiB", kibibytes / 1024.0)
+    }
+}
+
+/// Measure the median generation time and the peak heap usage of every algorithm and size as
+/// a markdown section followed by a recommendation
+fn algorithm_section() -> String {
+    let mut section = format!(
+        "The following numbers were measured on square mazes by the benchmark suite, which \
+         regenerates this section when it is run via \
+         `{}=1 cargo bench --bench generators`.\n\n\
+         | Algorithm | Size | Time | Peak heap usage |\n\
+         |---|---|---|---|\n",
+        UPDATE_DOCS_VAR
+    );
+    // time and peak heap usage of every algorithm at the largest size
+    let mut largest = Vec::new();
+    for (_, factory) in algorithms() {
+        let name = factory().info().name;
+        for size in SIZES.iter() {
+            let mut times = Vec::with_capacity(SECTION_RUNS);
+            let mut peak = 0;
+            for _ in 0..SECTION_RUNS {
+                let mut generator = factory();
+                let baseline = ALLOCATOR.current.load(Ordering::SeqCst);
+                ALLOCATOR.peak.store(baseline, Ordering::SeqCst);
+                let started = Instant::now();
+                let maze = generator.generate(*size, *size);
+                times.push(started.elapsed());
+                peak = ALLOCATOR.peak.load(Ordering::SeqCst) - baseline;
+                assert!(maze.is_ok());
+            }
+            times.sort();
+            let time = times[SECTION_RUNS / 2];
+            section.push_str(&format!(
+                "| {} | {}x{} | {} | {} |\n",
+                name,
+                size,
+                size,
+                format_duration(time),
+                format_bytes(peak)
+            ));
+            if Some(size) == SIZES.last() {
+                largest.push((name, time, peak));
+            }
+        }
+    }
+    section.push_str(&recommendation(&largest));
+    section
+}
+
+/// Recommend an algorithm based on the measurements `(name, time, peak)` at the largest size
+/// and the descriptions of the algorithms
+fn recommendation(largest: &[(&str, Duration, usize)]) -> String {
+    let fastest = largest.iter().min_by_key(|(_, time, _)| *time);
+    let leanest = largest.iter().min_by_key(|(_, _, peak)| *peak);
+    let size = SIZES[SIZES.len() - 1];
+
+    let mut recommendation = String::from("\n### Recommendation\n\n");
+    if let (Some(fastest), Some(leanest)) = (fastest, leanest) {
+        recommendation.push_str(&format!(
+            "At {}x{} fields, {} was the fastest and {} needed the least memory.\n",
+            size, size, fastest.0, leanest.0
+        ));
+    }
+    recommendation.push_str(
+        "Since all algorithms generate perfect mazes in linear time, pick the one whose mazes \
+         look the way you want:\n\n\
+         | Algorithm | Typical mazes | Memory besides the maze |\n\
+         |---|---|---|\n",
+    );
+    for (_, factory) in algorithms() {
+        let info = factory().info();
+        recommendation.push_str(&format!(
+            "| {} | {} | {} |\n",
+            info.name, info.texture_bias, info.memory_profile
+        ));
+    }
+    recommendation.push_str(
+        "\nRecursive backtracking is a good default for puzzles with long solutions and growing \
+         tree can imitate both it and Prim's while staying fast. \
+         Eller's algorithm is the only one which can stream mazes row by row via \
+         `EllersGenerator::generate_streaming()` with memory usage that does not grow with their \
+         height, so use it for mazes which are too large to be kept in memory as a whole.\n",
+    );
+    recommendation
+}
+
+/// Write `section` into the documentation and between the markers of the README
+fn write_algorithm_section(section: &str) -> io::Result<()> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    fs::write(root.join("docs/choosing-an-algorithm.md"), section)?;
+
+    let readme_path = root.join("README.md");
+    let readme = fs::read_to_string(&readme_path)?;
+    let (start, end) = README_MARKERS;
+    let (before, rest) = readme
+        .split_once(start)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing start marker"))?;
+    let (_, after) = rest
+        .split_once(end)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing end marker"))?;
+    fs::write(
+        readme_path,
+        format!("{}{}\n{}{}{}", before, start, section, end, after),
+    )
+}
+
+/// Width of the streamed mazes
+const STREAMING_WIDTH: i32 = 1000;
+
+/// Heights of the streamed mazes
+const STREAMING_HEIGHTS: [i32; 4] = [10, 1000, 10_000, 100_000];
+
+fn print_streaming_memory_report() {
+    println!("| Streamed size | Peak heap usage |");
+    println!("|---|---|");
+    for height in STREAMING_HEIGHTS.iter() {
+        let mut generator = EllersGenerator::new(Some(SEED));
+        let baseline = ALLOCATOR.current.load(Ordering::SeqCst);
+        ALLOCATOR.peak.store(baseline, Ordering::SeqCst);
+        let result = generator.generate_streaming(STREAMING_WIDTH, *height, |_| {});
+        let peak = ALLOCATOR.peak.load(Ordering::SeqCst) - baseline;
+        assert!(result.is_ok());
+        println!(
+            "| {}x{} | {:.1} KiB |",
+            STREAMING_WIDTH,
+            height,
+            peak as f64 / 1024.0
+        );
+    }
+    println!();
+}
+
+fn bench_generators(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+    group.sample_size(10);
+    for (name, factory) in algorithms() {
+        for size in SIZES.iter() {
+            group.bench_with_input(BenchmarkId::new(name, size), size, |b, size| {
+                b.iter(|| factory().generate(*size, *size))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_streaming(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate_streaming");
+    group.sample_size(10);
+    for height in STREAMING_HEIGHTS.iter().filter(|h| **h <= 10_000) {
+        group.bench_with_input(BenchmarkId::new("ellers", height), height, |b, height| {
+            b.iter(|| {
+                EllersGenerator::new(Some(SEED)).generate_streaming(
+                    STREAMING_WIDTH,
+                    *height,
+                    |_| {},
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn main() {
+    let section = algorithm_section();
+    if env::var(UPDATE_DOCS_VAR).as_deref() == Ok("1") {
+        if let Err(e) = write_algorithm_section(&section) {
+            eprintln!("could not write the algorithm section: {}", e);
+        }
+    }
+    print!("{}", section);
+    println!();
+    print_streaming_memory_report();
+
+    let mut criterion = Criterion::default().configure_from_args();
+    bench_generators(&mut criterion);
+    bench_streaming(&mut criterion);
+    criterion.final_summary();
+}
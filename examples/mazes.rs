@@ -1,14 +1,14 @@
 //! Maze generator example program
 //!
+use std::io::{self, BufWriter};
 use std::time::Instant;
 
 use anyhow::Result;
 use clap::{Arg, Command};
 
-use maze_generator::ellers_algorithm::EllersGenerator;
+use maze_generator::generators;
 use maze_generator::growing_tree::*;
 use maze_generator::prelude::*;
-use maze_generator::prims_algorithm::PrimsGenerator;
 use maze_generator::recursive_backtracking::RbGenerator;
 
 fn main() -> Result<()> {
@@ -22,7 +22,7 @@ fn main() -> Result<()> {
                 .long("type")
                 .takes_value(true)
                 .default_value("df")
-                .help("A maze generator name (ellers|prims|growingtree). Uses recursive backtracing if not specified."),
+                .help("A maze generator name (ellers|prims|growingtree|recursive_backtracking). Uses recursive backtracing if not specified."),
         )
         .arg(
             Arg::new("width")
@@ -105,40 +105,27 @@ fn main() -> Result<()> {
     // Generate the maze
     let start = Instant::now();
     let gentype = matches.value_of("type").unwrap().to_lowercase();
-    let actualtype: String;
-    let maze = match gentype.as_str() {
-        "ellers" | "eller" => {
-            actualtype = String::from("Eller's");
-            let mut generator = EllersGenerator::new(rngseed);
-            generator.generate(width, height)?
-        }
+    let mut generator: Box<dyn Generator> = match gentype.as_str() {
         "gt" | "growing" | "growingtree" => {
-            actualtype = String::from("Growing tree");
+            // the selection method can only be configured on the concrete type
             let mut generator = GrowingTreeGenerator::new(rngseed);
             generator.selection_method = match selection_method {
                 1 => GrowingTreeSelectionMethod::MostRecent,
                 2 => GrowingTreeSelectionMethod::Random,
                 _ => GrowingTreeSelectionMethod::First,
             };
-            generator.generate(width, height)?
-        }
-        "prim" | "prims" => {
-            actualtype = String::from("Prim's");
-            let mut generator = PrimsGenerator::new(rngseed);
-            generator.generate(width, height)?
-        }
-        _ => {
-            // Default to RbGenerator, so no need to specify it
-            actualtype = String::from("Recursive backtracing");
-            let mut generator = RbGenerator::new(rngseed);
-            generator.generate(width, height)?
+            Box::new(generator)
         }
+        // Default to RbGenerator, so no need to specify it
+        _ => generators::by_name(&gentype, rngseed)
+            .unwrap_or_else(|| Box::new(RbGenerator::new(rngseed))),
     };
+    let maze = generator.generate(width, height)?;
 
     let duration = start.elapsed();
     eprintln!(
-        "Generate {:?}({}) as static {:?}, Size {} x {} in time {:?}",
-        actualtype, gentype, isstatic, width, height, duration
+        "Generate {} as static {:?}, Size {} x {} in time {:?}",
+        gentype, isstatic, width, height, duration
     );
 
     if textoutput {
@@ -146,19 +133,11 @@ fn main() -> Result<()> {
     }
 
     // SVG generation options, lurid green lines and a smaller cellsize, use the defaults for everything else
-    let myoptions = SvgOptions {
-        strokecol: String::from("green"),
-        padding: 8,
-        height: Some(400),
-        ..Default::default()
-    };
+    let myoptions = SvgOptions::new().strokecol("green").padding(8).height(400);
 
     if svgoutput {
-        let svg: String = match maze.to_svg(myoptions) {
-            Ok(val) => val,
-            Err(_) => "".to_string(),
-        };
-        println!("{}", svg);
+        let stdout = io::stdout();
+        maze.write_svg(BufWriter::new(stdout.lock()), &myoptions)?;
     }
 
     Ok(())
@@ -0,0 +1,18 @@
+//! Interactive terminal maze viewer
+//!
+//! Run with `cargo run --example viewer --features tui -- [width] [height]`.
+use anyhow::Result;
+
+use maze_generator::growing_tree::GrowingTreeGenerator;
+use maze_generator::prelude::*;
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let width = args.next().map(|s| s.parse()).transpose()?.unwrap_or(20);
+    let height = args.next().map(|s| s.parse()).transpose()?.unwrap_or(10);
+
+    let maze = GrowingTreeGenerator::new(None).generate(width, height)?;
+    maze_generator::tui::run(&maze)?;
+
+    Ok(())
+}
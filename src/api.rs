@@ -0,0 +1,27 @@
+//! Curated single import of the complete public API
+//!
+//! [`prelude`](crate::prelude) only contains the common types and traits, so using a specific
+//! generator still requires importing it from its own module.
+//! This module additionally re-exports all generators and their configuration types so that
+//! everything can be brought into scope at once.
+//! [`prelude`](crate::prelude) is kept unchanged for compatibility.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::api::*;
+//!
+//! let maze = PrimsGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+//! assert!(maze.difficulty().solution_length > 0);
+//! ```
+
+pub use crate::builder::{generate, Algorithm, MazeBuilder};
+pub use crate::ellers_algorithm::{EllersGenerator, RowWalls};
+pub use crate::growing_tree::{GrowingTreeGenerator, GrowingTreeSelectionMethod, GrowingTreeState};
+pub use crate::origin_shift::{OriginShift, OriginShiftError};
+pub use crate::prelude::*;
+pub use crate::prims_algorithm::{PrimsGenerator, PrimsState};
+pub use crate::puzzle::{Door, Key, PuzzleError, PuzzleMaze};
+pub use crate::recursive_backtracking::RbGenerator;
+pub use crate::stack::{LevelCoordinates, MazeStack, Stair};
+pub use crate::symmetric::{SymmetricGenerator, Symmetry};
+pub use crate::theta::{PolarCoordinates, ThetaGenerator, ThetaMaze, ThetaTopology};
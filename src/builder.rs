@@ -0,0 +1,457 @@
+//! Fluent construction of mazes
+//!
+//! Every generator has its own configuration fields and post-processing like braiding is done
+//! through separate methods of [`Maze`].
+//! [`MazeBuilder`] combines the most common options behind a single interface so that an
+//! algorithm can be chosen and configured without knowing the specific generator types.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::builder::{Algorithm, MazeBuilder};
+//! use maze_generator::prelude::*;
+//!
+//! let maze = MazeBuilder::new(20, 20)
+//!     .algorithm(Algorithm::Prims)
+//!     .seed([42; 32])
+//!     .goal(GoalPlacement::EdgesOnly)
+//!     .braid(0.2)
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(maze.size, (20, 20));
+//! ```
+
+use std::collections::HashSet;
+
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+
+use crate::ellers_algorithm::EllersGenerator;
+use crate::growing_tree::{GrowingTreeGenerator, GrowingTreeSelectionMethod};
+use crate::prelude::*;
+use crate::prims_algorithm::PrimsGenerator;
+use crate::recursive_backtracking::RbGenerator;
+
+/// Number of mazes which [`MazeBuilder`] generates at most for satisfying a minimum solution
+/// length
+const DEFAULT_MAX_ATTEMPTS: usize = 100;
+
+/// All generation algorithms for rectangular mazes
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Algorithm {
+    /// [`RbGenerator`]
+    #[default]
+    RecursiveBacktracking,
+    /// [`GrowingTreeGenerator`]
+    GrowingTree,
+    /// [`PrimsGenerator`]
+    Prims,
+    /// [`EllersGenerator`]
+    Ellers,
+}
+
+impl Algorithm {
+    /// Every variant, e.g. for letting users choose between them
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::RecursiveBacktracking,
+        Algorithm::GrowingTree,
+        Algorithm::Prims,
+        Algorithm::Ellers,
+    ];
+
+    /// Create the generator of this algorithm with its default configuration
+    #[must_use]
+    pub fn generator(self, seed: Option<Seed>) -> Box<dyn Generator> {
+        match self {
+            Algorithm::RecursiveBacktracking => Box::new(RbGenerator::new(seed)),
+            Algorithm::GrowingTree => Box::new(GrowingTreeGenerator::new(seed)),
+            Algorithm::Prims => Box::new(PrimsGenerator::new(seed)),
+            Algorithm::Ellers => Box::new(EllersGenerator::new(seed)),
+        }
+    }
+
+    /// Create the generator of this algorithm configured for `texture`, see [`Texture`] for
+    /// how its parameters are mapped to the algorithm
+    ///
+    /// The [`dead_ends`](Texture::dead_ends) are not handled by generators, use
+    /// [`MazeBuilder::texture()`] for them.
+    #[must_use]
+    pub fn generator_with_texture(
+        self,
+        seed: Option<Seed>,
+        texture: &Texture,
+    ) -> Box<dyn Generator> {
+        match self {
+            Algorithm::RecursiveBacktracking => {
+                let mut generator = RbGenerator::new(seed);
+                generator.windiness = texture.windiness;
+                Box::new(generator)
+            }
+            Algorithm::GrowingTree => {
+                let mut generator = GrowingTreeGenerator::new(seed);
+                if let Some(branchiness) = texture.branchiness {
+                    generator.selection_method = GrowingTreeSelectionMethod::Mixed(branchiness);
+                }
+                Box::new(generator)
+            }
+            Algorithm::Prims => Box::new(PrimsGenerator::new(seed)),
+            Algorithm::Ellers => {
+                let mut generator = EllersGenerator::new(seed);
+                if let Some(windiness) = texture.windiness {
+                    let windiness = windiness.clamp(0.0, 1.0);
+                    generator.horizontal_join_chance = 1.0 - windiness / 2.0;
+                    generator.downward_connection_chance = windiness / 2.0;
+                }
+                Box::new(generator)
+            }
+        }
+    }
+}
+
+/// Generate a maze using the default configuration of `algorithm`
+///
+/// This is the shortest way of getting a maze without choosing a generator type, use
+/// [`MazeBuilder`] for configuring it further.
+///
+/// # Examples
+/// ```
+/// use maze_generator::Algorithm;
+///
+/// let maze = maze_generator::generate(Algorithm::Ellers, 10, 5, Some([42; 32])).unwrap();
+/// assert_eq!(maze.size, (10, 5));
+/// ```
+pub fn generate(
+    algorithm: Algorithm,
+    width: i32,
+    height: i32,
+    seed: Option<Seed>,
+) -> Result<Maze, MazeError> {
+    algorithm.generator(seed).generate(width, height)
+}
+
+/// Configures and generates a single maze, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MazeBuilder {
+    width: i32,
+    height: i32,
+    algorithm: Algorithm,
+    seed: Option<Seed>,
+    goal: GoalPlacement,
+    braid: f64,
+    texture: Texture,
+    mask: HashSet<Coordinates>,
+    min_solution_length: usize,
+    max_attempts: usize,
+}
+
+impl MazeBuilder {
+    /// Start configuring a maze of the given size
+    ///
+    /// By default it is generated by [`Algorithm::RecursiveBacktracking`] from entropy, the
+    /// algorithm decides where start and goal are placed and the maze has no loops.
+    #[must_use]
+    pub fn new(width: i32, height: i32) -> Self {
+        MazeBuilder {
+            width,
+            height,
+            algorithm: Algorithm::default(),
+            seed: None,
+            goal: GoalPlacement::default(),
+            braid: 0.0,
+            texture: Texture::default(),
+            mask: HashSet::new(),
+            min_solution_length: 0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    /// Set the algorithm which carves the passages
+    #[must_use]
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Set the seed from which all randomness is derived so that the same maze is built every
+    /// time
+    #[must_use]
+    pub fn seed(mut self, seed: Seed) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set where start and goal are placed
+    #[must_use]
+    pub fn goal(mut self, goal: GoalPlacement) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Set the chance with which each dead end is removed, see [`Maze::braid()`]
+    #[must_use]
+    pub fn braid(mut self, chance: f64) -> Self {
+        self.braid = chance;
+        self
+    }
+
+    /// Set the desired texture of the maze independently of the algorithm, see [`Texture`]
+    ///
+    /// If [`dead_ends`](Texture::dead_ends) is set, it replaces the chance of
+    /// [braiding](MazeBuilder::braid) with `1 - dead_ends`.
+    #[must_use]
+    pub fn texture(mut self, texture: Texture) -> Self {
+        if let Some(dead_ends) = texture.dead_ends {
+            self.braid = 1.0 - dead_ends.clamp(0.0, 1.0);
+        }
+        self.texture = texture;
+        self
+    }
+
+    /// Set fields which are left out of the maze, see
+    /// [`RbGenerator::generate_with_obstacles()`]
+    ///
+    /// Only [`Algorithm::RecursiveBacktracking`] supports masks.
+    /// With the `image` feature, masks can also be loaded from images via
+    /// `Mask::from_png_file()`.
+    #[must_use]
+    pub fn mask(mut self, mask: impl Into<HashSet<Coordinates>>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+
+    /// Set the minimum number of fields on the route from start to goal, including both of
+    /// them like [`DifficultyScore::solution_length`]
+    ///
+    /// If the solution of a maze is too short, start and goal are first moved to the ends of
+    /// its [longest path](Maze::longest_path) as long as the goal placement is
+    /// [`GoalPlacement::Anywhere`].
+    /// If that does not suffice either, another maze is generated from a seed derived from the
+    /// configured one, up to [`max_attempts`](MazeBuilder::max_attempts) times.
+    #[must_use]
+    pub fn min_solution_length(mut self, length: usize) -> Self {
+        self.min_solution_length = length;
+        self
+    }
+
+    /// Set how many mazes are generated at most for satisfying the
+    /// [minimum solution length](MazeBuilder::min_solution_length), default: 100
+    #[must_use]
+    pub fn max_attempts(mut self, attempts: usize) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Generate the configured maze
+    ///
+    /// Besides the errors of the generator itself this fails with
+    /// [`MazeError::MaskNotSupported`] if a mask is combined with an algorithm which can not
+    /// carve around it and with [`DifficultyError::AttemptsExhausted`] if no maze with the
+    /// [minimum solution length](MazeBuilder::min_solution_length) could be generated.
+    pub fn build(&self) -> Result<Maze, MazeError> {
+        ensure_valid_size(self.width, self.height)?;
+        // unseeded mazes get a random seed so that they can be reproduced from their provenance
+        let seed = self.seed.unwrap_or_else(random);
+
+        // further attempts use seeds derived from the first one so that the result stays
+        // deterministic, the seed of the returned maze is recorded in its provenance
+        let mut seeds = ChaChaRng::from_seed(seed);
+        seeds.set_stream(2);
+        let mut attempt_seed = seed;
+        for _ in 0..self.max_attempts {
+            let maze = self.build_from(attempt_seed)?;
+            if solution_length(&maze) >= self.min_solution_length {
+                return Ok(maze);
+            }
+            attempt_seed = seeds.gen();
+        }
+
+        Err(DifficultyError::AttemptsExhausted(self.max_attempts).into())
+    }
+
+    /// Generate and post-process a single maze from `seed`
+    fn build_from(&self, seed: Seed) -> Result<Maze, MazeError> {
+        let mut maze = if self.mask.is_empty() {
+            self.algorithm
+                .generator_with_texture(Some(seed), &self.texture)
+                .generate(self.width, self.height)?
+        } else if self.algorithm == Algorithm::RecursiveBacktracking {
+            RbGenerator::new(Some(seed)).generate_with_obstacles(
+                self.width,
+                self.height,
+                &self.mask,
+            )?
+        } else {
+            return Err(MazeError::MaskNotSupported(self.algorithm));
+        };
+        self.goal.apply(&mut maze);
+
+        if self.braid > 0.0 {
+            let mut rng = ChaChaRng::from_seed(seed);
+            // use a different stream than the generator so that braiding is independent of it
+            rng.set_stream(1);
+            maze.braid(self.braid, &mut rng);
+        }
+
+        if self.goal == GoalPlacement::Anywhere && solution_length(&maze) < self.min_solution_length
+        {
+            let (start, goal, _) = maze.longest_path();
+            maze.start = start;
+            maze.goal = goal;
+        }
+
+        if let Some(provenance) = &mut maze.provenance {
+            // the post-processing is part of how the maze was made
+            provenance.options_hash = hash_options(&(
+                provenance.options_hash,
+                self.goal,
+                self.braid,
+                self.min_solution_length,
+            ));
+        }
+
+        Ok(maze)
+    }
+}
+
+/// Number of fields on the route from start to goal, `0` if there is none
+fn solution_length(maze: &Maze) -> usize {
+    maze.find_path(maze.start, maze.goal)
+        .map_or(0, |path| path.len())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{generate, Algorithm, MazeBuilder};
+    use crate::prelude::{
+        Coordinates, DifficultyError, GoalPlacement, MazeError, ObstacleError, Texture,
+    };
+
+    #[test]
+    fn test_builder_matches_generator() -> anyhow::Result<()> {
+        for algorithm in Algorithm::ALL.iter() {
+            let built = MazeBuilder::new(7, 5)
+                .algorithm(*algorithm)
+                .seed([19; 32])
+                .build()?;
+            let generated = algorithm.generator(Some([19; 32])).generate(7, 5)?;
+            assert_eq!(built, generated);
+            assert_eq!(generate(*algorithm, 7, 5, Some([19; 32]))?, generated);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_options() -> anyhow::Result<()> {
+        let builder = MazeBuilder::new(8, 8)
+            .algorithm(Algorithm::GrowingTree)
+            .seed([20; 32])
+            .goal(GoalPlacement::EdgesOnly);
+        let perfect = builder.build()?;
+        let braided = builder.clone().braid(1.0).build()?;
+        assert!(perfect.is_perfect());
+        assert!(braided.dead_ends().is_empty());
+        assert_eq!((braided.start, braided.goal), (perfect.start, perfect.goal));
+        assert_eq!(braided, builder.braid(1.0).build()?);
+
+        let mask: HashSet<_> = [Coordinates::new(1, 1)].iter().copied().collect();
+        let masked = MazeBuilder::new(3, 3).mask(mask.clone()).build()?;
+        assert_eq!(masked.passage_count(Coordinates::new(1, 1)), 0);
+        assert_eq!(
+            MazeBuilder::new(3, 3)
+                .algorithm(Algorithm::Ellers)
+                .mask(mask)
+                .build(),
+            Err(MazeError::MaskNotSupported(Algorithm::Ellers))
+        );
+
+        let full: HashSet<_> = [Coordinates::new(0, 0)].iter().copied().collect();
+        assert_eq!(
+            MazeBuilder::new(1, 1).mask(full).build(),
+            Err(MazeError::Obstacles(ObstacleError::NoFreeFields))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_texture_is_mapped_to_algorithms() -> anyhow::Result<()> {
+        let histogram = |algorithm: Algorithm, texture: Texture| -> anyhow::Result<_> {
+            let maze = MazeBuilder::new(30, 30)
+                .algorithm(algorithm)
+                .seed([25; 32])
+                .texture(texture)
+                .build()?;
+            Ok(maze.corridor_histogram())
+        };
+        let windiness = |windiness| Texture {
+            windiness: Some(windiness),
+            ..Texture::default()
+        };
+        let branchiness = |branchiness| Texture {
+            branchiness: Some(branchiness),
+            ..Texture::default()
+        };
+
+        for algorithm in [Algorithm::RecursiveBacktracking, Algorithm::Ellers].iter() {
+            let straight = histogram(*algorithm, windiness(0.0))?;
+            let winding = histogram(*algorithm, windiness(1.0))?;
+            assert!(straight.mean_run_length() > winding.mean_run_length());
+        }
+        let few_branches = histogram(Algorithm::GrowingTree, branchiness(0.0))?;
+        let many_branches = histogram(Algorithm::GrowingTree, branchiness(1.0))?;
+        assert!(few_branches.junction_ratio() < many_branches.junction_ratio());
+        assert!(few_branches.dead_end_ratio() < many_branches.dead_end_ratio());
+
+        // unsupported parameters are ignored
+        assert_eq!(
+            histogram(Algorithm::Prims, windiness(0.0))?,
+            histogram(Algorithm::Prims, Texture::default())?
+        );
+
+        let no_dead_ends = Texture {
+            dead_ends: Some(0.0),
+            ..Texture::default()
+        };
+        for algorithm in Algorithm::ALL.iter() {
+            assert_eq!(histogram(*algorithm, no_dead_ends)?.degrees[1], 0);
+            let perfect = MazeBuilder::new(8, 8)
+                .algorithm(*algorithm)
+                .texture(Texture::default())
+                .build()?;
+            assert!(perfect.is_perfect());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_solution_length() -> anyhow::Result<()> {
+        for goal in [GoalPlacement::Anywhere, GoalPlacement::EdgesOnly].iter() {
+            let builder = MazeBuilder::new(8, 8)
+                .algorithm(Algorithm::Prims)
+                .seed([24; 32])
+                .goal(*goal)
+                .braid(0.3)
+                .min_solution_length(20);
+            let maze = builder.build()?;
+            let solution = maze.find_path(maze.start, maze.goal).unwrap_or_default();
+            assert!(solution.len() >= 20);
+            assert_eq!(builder.build()?, maze);
+
+            // the seed of the attempt which succeeded reproduces the maze
+            let seed = maze.provenance().and_then(|p| p.seed).unwrap_or_default();
+            assert_eq!(builder.clone().seed(seed).max_attempts(1).build()?, maze);
+        }
+
+        assert_eq!(
+            MazeBuilder::new(3, 3)
+                .min_solution_length(10)
+                .max_attempts(5)
+                .build(),
+            Err(MazeError::Difficulty(DifficultyError::AttemptsExhausted(5)))
+        );
+        Ok(())
+    }
+}
@@ -1,14 +1,20 @@
 //! Ellers algorithm implementation
 //!
-//! Fast algorithm for generating arbitrarily large mazes in linear time.
+//! Algorithm for generating arbitrarily large mazes in linear time.
+//!
+//! Since only the current row has to be remembered, mazes can be streamed row by row via
+//! [`EllersGenerator::generate_streaming()`] with memory usage that does not grow with their
+//! height.
+//! When generating a whole [`Maze`] however, it is not faster than the
+//! other algorithms, see the [comparison](crate#choosing-an-algorithm).
 //!
 //! # Algorithm rundown
 //! 1. Initialize the fields of the first row to each exist in its own set.
 //! 2. Randomly join fields but only if they are not already in the same set.
-//!     When joining, merge the two sets (which indicates that the cells are now connected)
+//!    When joining, merge the two sets (which indicates that the cells are now connected)
 //! 3. For each set, randomly create vertical connections downward to the next row.
-//!     Each set must have at least one vertical connection created in this way.
-//!     The cells in the next row share the same set because they are connected.
+//!    Each set must have at least one vertical connection created in this way.
+//!    The cells in the next row share the same set because they are connected.
 //! 4. Flesh out the next row by creating sets for the fields not already vertically connected.
 //! 5. Repeat from *2.* until the last row is reached
 //! 6. For the last row, join all adjacent cells which do not yet share a set.
@@ -25,7 +31,7 @@
 //!     ```
 //!
 //! 2. Next, we randomly join adjacent fields that belong to different sets.
-//!     The fields so joined also are merged into the same set:
+//!    The fields so joined also are merged into the same set:
 //!
 //!     ```text
 //!     ·-·-·-·-·-·
@@ -55,7 +61,7 @@
 //!     ```
 //!
 //! 5. Now, we just repeat the previous steps on our new row.
-//!     We randomly connect adjacent sets that do not share a set. Something like this:
+//!    We randomly connect adjacent sets that do not share a set. Something like this:
 //!
 //!     ```text
 //!     ·-·-·-·-·-·
@@ -149,24 +155,121 @@
 //! [Jamis Buck's Buckblog](http://weblog.jamisbuck.org/2010/12/29/maze-generation-eller-s-algorithm.html)*
 //!
 
-use std::collections::{BTreeSet, HashSet, VecDeque};
-
-use anyhow::{Context, Result};
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 
+use crate::builder::Algorithm;
 use crate::prelude::*;
 
-const HORIZONTAL_JOIN_CHANCE: f64 = 0.5;
+const DEFAULT_HORIZONTAL_JOIN_CHANCE: f64 = 0.5;
+const DEFAULT_DOWNWARD_CONNECTION_CHANCE: f64 = 0.5;
+
+/// Union-find (disjoint-set) structure which tracks the sets of the fields in a single row.
+///
+/// Fields are identified by their column index.
+#[derive(Debug, Clone, Default)]
+struct RowSets {
+    parents: Vec<usize>,
+}
+
+impl RowSets {
+    /// Create a row of `width` fields which are each in their own set
+    fn new(width: usize) -> Self {
+        RowSets {
+            parents: (0..width).collect(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Find the representative of the set containing column `x`
+    fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            // path halving keeps the trees flat
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    /// Merge the sets containing columns `a` and `b`.
+    ///
+    /// Returns whether the two columns were in different sets before.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b {
+            return false;
+        }
+        // always keep the leftmost column as representative so that iteration order is stable
+        self.parents[root_a.max(root_b)] = root_a.min(root_b);
+        true
+    }
+}
+
+/// Walls of a single row of a maze generated by [`EllersGenerator::generate_streaming()`],
+/// `true` meaning that a wall exists.
+///
+/// The walls to the north and west of a field are described by the previous row and the field
+/// to the left, the outer borders of the maze are always closed.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct RowWalls {
+    /// Index of the row, starting at 0 for the northernmost one
+    pub y: i32,
+    /// Whether a wall exists to the east of each field, always `true` for the last field
+    pub east: Vec<bool>,
+    /// Whether a wall exists to the south of each field, always `true` in the last row
+    pub south: Vec<bool>,
+}
 
-type EllersSet = BTreeSet<Coordinates>;
+impl RowWalls {
+    /// Row `y` with a width of `width` fields which are enclosed by walls on all sides
+    fn closed(y: i32, width: usize) -> Self {
+        RowWalls {
+            y,
+            east: vec![true; width],
+            south: vec![true; width],
+        }
+    }
+
+    /// Carve the passages of this row into `maze`
+    fn carve_into(&self, maze: &mut Maze) {
+        for (i_x, (east, south)) in self.east.iter().zip(self.south.iter()).enumerate() {
+            let coordinates = Coordinates::new(i_x as i32, self.y);
+            if !east {
+                maze.add_passage(coordinates, coordinates.next(&Direction::East));
+            }
+            if !south {
+                maze.add_passage(coordinates, coordinates.next(&Direction::South));
+            }
+        }
+    }
+}
 
 /// [`Generator`] implementation which uses Ellers algorithm.
 #[derive(Debug, Clone)]
 pub struct EllersGenerator {
+    seed: Seed,
     rng: ChaChaRng,
-    sets: Vec<EllersSet>,
-    graph: MazeGraph,
+    /// Where start and goal are placed, default: [`GoalPlacement::Anywhere`]
+    pub goal_placement: GoalPlacement,
+    /// Chance with which two adjacent fields of a row are joined, in range `0..=1`, default:
+    /// `0.5`
+    ///
+    /// Higher chances carve long horizontal corridors while lower ones result in mostly
+    /// vertical passages.
+    pub horizontal_join_chance: f64,
+    /// Chance with which every field of a set is connected to the next row in addition to the
+    /// one field which connects each set, in range `0..=1`, default: `0.5`
+    ///
+    /// Higher chances result in more vertical passages.
+    /// At `0` every set is connected downwards through exactly one field, which is how mazes
+    /// were generated before version 2 of
+    /// [`CRATE_MAZE_FORMAT_VERSION`](crate::CRATE_MAZE_FORMAT_VERSION).
+    pub downward_connection_chance: f64,
+    weight_map: Option<WeightMap>,
+    sets: RowSets,
 }
 
 impl EllersGenerator {
@@ -175,215 +278,301 @@ impl EllersGenerator {
     /// Optionally a 32 bit seed can be provided to seed the internal random generator.
     /// Giving a seed results in identical mazes being generated while omitting it sources the
     /// random generator from entropy.
-    pub fn new(seed: Option<[u8; 32]>) -> Self {
+    #[must_use]
+    pub fn new(seed: Option<Seed>) -> Self {
+        // the seed is remembered so that it can be recorded in the provenance of the mazes
+        let seed = seed.unwrap_or_else(random);
         EllersGenerator {
-            rng: match seed {
-                None => ChaChaRng::from_entropy(),
-                Some(seed) => ChaChaRng::from_seed(seed),
-            },
-            sets: Vec::new(),
-            graph: MazeGraph::new(),
+            seed,
+            rng: RngCheckpoint::seeded(seed),
+            goal_placement: GoalPlacement::default(),
+            horizontal_join_chance: DEFAULT_HORIZONTAL_JOIN_CHANCE,
+            downward_connection_chance: DEFAULT_DOWNWARD_CONNECTION_CHANCE,
+            weight_map: None,
+            sets: RowSets::default(),
         }
     }
 
-    /// Join the containing sets of two given fields.
+    /// Prefer carving into fields with a higher weight in `map`.
     ///
-    /// Only changes anything if the two fields are not already in the same set.
-    /// Also keeps track of the connection in `self.graph`.
-    fn join_sets_of_fields(&mut self, field1: Coordinates, field2: Coordinates) -> Result<()> {
-        let set1 = self
-            .sets
-            .iter()
-            .find(|set| set.contains(&field1))
-            .ok_or_else(|| {
-                GenericGeneratorError::InternalError(format!(
-                    "Expected to find coordinates {}",
-                    field1
-                ))
-            })?;
-        let set2 = self
-            .sets
-            .iter()
-            .find(|set| set.contains(&field2))
-            .ok_or_else(|| {
-                GenericGeneratorError::InternalError(format!(
-                    "Expected to find coordinates {}",
-                    field2
-                ))
-            })?;
-
-        if set1 != set2 {
-            let index1 = self
-                .sets
-                .iter()
-                .position(|set| set == set1)
-                .ok_or_else(|| {
-                    GenericGeneratorError::InternalError(String::from(
-                        "Could not determine position of set",
-                    ))
-                })?;
-            let index2 = self
-                .sets
-                .iter()
-                .position(|set| set == set2)
-                .ok_or_else(|| {
-                    GenericGeneratorError::InternalError(String::from(
-                        "Could not determine position of set",
-                    ))
-                })?;
-
-            self.sets[index1] = set1.union(set2).cloned().collect();
-            self.sets[index2] = EllersSet::new();
-            self.graph.add_edge(field1, field2, ());
-        }
+    /// Since Eller's algorithm never chooses between fields but only decides whether to carve
+    /// into the next one, the weight of a field multiplies the odds of joining it to its western
+    /// neighbour with the [`horizontal_join_chance`](EllersGenerator::horizontal_join_chance)
+    /// and of connecting it to the field above with the
+    /// [`downward_connection_chance`](EllersGenerator::downward_connection_chance).
+    /// The one field through which every set has to be connected downwards is chosen
+    /// proportionally to the weights.
+    #[must_use]
+    pub fn with_weight_map(mut self, map: WeightMap) -> Self {
+        self.weight_map = Some(map);
+        self
+    }
 
+    /// Generate a maze row by row and pass every finished row to `sink` without ever holding
+    /// the whole maze in memory.
+    ///
+    /// Only the set membership of the current row and the walls of the row which is being
+    /// carved are kept, so the memory usage depends on `width` but not on `height`.
+    /// This allows generating mazes which are far too large to fit into memory, e.g. for
+    /// writing them into a file while they are generated.
+    /// Start and goal are not placed because doing so requires the whole maze.
+    ///
+    /// The passages are identical to those of [`generate()`](Generator::generate) with the
+    /// same seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::ellers_algorithm::EllersGenerator;
+    /// use maze_generator::prelude::*;
+    ///
+    /// let mut rows = Vec::new();
+    /// EllersGenerator::new(Some([42; 32]))
+    ///     .generate_streaming(4, 1_000, |row| rows.push(row.east.iter().filter(|w| !**w).count()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(rows.len(), 1_000);
+    /// // the last row joins all fields which are not yet connected
+    /// assert!(rows[999] > 0);
+    /// ```
+    pub fn generate_streaming(
+        &mut self,
+        width: i32,
+        height: i32,
+        sink: impl FnMut(RowWalls),
+    ) -> Result<(), MazeError> {
+        ensure_valid_size(width, height)?;
+        self.generate_rows(width, height, sink);
         Ok(())
     }
 
-    /// Initialize the fields of the first row to each exist in its own set.
-    fn init_fields_first_row(&mut self, width: i32) {
-        self.sets = (0..width)
-            .map(|x| {
-                let mut set = EllersSet::new();
-                set.insert(Coordinates::new(x, 0));
-                set
-            })
-            .collect();
+    /// Provenance of a maze which is about to be generated, optionally from a given start
+    fn provenance(&self, start: Option<Coordinates>) -> Provenance {
+        let options = (
+            self.goal_placement,
+            self.horizontal_join_chance,
+            self.downward_connection_chance,
+            self.weight_map.as_ref().map(WeightMap::fingerprint),
+            start,
+        );
+        Provenance::new(Algorithm::Ellers, self.seed, &self.rng, &options)
+    }
+
+    /// Generate all rows of a maze with a valid size and pass them to `sink` in order
+    fn generate_rows(&mut self, width: i32, height: i32, mut sink: impl FnMut(RowWalls)) {
+        // Initialize the fields of the first row to each exist in its own set.
+        self.sets = RowSets::new(width as usize);
+        for y in 0..(height - 1) {
+            let mut row = RowWalls::closed(y, width as usize);
+            self.randomly_join_fields(&mut row);
+            self.create_downward_connections(&mut row);
+            sink(row);
+        }
+        let mut row = RowWalls::closed(height - 1, width as usize);
+        self.join_last_rows(&mut row);
+        sink(row);
+    }
+
+    /// Join the containing sets of two adjacent fields in the current row.
+    ///
+    /// Only changes anything if the two fields are not already in the same set.
+    /// Also removes the wall between them from `row`.
+    fn join_sets_of_fields(&mut self, row: &mut RowWalls, x1: usize, x2: usize) {
+        if self.sets.union(x1, x2) {
+            row.east[x1.min(x2)] = false;
+        }
     }
 
     /// Randomly join fields but only if they are not already in the same set.
     /// When joining, merge the two sets (which indicates that the cells are now connected)
-    fn randomly_join_fields(&mut self, current_y: i32) -> Result<()> {
+    fn randomly_join_fields(&mut self, row: &mut RowWalls) {
         // iterate over all fields and randomly join them with the field on the right
+        let chance = self.horizontal_join_chance.clamp(0.0, 1.0);
         for i_x in 0..(self.sets.len() - 1) {
-            if self.rng.gen_bool(HORIZONTAL_JOIN_CHANCE) {
-                self.join_sets_of_fields(
-                    (i_x as i32, current_y).into(),
-                    (i_x as i32 + 1, current_y).into(),
-                )
-                .with_context(|| "Could not randomly join fields")?;
+            let east = Coordinates::new(i_x as i32 + 1, row.y);
+            if self.rng.gen_bool(self.weighted_chance(chance, east)) {
+                self.join_sets_of_fields(row, i_x, i_x + 1);
             }
         }
-
-        Ok(())
     }
 
     /// For each set, randomly create vertical connections downward to the next row.
     /// Each set must have at least one vertical connection created in this way.
     /// The cells in the next row share the same set because they are connected.
-    fn create_downward_connections(&mut self, current_y: i32) {
-        for i_set in &mut self.sets {
-            if !i_set.is_empty() {
-                // filter to those fields which are located on the bottom-most row
-                let bottom_most_fields: Vec<_> =
-                    i_set.iter().filter(|c| c.y == current_y).cloned().collect();
-
-                // how many downward connections should be added
-                let count = if !bottom_most_fields.is_empty() {
-                    1
-                } else {
-                    self.rng.gen_range(1, bottom_most_fields.len())
-                };
-
-                for coordinates in bottom_most_fields.choose_multiple(&mut self.rng, count) {
-                    let next_coords = coordinates.next(&Direction::South);
-                    i_set.insert(next_coords);
-                    self.graph.add_edge(*coordinates, next_coords, ());
-                }
-            }
+    ///
+    /// This also fleshes out the next row by creating sets for the fields not already
+    /// vertically connected.
+    fn create_downward_connections(&mut self, row: &mut RowWalls) {
+        let width = self.sets.len();
+
+        // Group the columns of the current row by the set they belong to.
+        // The leftmost column of every set is its representative, so the groups are visited in
+        // the order of their leftmost columns and list their columns from left to right.
+        // The random numbers are therefore always drawn in the same order, no matter in which
+        // order the sets were joined.
+        let mut members = vec![Vec::new(); width];
+        for i_x in 0..width {
+            members[self.sets.find(i_x)].push(i_x);
         }
-    }
 
-    /// Flesh out the next row by creating sets for the fields not already vertically connected.
-    fn flesh_out_next_row(&mut self, current_y: i32) -> Result<()> {
-        let next_y = current_y + 1;
-        // add the coordinate to its set if not already present
-        for i_x in 0..self.sets.len() {
-            let coordinates = (i_x as i32, next_y).into();
-
-            if !self.sets.iter().any(|set| set.contains(&coordinates)) {
-                self.sets
-                    .iter_mut()
-                    .find(|set| set.is_empty())
-                    .ok_or_else(|| {
-                        GenericGeneratorError::InternalError(String::from("No empty set found"))
-                    })?
-                    .insert(coordinates);
+        let mut next_sets = RowSets::new(width);
+        for columns in members.iter().filter(|columns| !columns.is_empty()) {
+            let connected = self.choose_downward_connections(row.y + 1, columns);
+            for i_x in connected.iter() {
+                row.south[*i_x] = false;
+            }
+            for pair in connected.windows(2) {
+                next_sets.union(pair[0], pair[1]);
             }
         }
 
-        Ok(())
+        self.sets = next_sets;
     }
 
-    /// For the last row, join all adjacent cells which do not yet share a set.
-    fn join_last_rows(&mut self, current_y: i32) -> Result<()> {
-        for i_x in 0..(self.sets.len() - 1) {
-            self.join_sets_of_fields(
-                (i_x as i32, current_y).into(),
-                (i_x as i32 + 1, current_y).into(),
-            )
-            .with_context(|| "Could not join last rows")?;
+    /// Choose the columns through which the set consisting of `columns` is connected to the
+    /// next row, ordered from left to right
+    ///
+    /// One random column is always connected and every other column with the
+    /// [`downward_connection_chance`](EllersGenerator::downward_connection_chance).
+    /// `next_y` is the index of the row which is connected to.
+    fn choose_downward_connections(&mut self, next_y: i32, columns: &[usize]) -> Vec<usize> {
+        let below = |i_x: usize| Coordinates::new(i_x as i32, next_y);
+        let i_guaranteed = match &self.weight_map {
+            Some(map) => {
+                let indices: Vec<usize> = (0..columns.len()).collect();
+                let weighted = indices
+                    .choose_weighted(&mut self.rng, |i_column| {
+                        map.weight(&below(columns[*i_column]))
+                    })
+                    .ok()
+                    .copied();
+                // all weights being zero is treated like no weight map at all
+                weighted.unwrap_or_else(|| gen_index(&mut self.rng, columns.len()))
+            }
+            // drawn from a `u32` range like `SliceRandom::choose_multiple()` which was used
+            // before, so that a chance of zero keeps producing the same mazes
+            None => self.rng.gen_range(0, columns.len() as u32) as usize,
+        };
+        let chance = self.downward_connection_chance.clamp(0.0, 1.0);
+        if chance <= 0.0 {
+            return vec![columns[i_guaranteed]];
         }
 
-        Ok(())
+        let mut connected = Vec::with_capacity(columns.len());
+        for (i_column, i_x) in columns.iter().enumerate() {
+            if i_column == i_guaranteed
+                || self.rng.gen_bool(self.weighted_chance(chance, below(*i_x)))
+            {
+                connected.push(*i_x);
+            }
+        }
+        connected
     }
 
-    fn find_suitable_goal(&self, start: Coordinates) -> Coordinates {
-        // do breadth-first search for the field which has the most distance
-        let mut already_visited = HashSet::new();
-        let mut queue: VecDeque<Coordinates> = self.graph.neighbors(start).collect();
-        let mut last_coords = start;
-
-        while let Some(i_coords) = queue.pop_front() {
-            queue.extend(
-                self.graph
-                    .neighbors(i_coords)
-                    .filter(|c| !already_visited.contains(c)),
-            );
-            already_visited.insert(i_coords);
-            last_coords = i_coords;
+    /// Chance with which to carve into the field at `coordinates`, given the unweighted `chance`
+    ///
+    /// The weight of the field multiplies the odds of carving, so that a weight of `0` never
+    /// carves and a weight of `1` keeps `chance` as it is.
+    fn weighted_chance(&self, chance: f64, coordinates: Coordinates) -> f64 {
+        let weight = match &self.weight_map {
+            Some(map) => map.weight(&coordinates),
+            None => return chance,
+        };
+        if weight <= 0.0 || chance <= 0.0 {
+            return 0.0;
         }
+        if weight.is_infinite() {
+            return 1.0;
+        }
+        let carve = chance * weight;
+        (carve / (carve + (1.0 - chance))).clamp(0.0, 1.0)
+    }
 
-        last_coords
+    /// For the last row, join all adjacent cells which do not yet share a set.
+    fn join_last_rows(&mut self, row: &mut RowWalls) {
+        for i_x in 0..(self.sets.len() - 1) {
+            self.join_sets_of_fields(row, i_x, i_x + 1);
+        }
     }
 }
 
 impl Generator for EllersGenerator {
-    fn generate(&mut self, width: i32, height: i32) -> Result<Maze> {
-        self.graph = MazeGraph::with_capacity((width * height) as usize, 0);
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        self.generate_into(&mut maze)?;
+        Ok(maze)
+    }
 
-        self.init_fields_first_row(width);
-        for y in 0..(height - 1) {
-            self.randomly_join_fields(y)
-                .with_context(|| "Could not generate maze")?;
-            self.create_downward_connections(y);
-            self.flesh_out_next_row(y)
-                .with_context(|| "Could not generate maze")?;
-        }
-        self.join_last_rows(height - 1)
-            .with_context(|| "Could not generate maze")?;
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        ensure_valid_size(maze.size.0, maze.size.1)?;
+        maze.reset();
+        maze.provenance = Some(self.provenance(None));
+        let (width, height) = maze.size;
+
+        self.generate_rows(width, height, |row| row.carve_into(maze));
 
-        // convert hashset representation to final maze
-        let start = (0, 0).into();
-        let goal = (0, 0).into();
-        let mut maze = Maze::new(width, height, start, goal);
-        maze.graph = self.graph.clone();
-        maze.goal = self.find_suitable_goal(start);
+        // place start and goal as far apart as possible
+        let (start, goal, _) = maze.longest_path();
+        maze.start = start;
+        maze.goal = goal;
+        self.goal_placement.apply(maze);
+
+        Ok(())
+    }
+
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        ensure_start_inside(width, height, start)?;
+        let provenance = self.provenance(Some(start));
+        // the maze is generated row by row so it can not spread out from the start
+        let mut maze = self.generate(width, height)?;
+        maze.provenance = Some(provenance);
+        maze.start = start;
+        self.goal_placement.apply_keeping_start(&mut maze);
 
         Ok(maze)
     }
+
+    fn info(&self) -> AlgorithmInfo {
+        AlgorithmInfo::new(
+            "Eller's",
+            "O(n)",
+            "Set membership of the fields in the current row, i.e. O(width)",
+            "Generated row by row with a slight bias towards horizontal passages",
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::prelude::{Coordinates, Direction, Generator};
+    use std::collections::HashSet;
 
-    use super::EllersGenerator;
+    use crate::prelude::{Coordinates, Direction, DisjointSets, Generator, MazeError};
 
-    test_all_coordinates_have_fields!(super::EllersGenerator);
-    test_route_from_start_to_goal_exists!(super::EllersGenerator);
-    test_all_fields_connected!(super::EllersGenerator);
-    test_generation_is_deterministic!(super::EllersGenerator);
+    use super::{EllersGenerator, RowSets};
+
+    test_all_coordinates_have_fields!(EllersGenerator);
+    test_route_from_start_to_goal_exists!(EllersGenerator);
+    test_all_fields_connected!(EllersGenerator);
+    test_generation_is_deterministic!(EllersGenerator);
+    test_generate_into_matches_generate!(EllersGenerator);
+    test_generate_from_keeps_start!(EllersGenerator);
+    test_passages_are_consistent!(EllersGenerator);
+
+    #[test]
+    fn test_goal_differs_from_start_in_two_field_mazes() -> anyhow::Result<()> {
+        for &(width, height) in [(2, 1), (1, 2)].iter() {
+            for seed in 0..4 {
+                let maze = EllersGenerator::new(Some([seed; 32])).generate(width, height)?;
+                assert_ne!(maze.start, maze.goal);
+            }
+        }
+        Ok(())
+    }
 
     #[test]
     fn test_south_passage() -> anyhow::Result<()> {
@@ -405,4 +594,143 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_downward_connection_chance() -> anyhow::Result<()> {
+        let (width, height) = (100, 100);
+        let index = |c: Coordinates| (c.y * width + c.x) as usize;
+        let mut densities = Vec::new();
+        for chance in [0.0, 0.25, 0.5, 0.75, 1.0].iter() {
+            let mut generator = EllersGenerator::new(Some([42; 32]));
+            generator.downward_connection_chance = *chance;
+            let maze = generator.generate(width, height)?;
+            assert!(maze.is_perfect());
+
+            // replay the sets of every row to count the connections beyond the one which each
+            // set needs
+            let mut sets = DisjointSets::new((width * height) as usize);
+            let (mut vertical, mut extra, mut candidates) = (0, 0, 0);
+            for y in 0..height - 1 {
+                let row: Vec<Coordinates> = (0..width).map(|x| Coordinates::new(x, y)).collect();
+                for c in row.iter() {
+                    if maze.has_passage(c, &Direction::East) {
+                        sets.union(index(*c), index(c.next(&Direction::East)));
+                    }
+                }
+                let roots: HashSet<usize> = row.iter().map(|c| sets.find(index(*c))).collect();
+                let connections = row
+                    .iter()
+                    .filter(|c| maze.has_passage(c, &Direction::South))
+                    .count();
+                vertical += connections;
+                extra += connections - roots.len();
+                candidates += row.len() - roots.len();
+                for c in row.iter() {
+                    if maze.has_passage(c, &Direction::South) {
+                        sets.union(index(*c), index(c.next(&Direction::South)));
+                    }
+                }
+            }
+
+            let ratio = extra as f64 / candidates as f64;
+            assert!(
+                (ratio - chance).abs() < 0.03,
+                "{} of {} possible extra connections for chance {}",
+                extra,
+                candidates,
+                chance
+            );
+            densities.push(vertical as f64 / (width * (height - 1)) as f64);
+        }
+
+        for pair in densities.windows(2) {
+            assert!(pair[0] < pair[1], "{:?}", densities);
+        }
+        assert_eq!(densities.last(), Some(&1.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_weight_map_shapes_passages() -> anyhow::Result<()> {
+        use crate::prelude::WeightMap;
+
+        // nothing is carved into the eastern half before the last row except for the one
+        // downward connection which each field needs, so it becomes a row of vertical corridors
+        let (width, height) = (10, 6);
+        let map = WeightMap::from_fn(width, height, |c| if c.x < 5 { 1.0 } else { 0.0 });
+        for seed in 0..3 {
+            let mut generator = EllersGenerator::new(Some([seed; 32])).with_weight_map(map.clone());
+            let maze = generator.generate(width, height)?;
+            assert!(maze.is_perfect());
+
+            for y in 0..height - 1 {
+                for x in 0..width {
+                    let coordinates = Coordinates::new(x, y);
+                    if x >= 4 {
+                        assert!(!maze.has_passage(&coordinates, &Direction::East));
+                    }
+                    if x >= 5 {
+                        assert!(maze.has_passage(&coordinates, &Direction::South));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_matches_generate() -> anyhow::Result<()> {
+        let maze = EllersGenerator::new(Some([23; 32])).generate(9, 7)?;
+
+        let mut rows = Vec::new();
+        EllersGenerator::new(Some([23; 32])).generate_streaming(9, 7, |row| rows.push(row))?;
+        assert_eq!(rows.len(), 7);
+        for (iy, row) in rows.iter().enumerate() {
+            assert_eq!(row.y, iy as i32);
+            for ix in 0..9 {
+                let coordinates = Coordinates::new(ix as i32, row.y);
+                assert_eq!(
+                    row.east[ix],
+                    !maze.has_passage(&coordinates, &Direction::East)
+                );
+                assert_eq!(
+                    row.south[ix],
+                    !maze.has_passage(&coordinates, &Direction::South)
+                );
+            }
+        }
+
+        assert_eq!(
+            EllersGenerator::new(None).generate_streaming(0, 5, |_| {}),
+            Err(MazeError::InvalidDimensions {
+                width: 0,
+                height: 5
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_row_sets_merge_into_the_leftmost_column() {
+        let mut sets = RowSets::new(6);
+        assert_eq!(sets.len(), 6);
+        assert!((0..6).all(|x| sets.find(x) == x));
+
+        assert!(sets.union(4, 5));
+        assert!(sets.union(5, 2));
+        assert!(sets.union(0, 1));
+        assert!(!sets.union(2, 4));
+        assert_eq!(
+            (0..6).map(|x| sets.find(x)).collect::<Vec<_>>(),
+            vec![0, 0, 2, 3, 2, 2]
+        );
+
+        // joining two sets which already consist of several columns merges all of them
+        assert!(sets.union(5, 1));
+        assert!(!sets.union(4, 0));
+        assert_eq!(
+            (0..6).map(|x| sets.find(x)).collect::<Vec<_>>(),
+            vec![0, 0, 0, 3, 0, 0]
+        );
+    }
 }
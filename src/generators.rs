@@ -0,0 +1,163 @@
+//! Registry for selecting generators by name at runtime
+//!
+//! Command line tools and plugins often let the user choose an algorithm by name.
+//! Instead of reimplementing the mapping from names to generators, they can look generators up
+//! here.
+//! All algorithms of this crate are registered by default and downstream crates can add their
+//! own generators via [`register()`].
+//!
+//! # Examples
+//! ```
+//! use maze_generator::generators;
+//!
+//! let mut generator = generators::by_name("prims", Some([42; 32])).unwrap();
+//! let maze = generator.generate(5, 5).unwrap();
+//! assert_eq!(maze.size, (5, 5));
+//!
+//! assert!(generators::by_name("unknown", None).is_none());
+//! ```
+
+use std::sync::{OnceLock, PoisonError, RwLock};
+
+use crate::ellers_algorithm::EllersGenerator;
+use crate::growing_tree::GrowingTreeGenerator;
+use crate::prelude::*;
+use crate::prims_algorithm::PrimsGenerator;
+use crate::recursive_backtracking::RbGenerator;
+
+/// Function which creates a generator from an optional seed
+pub type GeneratorFactory = fn(Option<Seed>) -> Box<dyn Generator>;
+
+/// A registered generator together with all names under which it can be found
+#[derive(Debug, Clone)]
+struct Entry {
+    names: Vec<String>,
+    factory: GeneratorFactory,
+}
+
+fn registry() -> &'static RwLock<Vec<Entry>> {
+    static REGISTRY: OnceLock<RwLock<Vec<Entry>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let builtin: [(&[&str], GeneratorFactory); 4] = [
+            (&["ellers", "eller"], |seed| {
+                Box::new(EllersGenerator::new(seed))
+            }),
+            (&["growing_tree", "growingtree", "growing", "gt"], |seed| {
+                Box::new(GrowingTreeGenerator::new(seed))
+            }),
+            (&["prims", "prim"], |seed| {
+                Box::new(PrimsGenerator::new(seed))
+            }),
+            (&["recursive_backtracking", "rb", "backtracking"], |seed| {
+                Box::new(RbGenerator::new(seed))
+            }),
+        ];
+        RwLock::new(
+            builtin
+                .iter()
+                .map(|(names, factory)| Entry {
+                    names: names.iter().map(|name| name.to_string()).collect(),
+                    factory: *factory,
+                })
+                .collect(),
+        )
+    })
+}
+
+/// Names are matched case-insensitively and `-` can be used in place of `_`
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase().replace('-', "_")
+}
+
+/// Create the generator which is registered under `name`.
+///
+/// Returns `None` if no generator with that name is known.
+#[must_use]
+pub fn by_name(name: &str, seed: Option<Seed>) -> Option<Box<dyn Generator>> {
+    let name = normalize(name);
+    let entries = registry().read().unwrap_or_else(PoisonError::into_inner);
+    entries
+        .iter()
+        .find(|entry| entry.names.contains(&name))
+        .map(|entry| (entry.factory)(seed))
+}
+
+/// Register a generator under `name` so that it can be created via [`by_name()`].
+///
+/// A generator which was previously registered under the same name is replaced, which also
+/// allows overriding the built-in algorithms.
+///
+/// # Examples
+/// ```
+/// use maze_generator::generators;
+/// use maze_generator::growing_tree::{GrowingTreeGenerator, GrowingTreeSelectionMethod};
+///
+/// generators::register("growing_tree_random", |seed| {
+///     let mut generator = GrowingTreeGenerator::new(seed);
+///     generator.selection_method = GrowingTreeSelectionMethod::Random;
+///     Box::new(generator)
+/// });
+///
+/// assert!(generators::by_name("growing_tree_random", None).is_some());
+/// ```
+pub fn register(name: &str, factory: GeneratorFactory) {
+    let name = normalize(name);
+    let mut entries = registry().write().unwrap_or_else(PoisonError::into_inner);
+    for entry in entries.iter_mut() {
+        entry.names.retain(|i_name| i_name != &name);
+    }
+    entries.retain(|entry| !entry.names.is_empty());
+    entries.push(Entry {
+        names: vec![name],
+        factory,
+    });
+}
+
+/// The primary names of all registered generators
+#[must_use]
+pub fn names() -> Vec<String> {
+    let entries = registry().read().unwrap_or_else(PoisonError::into_inner);
+    entries
+        .iter()
+        .filter_map(|entry| entry.names.first().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{by_name, names, register};
+    use crate::prelude::AlgorithmInfo;
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_builtin_generators_are_registered() -> anyhow::Result<()> {
+        for name in names().iter() {
+            let mut generator = by_name(name, Some([1; 32]));
+            assert_ne!(
+                generator.as_ref().map(|g| g.info()),
+                Some(AlgorithmInfo::UNKNOWN)
+            );
+            assert_eq!(
+                generator
+                    .as_mut()
+                    .map(|g| g.generate(4, 3))
+                    .transpose()?
+                    .map(|maze| maze.size),
+                Some((4, 3))
+            );
+        }
+        assert!(by_name("Growing-Tree", None).is_some());
+        assert!(by_name("eller", None).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_register() {
+        assert!(by_name("custom_test_generator", None).is_none());
+        register("custom_test_generator", |seed| {
+            Box::new(PrimsGenerator::new(seed))
+        });
+        assert!(by_name("Custom_Test_Generator", None).is_some());
+        assert!(names().contains(&String::from("custom_test_generator")));
+    }
+}
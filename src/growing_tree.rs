@@ -15,10 +15,14 @@
 //! *Explanation and credits to
 //! [Jamis Buck's Buckblog]( http://weblog.jamisbuck.org/2011/1/27/maze-generation-growing-tree-algorithm.html)*
 
-use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 
+use crate::builder::Algorithm;
 use crate::prelude::*;
 
 /// Different ways in which the next root cell is selected from the stack of possibilities
@@ -30,148 +34,674 @@ pub enum GrowingTreeSelectionMethod {
     MostRecent,
     /// The first cell on the stack.
     First,
+    /// The most recently added cell, but at every step a random one with the given chance in
+    /// range `0..=1`.
+    ///
+    /// `0` behaves like [`MostRecent`](GrowingTreeSelectionMethod::MostRecent) while higher
+    /// chances result in more and shorter branches, up to a texture like Prim's algorithm at `1`.
+    Mixed(f64),
+}
+
+/// Function which picks the next cell from the list of candidates, see
+/// [`GrowingTreeGenerator::with_selector()`]
+type SelectorFn = dyn FnMut(&[Coordinates], &mut ChaChaRng) -> usize + Send;
+
+/// A custom selection policy which is shared between clones of the generator
+#[derive(Clone)]
+struct Selector(Arc<Mutex<SelectorFn>>);
+
+impl fmt::Debug for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Selector")
+    }
 }
 
 /// [`Generator`] implementation which uses the recursive-backtracking algorithm.
 #[derive(Debug, Clone)]
 pub struct GrowingTreeGenerator {
+    seed: Seed,
     rng: ChaChaRng,
     /// The method by which to select the next candidate cell from the available possibilities
     pub selection_method: GrowingTreeSelectionMethod,
-    cell_stack: Vec<Coordinates>,
-    visited: Vec<Coordinates>,
+    /// Where start and goal are placed, default: [`GoalPlacement::Anywhere`]
+    pub goal_placement: GoalPlacement,
+    /// Start carving at a random field instead of the start field, default: false
+    ///
+    /// This removes the bias of the maze texture towards the start field which otherwise lies in
+    /// the north-western corner.
+    /// The position of the start field itself is not affected.
+    pub random_carving_start: bool,
+    /// Preference for horizontal or vertical corridors, default: no preference
+    pub direction_bias: DirectionBias,
+    selector: Option<Selector>,
+    weight_map: Option<WeightMap>,
+    cell_stack: VecDeque<Coordinates>,
     neighbours: Vec<Coordinates>,
 }
 
+/// Progress of carving the passages of a maze which can be paused after every passage
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Carving {
+    rng: RngCheckpoint,
+    /// The field at which carving started, which is the only visited field without passages
+    origin: Coordinates,
+    cell_stack: VecDeque<Coordinates>,
+    current: Coordinates,
+    /// Where `current` is located in `cell_stack`, if it is located there at all
+    current_position: Option<usize>,
+    /// The end of the longest cell stack so far
+    goal: Coordinates,
+    max_q: usize,
+    complete: bool,
+    /// Whether the carving is known to belong to its maze, which is only unknown after it was
+    /// deserialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checked: bool,
+}
+
+impl Carving {
+    /// Whether the carving can be continued on `maze` without panicking
+    fn is_valid_for(&mut self, maze: &Maze) -> bool {
+        if !self.checked {
+            let inside = |coordinates: &Coordinates| maze.are_coordinates_inside(coordinates);
+            self.checked = maze.passages.size() == maze.size
+                && inside(&self.origin)
+                && inside(&self.current)
+                && inside(&self.goal)
+                && self.cell_stack.iter().all(inside)
+                && self
+                    .current_position
+                    .iter()
+                    .all(|idx| *idx < self.cell_stack.len());
+        }
+        self.checked
+    }
+}
+
+/// A maze which a [`GrowingTreeGenerator`] has only partially carved, see
+/// [`GrowingTreeGenerator::start()`]
+///
+/// Pausing makes it possible to spread the generation of huge mazes over multiple sessions.
+/// With the `serde` feature the state can be serialized to continue the generation in another
+/// process or on another machine.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GrowingTreeState {
+    maze: Maze,
+    carving: Carving,
+}
+
+impl GrowingTreeState {
+    /// The maze with all passages carved so far.
+    ///
+    /// Start and goal are only placed once the generation is finished.
+    #[must_use]
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Whether all passages have been carved
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.carving.complete
+    }
+}
+
 impl GrowingTreeGenerator {
     /// Create a new instance.
     ///
     /// Optionally a 32 bit seed can be provided to seed the internal random generator.
     /// Giving a seed results in identical mazes being generated which omitting it sources the
     /// random generator from entropy.
-    pub fn new(seed: Option<[u8; 32]>) -> GrowingTreeGenerator {
+    #[must_use]
+    pub fn new(seed: Option<Seed>) -> GrowingTreeGenerator {
+        // the seed is remembered so that paused generations can restore the random generator
+        let seed = seed.unwrap_or_else(random);
         GrowingTreeGenerator {
-            rng: match seed {
-                None => ChaChaRng::from_entropy(),
-                Some(seed) => ChaChaRng::from_seed(seed),
-            },
+            seed,
+            rng: RngCheckpoint::seeded(seed),
             selection_method: GrowingTreeSelectionMethod::First,
-            cell_stack: Vec::new(),
-            visited: Vec::new(),
+            goal_placement: GoalPlacement::default(),
+            random_carving_start: false,
+            direction_bias: DirectionBias::default(),
+            selector: None,
+            weight_map: None,
+            cell_stack: VecDeque::new(),
             neighbours: Vec::new(),
         }
     }
 
-    /// Core algorithm implementation
+    /// Select the next cell with a custom policy instead of one of the predefined
+    /// [`GrowingTreeSelectionMethod`]s, which then is ignored.
+    ///
+    /// The selector is given the list of candidate cells, ordered from the oldest to the most
+    /// recently added one, together with the random generator of this generator so that
+    /// generation stays deterministic for a given seed.
+    /// It returns the index of the chosen cell.
+    /// Returning an index outside of the list aborts the generation with an error.
+    ///
+    /// Clones of the generator share the same selector.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::growing_tree::GrowingTreeGenerator;
+    /// use rand::Rng;
+    ///
+    /// // mostly behave like the recursive backtracker but sometimes branch off like Prim's
+    /// let mut generator = GrowingTreeGenerator::new(Some([42; 32])).with_selector(|cells, rng| {
+    ///     if rng.gen_bool(0.75) {
+    ///         cells.len() - 1
+    ///     } else {
+    ///         rng.gen_range(0, cells.len())
+    ///     }
+    /// });
+    /// let maze = generator.generate(10, 10).unwrap();
+    /// ```
+    #[must_use]
+    pub fn with_selector(
+        mut self,
+        selector: impl FnMut(&[Coordinates], &mut ChaChaRng) -> usize + Send + 'static,
+    ) -> Self {
+        self.selector = Some(Selector(Arc::new(Mutex::new(selector))));
+        self
+    }
+
+    /// Prefer carving from the selected cell into neighbours with a higher weight in `map`, on
+    /// top of the [`direction_bias`](GrowingTreeGenerator::direction_bias)
+    #[must_use]
+    pub fn with_weight_map(mut self, map: WeightMap) -> Self {
+        self.weight_map = Some(map);
+        self
+    }
+
+    /// Start generating a maze which is carved a limited number of passages at a time via
+    /// [`resume()`](GrowingTreeGenerator::resume).
+    ///
+    /// Once [`finish()`](GrowingTreeGenerator::finish)ed, the maze is the same one which
+    /// [`generate()`](Generator::generate) would have returned, regardless of how often the
+    /// generation was paused.
     ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::growing_tree::GrowingTreeGenerator;
+    ///
+    /// let mut generator = GrowingTreeGenerator::new(Some([42; 32]));
+    /// let mut state = generator.start(20, 20).unwrap();
+    /// while !generator.resume(&mut state, 50).unwrap() {
+    ///     // e.g. save the state or hand it to another worker
+    /// }
+    /// let maze = generator.finish(state).unwrap();
+    ///
+    /// assert_eq!(maze, GrowingTreeGenerator::new(Some([42; 32])).generate(20, 20).unwrap());
+    /// ```
+    pub fn start(&mut self, width: i32, height: i32) -> Result<GrowingTreeState, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        maze.provenance = Some(self.provenance(None));
+        let carving_start = self.carving_start(&maze);
+        Ok(GrowingTreeState {
+            carving: self.begin_carving(carving_start),
+            maze,
+        })
+    }
+
+    /// Carve at most `passages` further passages of a paused generation.
     ///
-    /// Returns coordinates of the goal field
-    fn carve_passages_from(
+    /// Returns whether all passages have been carved.
+    /// The generator must be configured like the one which started the generation, while its
+    /// random generator is replaced so that it continues where the state left off.
+    pub fn resume(
+        &mut self,
+        state: &mut GrowingTreeState,
+        passages: usize,
+    ) -> Result<bool, MazeError> {
+        if !state.carving.is_valid_for(&state.maze) {
+            return Err(MazeError::InvalidState);
+        }
+        self.carve(&mut state.maze, &mut state.carving, passages)?;
+        Ok(state.carving.complete)
+    }
+
+    /// Carve all remaining passages of a paused generation and place start and goal
+    pub fn finish(&mut self, mut state: GrowingTreeState) -> Result<Maze, MazeError> {
+        self.resume(&mut state, usize::MAX)?;
+        self.place_goal(&mut state.maze, state.carving);
+        Ok(state.maze)
+    }
+
+    /// Provenance of a maze which is about to be generated, optionally from a given start
+    fn provenance(&self, start: Option<Coordinates>) -> Provenance {
+        let options = (
+            self.selection_method,
+            self.goal_placement,
+            self.random_carving_start,
+            self.direction_bias,
+            self.selector.is_some(),
+            self.weight_map.as_ref().map(WeightMap::fingerprint),
+            start,
+        );
+        Provenance::new(Algorithm::GrowingTree, self.seed, &self.rng, &options)
+    }
+
+    /// Choose the field at which carving starts
+    fn carving_start(&mut self, maze: &Maze) -> Coordinates {
+        if self.random_carving_start {
+            maze.random_coordinates(&mut self.rng)
+        } else {
+            maze.start
+        }
+    }
+
+    /// Prepare carving passages starting at `start_coordinates`
+    fn begin_carving(&mut self, start_coordinates: Coordinates) -> Carving {
+        let mut cell_stack = std::mem::take(&mut self.cell_stack);
+        cell_stack.clear();
+        cell_stack.push_back(start_coordinates);
+        Carving {
+            rng: RngCheckpoint::new(self.seed, &self.rng),
+            origin: start_coordinates,
+            cell_stack,
+            current: start_coordinates,
+            current_position: Some(0),
+            goal: start_coordinates,
+            max_q: 0,
+            complete: false,
+            checked: true,
+        }
+    }
+
+    /// Carve at most `limit` passages, continuing with the random generator of `carving`
+    fn carve(
         &mut self,
         maze: &mut Maze,
-        start_coordinates: Coordinates,
-    ) -> Result<Coordinates> {
-        let mut current_coordinates = start_coordinates;
-        let mut goal_coordinates = current_coordinates;
-        let mut max_q = 0;
+        carving: &mut Carving,
+        limit: usize,
+    ) -> Result<(), MazeError> {
+        self.seed = carving.rng.seed();
+        self.rng = carving.rng.restore();
+        let result = self.carve_passages(maze, carving, limit);
+        carving.rng = RngCheckpoint::new(self.seed, &self.rng);
+        result
+    }
 
-        self.cell_stack.clear();
-        self.cell_stack.push(current_coordinates);
-        self.visited.push(current_coordinates); // Mark it as visited
+    /// Core algorithm implementation
+    ///
+    /// Stops after `limit` passages so that the carving can be paused.
+    fn carve_passages(
+        &mut self,
+        maze: &mut Maze,
+        carving: &mut Carving,
+        limit: usize,
+    ) -> Result<(), MazeError> {
+        let mut carved = 0;
 
-        while !self.cell_stack.is_empty() {
-            self.find_unvisited_neighbours(maze, current_coordinates);
+        // the cells selected from the stack by popping them are no longer on it, so the stack
+        // can be empty while the current cell still has unvisited neighbours
+        while !carving.complete && carved < limit {
+            // only jump away from cells which are on the stack so that none of them get lost
+            if let GrowingTreeSelectionMethod::Mixed(random_chance) = self.selection_method {
+                if self.selector.is_none()
+                    && carving.current_position.is_some()
+                    && self.rng.gen_bool(random_chance.clamp(0.0, 1.0))
+                {
+                    let idx = gen_index(&mut self.rng, carving.cell_stack.len());
+                    carving.current_position = Some(idx);
+                    carving.current = carving.cell_stack[idx];
+                }
+            }
+            self.find_unvisited_neighbours(maze, carving.origin, carving.current);
 
             if self.neighbours.is_empty() {
-                // We've reached a dead end - remove the current_coordinates from the stack
-                if self.cell_stack.contains(&current_coordinates) {
-                    let idx = self
-                        .cell_stack
-                        .iter()
-                        .position(|&r| r == current_coordinates)
-                        .ok_or_else(|| {
-                            GenericGeneratorError::InternalError(String::from(
-                                "Could not find position of coordinates in cell_stack",
-                            ))
-                        })
-                        .with_context(|| "Could not carve passages")?;
-                    self.cell_stack.remove(idx);
+                // We've reached a dead end - remove the current cell from the stack
+                // The order only matters for the non-random selection methods which never leave
+                // the current cell in the middle of the stack so it can be swapped out cheaply.
+                // Custom selectors are promised an ordered stack though.
+                if let Some(idx) = carving.current_position.take() {
+                    if self.selector.is_some() {
+                        carving.cell_stack.remove(idx);
+                    } else {
+                        carving.cell_stack.swap_remove_back(idx);
+                    }
                 }
 
                 // If there are no more cells, quit
-                if self.cell_stack.is_empty() {
-                    continue;
+                if carving.cell_stack.is_empty() {
+                    carving.complete = true;
+                    break;
                 }
 
                 // And now select a new current cell according to 'selectionmethod' parameter
                 // pop and remove wont fail because we just tested for non-zero length
-                current_coordinates = match self.selection_method {
-                    GrowingTreeSelectionMethod::MostRecent => self
-                        .cell_stack
-                        .pop()
-                        .ok_or_else(|| {
-                            GenericGeneratorError::InternalError(String::from(
+                if let Some(selector) = &self.selector {
+                    let mut selector = selector.0.lock().unwrap_or_else(PoisonError::into_inner);
+                    let cells = carving.cell_stack.make_contiguous();
+                    let idx = selector(cells, &mut self.rng);
+                    carving.current = *cells.get(idx).ok_or(MazeError::InvalidSelection {
+                        index: idx,
+                        candidates: cells.len(),
+                    })?;
+                    carving.current_position = Some(idx);
+                    continue;
+                }
+                carving.current = match self.selection_method {
+                    GrowingTreeSelectionMethod::Random => {
+                        let idx = gen_index(&mut self.rng, carving.cell_stack.len());
+                        carving.current_position = Some(idx);
+                        carving.cell_stack[idx]
+                    }
+                    GrowingTreeSelectionMethod::Mixed(random_chance)
+                        if self.rng.gen_bool(random_chance.clamp(0.0, 1.0)) =>
+                    {
+                        let idx = gen_index(&mut self.rng, carving.cell_stack.len());
+                        carving.current_position = Some(idx);
+                        carving.cell_stack[idx]
+                    }
+                    GrowingTreeSelectionMethod::MostRecent
+                    | GrowingTreeSelectionMethod::Mixed(_) => {
+                        carving.cell_stack.pop_back().ok_or_else(|| {
+                            MazeError::InternalError(String::from(
                                 "Could not pop most recent cell from cell_stack",
                             ))
-                        })
-                        .with_context(|| "Could not carve passage")?,
-                    GrowingTreeSelectionMethod::Random => {
-                        self.cell_stack[self.rng.gen_range(0, self.cell_stack.len())]
+                        })?
+                    }
+                    GrowingTreeSelectionMethod::First => {
+                        carving.cell_stack.pop_front().ok_or_else(|| {
+                            MazeError::InternalError(String::from(
+                                "Could not pop first cell from cell_stack",
+                            ))
+                        })?
                     }
-                    GrowingTreeSelectionMethod::First => self.cell_stack.remove(0),
                 };
             } else {
                 // We have some neighbours so we can make a passage
 
                 // Choose a random neighbouring cell and move to it.
-                let next_coords = self.neighbours[self.rng.gen_range(0, self.neighbours.len())];
-                maze.graph.add_edge(current_coordinates, next_coords, ()); // Knock down the wall between them
-                self.cell_stack.push(next_coords);
-                current_coordinates = next_coords;
-                self.visited.push(current_coordinates); // Mark the new cell as visited
+                let next_coords = self.choose_neighbour(carving.current);
+                maze.add_passage(carving.current, next_coords); // Knock down the wall between them
+                carving.cell_stack.push_back(next_coords);
+                carving.current_position = Some(carving.cell_stack.len() - 1);
+                carving.current = next_coords;
+                carved += 1;
 
                 // Keep track of the longest cell stack. Our target is at the end of this stack - the neighbour to which we just connected
-                if self.cell_stack.len() > max_q {
-                    max_q = self.cell_stack.len();
-                    goal_coordinates = current_coordinates;
+                if carving.cell_stack.len() > carving.max_q {
+                    carving.max_q = carving.cell_stack.len();
+                    carving.goal = carving.current;
                 }
             }
         }
 
-        Ok(goal_coordinates)
+        Ok(())
+    }
+
+    /// Place start and goal of a completely carved maze
+    fn place_goal(&mut self, maze: &mut Maze, carving: Carving) {
+        maze.goal = if carving.origin == maze.start {
+            carving.goal
+        } else {
+            maze.farthest_from(maze.start).0
+        };
+        self.goal_placement.apply(maze);
+        // keep the allocation for the next maze
+        self.cell_stack = carving.cell_stack;
+    }
+
+    /// Choose one of the unvisited neighbours of the current cell, weighted by the direction
+    /// bias and the weight map
+    fn choose_neighbour(&mut self, current_coordinates: Coordinates) -> Coordinates {
+        let bias = self.direction_bias;
+        let weight_map = &self.weight_map;
+        let weighted = if bias.is_uniform() && weight_map.is_none() {
+            None
+        } else {
+            self.neighbours
+                .choose_weighted(&mut self.rng, |next| {
+                    let direction_weight = if next.y == current_coordinates.y {
+                        bias.horizontal
+                    } else {
+                        bias.vertical
+                    };
+                    direction_weight * weight_map.as_ref().map_or(1.0, |map| map.weight(next))
+                })
+                .ok()
+                .copied()
+        };
+        // all weights being zero is treated like no bias at all
+        weighted.unwrap_or_else(|| self.neighbours[gen_index(&mut self.rng, self.neighbours.len())])
     }
 
     /// Find the neighbours of this cell that have NOT been visited
-    fn find_unvisited_neighbours(&mut self, maze: &mut Maze, current_coordinates: Coordinates) {
-        self.neighbours.clear(); // Clear the current neighbour list
-
-        // Look all around, add any UNvisited neighbours to the list
-        for i_dir in Direction::all().iter() {
-            let next_coords = current_coordinates.next(i_dir);
-            if maze.are_coordinates_inside(&next_coords) && !self.visited.contains(&next_coords) {
-                self.neighbours.push(next_coords);
-            }
-        }
+    ///
+    /// Every visited field except the one at which carving started has a passage, so the
+    /// passages are all that needs to be remembered about visited fields.
+    fn find_unvisited_neighbours(
+        &mut self,
+        maze: &Maze,
+        origin: Coordinates,
+        current_coordinates: Coordinates,
+    ) {
+        // Look all around, keep only the UNvisited neighbours in the list
+        maze.topology()
+            .neighbours_into(&current_coordinates, &mut self.neighbours);
+        self.neighbours.retain(|next_coords| {
+            *next_coords != origin && maze.passage_neighbours(*next_coords).next().is_none()
+        });
     }
 }
 
 impl Generator for GrowingTreeGenerator {
-    fn generate(&mut self, width: i32, height: i32) -> Result<Maze> {
-        let start = (0, 0).into();
-        let mut maze = Maze::new(width, height, start, (0, 0).into());
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        self.generate_into(&mut maze)?;
+        Ok(maze)
+    }
+
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        ensure_valid_size(maze.size.0, maze.size.1)?;
+        maze.reset();
+        maze.provenance = Some(self.provenance(None));
+        let carving_start = self.carving_start(maze);
+        let mut carving = self.begin_carving(carving_start);
+        self.carve(maze, &mut carving, usize::MAX)?;
+        self.place_goal(maze, carving);
 
-        maze.goal = self
-            .carve_passages_from(&mut maze, start)
-            .with_context(|| "Could not generate maze")?;
+        Ok(())
+    }
+
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        ensure_start_inside(width, height, start)?;
+        let mut maze = Maze::new(width, height, start, start);
+        maze.provenance = Some(self.provenance(Some(start)));
+        let carving_start = self.carving_start(&maze);
+        let mut carving = self.begin_carving(carving_start);
+        self.carve(&mut maze, &mut carving, usize::MAX)?;
+        self.cell_stack = carving.cell_stack;
+        self.goal_placement.apply_keeping_start(&mut maze);
 
         Ok(maze)
     }
+
+    fn info(&self) -> AlgorithmInfo {
+        AlgorithmInfo::new(
+            "Growing tree",
+            "O(n)",
+            "Visited fields and the list of candidate fields, i.e. O(n)",
+            match self.selection_method {
+                _ if self.selector.is_some() => "Depends on the custom selector",
+                GrowingTreeSelectionMethod::Random => {
+                    "Like Prim's: many short dead ends with rather short solutions"
+                }
+                GrowingTreeSelectionMethod::MostRecent => {
+                    "Like recursive backtracking: long winding passages with few dead ends"
+                }
+                GrowingTreeSelectionMethod::First => {
+                    "Long straight corridors spreading out from the start"
+                }
+                GrowingTreeSelectionMethod::Mixed(_) => {
+                    "Between Prim's and recursive backtracking, depending on the chance"
+                }
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::prelude::{Coordinates, Direction, DirectionBias, Generator};
+
     test_all_coordinates_have_fields!(super::GrowingTreeGenerator);
     test_route_from_start_to_goal_exists!(super::GrowingTreeGenerator);
     test_all_fields_connected!(super::GrowingTreeGenerator);
     test_generation_is_deterministic!(super::GrowingTreeGenerator);
+    test_generate_into_matches_generate!(super::GrowingTreeGenerator);
+    test_random_carving_start!(super::GrowingTreeGenerator);
+    test_generate_from_keeps_start!(super::GrowingTreeGenerator);
+    test_passages_are_consistent!(super::GrowingTreeGenerator);
+    test_resumed_generation_matches_generate!(super::GrowingTreeGenerator);
+
+    #[test]
+    fn test_cell_stack_holds_distinct_visited_fields() -> anyhow::Result<()> {
+        use std::collections::HashSet;
+
+        use super::{GrowingTreeGenerator, GrowingTreeSelectionMethod};
+
+        for method in [
+            GrowingTreeSelectionMethod::Random,
+            GrowingTreeSelectionMethod::Mixed(0.5),
+            GrowingTreeSelectionMethod::First,
+        ]
+        .iter()
+        {
+            let mut generator = GrowingTreeGenerator::new(Some([3; 32]));
+            generator.selection_method = *method;
+            let mut state = generator.start(6, 5)?;
+            loop {
+                let (maze, carving) = (&state.maze, &state.carving);
+                let visited = |c: &Coordinates| {
+                    *c == carving.origin || maze.passage_neighbours(*c).next().is_some()
+                };
+                let stack: HashSet<_> = carving.cell_stack.iter().copied().collect();
+                assert_eq!(stack.len(), carving.cell_stack.len());
+                assert!(stack.iter().all(visited));
+                assert_eq!(
+                    maze.coordinates().filter(visited).count(),
+                    maze.passages.passage_count() + 1
+                );
+                if let Some(idx) = carving.current_position {
+                    assert_eq!(carving.cell_stack[idx], carving.current);
+                }
+
+                if generator.resume(&mut state, 1)? {
+                    break;
+                }
+            }
+            assert_eq!(state.maze.passages.passage_count(), 6 * 5 - 1);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_carving_continues_when_the_stack_runs_empty() -> anyhow::Result<()> {
+        use crate::prelude::Generator;
+
+        // carving from the middle of a corridor takes the carving start off the stack while the
+        // other half of the corridor is still unvisited
+        for method in [
+            super::GrowingTreeSelectionMethod::MostRecent,
+            super::GrowingTreeSelectionMethod::First,
+        ]
+        .iter()
+        {
+            for seed in 0..8 {
+                let mut generator = super::GrowingTreeGenerator::new(Some([seed; 32]));
+                generator.selection_method = *method;
+                generator.random_carving_start = true;
+                for &(width, height) in [(1, 9), (9, 1)].iter() {
+                    let maze = generator.generate(width, height)?;
+                    assert_eq!(maze.bfs_order(maze.start).len(), 9, "{:?}", method);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_selector_replaces_selection_method() -> anyhow::Result<()> {
+        let mut most_recent = super::GrowingTreeGenerator::new(Some([5; 32]));
+        most_recent.selection_method = super::GrowingTreeSelectionMethod::MostRecent;
+        let mut newest = super::GrowingTreeGenerator::new(Some([5; 32]))
+            .with_selector(|cells, _| cells.len() - 1);
+        assert_eq!(newest.generate(12, 9)?, most_recent.generate(12, 9)?);
+
+        let mut oldest = newest.clone().with_selector(|_, _| 0);
+        let maze = oldest.generate(12, 9)?;
+        assert_eq!(maze.unreachable_cells(), vec![]);
+
+        let mut broken =
+            super::GrowingTreeGenerator::new(None).with_selector(|cells, _| cells.len());
+        assert!(broken.generate(5, 5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_selection_reaches_every_field() -> anyhow::Result<()> {
+        for random_chance in [0.0, 0.3, 1.0].iter() {
+            let mut generator = super::GrowingTreeGenerator::new(Some([6; 32]));
+            generator.selection_method = super::GrowingTreeSelectionMethod::Mixed(*random_chance);
+            let maze = generator.generate(12, 9)?;
+            assert_eq!(maze.unreachable_cells(), vec![]);
+            assert!(maze.is_perfect());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_direction_bias_favours_corridors() -> anyhow::Result<()> {
+        let mut generator = super::GrowingTreeGenerator::new(Some([13; 32]));
+        generator.selection_method = super::GrowingTreeSelectionMethod::MostRecent;
+        for (bias, horizontal_wins) in [
+            (DirectionBias::new(8.0, 1.0), true),
+            (DirectionBias::new(1.0, 8.0), false),
+        ]
+        .iter()
+        {
+            generator.direction_bias = *bias;
+            let maze = generator.generate(30, 30)?;
+            let count = |direction: &Direction| {
+                maze.coordinates()
+                    .filter(|c| maze.has_passage(c, direction))
+                    .count()
+            };
+            let (horizontal, vertical) = (count(&Direction::East), count(&Direction::South));
+            assert_eq!(horizontal > 2 * vertical, *horizontal_wins);
+            assert_eq!(vertical > 2 * horizontal, !*horizontal_wins);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_weight_map_attracts_corridors() -> anyhow::Result<()> {
+        use crate::prelude::WeightMap;
+
+        // weights growing steeply towards the east make the first row a single corridor
+        let map = WeightMap::from_fn(12, 8, |c| 1000_f64.powi(c.x));
+        for seed in 0..3 {
+            let mut generator =
+                super::GrowingTreeGenerator::new(Some([seed; 32])).with_weight_map(map.clone());
+            generator.selection_method = super::GrowingTreeSelectionMethod::MostRecent;
+            let maze = generator.generate(12, 8)?;
+            assert_eq!(maze.corridor_histogram().longest_run(), 11);
+            assert!((0..11).all(|x| maze.has_passage(&(x, 0).into(), &Direction::East)));
+        }
+        Ok(())
+    }
 }
@@ -9,7 +9,8 @@
     unused_qualifications,
     clippy::unwrap_used,
     clippy::expect_used,
-    clippy::panic
+    clippy::panic,
+    clippy::must_use_candidate
 )]
 
 //! This is a collection of different maze generation algorithms.
@@ -47,6 +48,55 @@
 //! assert_eq!(format!("{:?}", maze.get_field(&maze.start).unwrap()),
 //!            "Field { north: \"wall\", east: \"wall\", south: \"passage\", west: \"wall\" }");
 //! ```
+//!
+//! ```
+//! // Generate a maze without choosing a specific generator type
+//! use maze_generator::Algorithm;
+//!
+//! let maze = maze_generator::generate(Algorithm::Prims, 10, 10, Some([42; 32])).unwrap();
+//! assert_eq!(maze.size, (10, 10));
+//! ```
+//!
+//! # Imports
+//! [`prelude`] contains the types and traits that are common to all generators while [`api`]
+//! additionally re-exports every generator so that a single import suffices.
+//!
+//! # Seed stability
+//! Generating a maze with the same algorithm, configuration and [seed](prelude::Seed) yields
+//! the same maze on every operating system and architecture, so seeds can be shared between
+//! players or persisted instead of whole mazes.
+//! Changes which alter the mazes generated from a seed are only made in combination with an
+//! increment of [`CRATE_MAZE_FORMAT_VERSION`].
+//!
+//! # Crate features
+//! All algorithms and the text, DOT, tilemap and voxel exports are always available.
+//! Additional functionality is gated behind the following features so that applications which
+//! only generate mazes, e.g. game servers, do not need to compile any of the exporters:
+//!
+//! - `svg` (enabled by default): Export mazes as SVG images via `Maze::to_svg()` and
+//!   `Maze::write_svg()`.
+//! - `pdf`: Export mazes as printable PDF documents via `Maze::to_pdf()` and
+//!   `mazes_to_pdf()`, placing multiple mazes onto each page, and whole puzzle booklets with
+//!   a cover page and solutions via `booklet_to_pdf()`.
+//! - `tui`: Interactive terminal viewer in the `tui` module which lets the user walk through a
+//!   maze with the arrow keys.
+//! - `rayon`: Generate large batches of mazes on multiple threads via `generate_batch()` and
+//!   render the rows of large mazes in parallel.
+//! - `tmx`: Export mazes as maps for the Tiled editor via `Maze::to_tmx()`, which Godot can
+//!   import as well.
+//! - `mesh`: Export the walls of mazes as Wavefront OBJ meshes via `Maze::to_obj()`.
+//! - `image`: Load masks from PNG images via `Mask::from_png_file()` to generate mazes in the
+//!   shape of logos or text.
+//! - `ndarray`: Convert mazes from and to arrays of wall bitmasks via `Maze::to_ndarray()` and
+//!   `Maze::from_ndarray()`.
+//! - `rl`: Reinforcement learning environment in the `rl` module which lets agents walk through
+//!   generated mazes step by step.
+//! - `serde`: Serialize mazes as well as the paused generations of
+//!   `GrowingTreeGenerator::start()` and `PrimsGenerator::start()` so that huge mazes can be
+//!   generated over multiple sessions or by multiple workers.
+//!
+//! # Choosing an algorithm
+#![doc = include_str!("../docs/choosing-an-algorithm.md")]
 
 #[cfg(test)]
 #[macro_use]
@@ -58,7 +108,31 @@ mod test_util;
 
 #[macro_use]
 pub mod prelude;
+pub mod api;
+pub mod builder;
 pub mod ellers_algorithm;
+pub mod generators;
 pub mod growing_tree;
+pub mod origin_shift;
 pub mod prims_algorithm;
+pub mod puzzle;
 pub mod recursive_backtracking;
+#[cfg(feature = "rl")]
+pub mod rl;
+pub mod stack;
+pub mod symmetric;
+pub mod theta;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod world;
+
+pub use builder::{generate, Algorithm};
+/// Version of the mazes generated from a seed
+///
+/// It is incremented whenever any generator starts to produce a different maze from the same
+/// seed and configuration, so it can be stored next to seeds to detect that they no longer
+/// reproduce the original maze.
+pub const CRATE_MAZE_FORMAT_VERSION: u32 = 2;
+
+/// The graph library used by [`MazeGraph`](prelude::MazeGraph)
+pub use petgraph;
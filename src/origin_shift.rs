@@ -0,0 +1,167 @@
+//! Origin shift implementation
+//!
+//! Every perfect maze can be seen as a tree in which all passages lead towards one field, the
+//! origin. Each field except the origin therefore has exactly one passage towards its parent.
+//!
+//! 1. Choose a random neighbour of the origin.
+//! 2. Carve a passage from the origin to the neighbour, which becomes the parent of the old
+//!    origin.
+//! 3. Wall off the passage from the neighbour to its own parent and make it the new origin.
+//!
+//! The maze is perfect again after every single step, so it can be shifted a little at a time,
+//! e.g. to let a maze change while a player walks through it.
+//! Given enough steps the result is independent of the initial maze.
+//!
+//! *Explanation and credits to
+//! [CaptainLuma](https://github.com/CaptainLuma/New-Maze-Generating-Algorithm)*
+
+use std::collections::HashMap;
+
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while preparing a maze for [`OriginShift`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OriginShiftError {
+    /// Only perfect mazes form a tree of passages, see [`Maze::is_perfect()`]
+    #[error("Only perfect mazes can be shifted")]
+    NotPerfect,
+}
+
+/// A perfect maze which can be mutated step by step using the origin shift algorithm
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::origin_shift::OriginShift;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+/// let mut shifting = OriginShift::new(maze.clone(), Some([42; 32])).unwrap();
+///
+/// shifting.steps(50);
+/// assert_ne!(shifting.maze(), &maze);
+/// assert!(shifting.maze().is_perfect());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OriginShift {
+    rng: ChaChaRng,
+    maze: Maze,
+    origin: Coordinates,
+    /// The field towards which the passage of every field except the origin leads
+    parents: HashMap<Coordinates, Coordinates>,
+}
+
+impl OriginShift {
+    /// Prepare `maze` for shifting, starting with its start field as origin.
+    ///
+    /// Optionally a 32 bit seed can be provided to seed the internal random generator.
+    /// Giving a seed results in identical shifts which omitting it sources the random generator
+    /// from entropy.
+    pub fn new(maze: Maze, seed: Option<Seed>) -> Result<OriginShift, OriginShiftError> {
+        if !maze.is_perfect() {
+            return Err(OriginShiftError::NotPerfect);
+        }
+
+        let parents = maze
+            .breadth_first_search(maze.start)
+            .into_iter()
+            .filter_map(|(coordinates, (_, parent))| parent.map(|parent| (coordinates, parent)))
+            .collect();
+        Ok(OriginShift {
+            rng: match seed {
+                None => ChaChaRng::from_entropy(),
+                Some(seed) => ChaChaRng::from_seed(seed),
+            },
+            origin: maze.start,
+            maze,
+            parents,
+        })
+    }
+
+    /// The current state of the maze
+    #[must_use]
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Stop shifting and return the current state of the maze
+    #[must_use]
+    pub fn into_maze(self) -> Maze {
+        self.maze
+    }
+
+    /// The field which currently has no parent
+    #[must_use]
+    pub fn origin(&self) -> Coordinates {
+        self.origin
+    }
+
+    /// Move the origin to a random neighbour, which changes at most one passage of the maze.
+    ///
+    /// Start and goal stay where they are and remain connected.
+    pub fn step(&mut self) {
+        let neighbours: Vec<Coordinates> = self.maze.adjacent_cells(&self.origin).collect();
+        let next = match neighbours.choose(&mut self.rng) {
+            Some(next) => *next,
+            None => return,
+        };
+
+        if let Some(parent) = self.parents.remove(&next) {
+            self.maze.passages.remove(next, parent);
+        }
+        self.maze.add_passage(self.origin, next);
+        self.parents.insert(self.origin, next);
+        self.origin = next;
+    }
+
+    /// Shift the maze by `count` steps
+    pub fn steps(&mut self, count: usize) {
+        for _ in 0..count {
+            self.step();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::origin_shift::{OriginShift, OriginShiftError};
+    use crate::prelude::{Coordinates, Generator, Room};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_maze_stays_perfect() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([11; 32])).generate(7, 5)?;
+        let mut shifting = OriginShift::new(maze.clone(), Some([11; 32]))?;
+
+        let mut changed = 0;
+        for _ in 0..200 {
+            let before = shifting.maze().clone();
+            shifting.step();
+            assert!(shifting.maze().is_perfect());
+            if shifting.maze() != &before {
+                changed += 1;
+            }
+        }
+        // moving back towards the previous origin or a child of the origin changes nothing
+        assert!(changed > 50);
+
+        let shifted = shifting.into_maze();
+        assert_eq!((shifted.start, shifted.goal), (maze.start, maze.goal));
+
+        let mut single = OriginShift::new(PrimsGenerator::new(None).generate(1, 1)?, None)?;
+        single.steps(3);
+        assert_eq!(single.origin(), Coordinates::new(0, 0));
+
+        let mut looped = maze;
+        looped.carve_room(Room::new(Coordinates::new(0, 0), 2, 2))?;
+        assert!(matches!(
+            OriginShift::new(looped, None),
+            Err(OriginShiftError::NotPerfect)
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+/// Human readable description of a maze generation algorithm
+///
+/// User interfaces which let users pick an algorithm can display this instead of hard-coding
+/// descriptions.
+/// Retrieve it via [`Generator::info()`](crate::prelude::Generator::info).
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let info = RbGenerator::new(None).info();
+/// assert_eq!(info.name, "Recursive backtracking");
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct AlgorithmInfo {
+    /// Display name of the algorithm
+    pub name: &'static str,
+    /// Asymptotic run time in relation to the number of fields `n`
+    pub time_complexity: &'static str,
+    /// Which data the algorithm keeps in memory besides the maze itself
+    pub memory_profile: &'static str,
+    /// What the generated mazes typically look like
+    pub texture_bias: &'static str,
+}
+
+impl AlgorithmInfo {
+    /// Describe an algorithm
+    #[must_use]
+    pub const fn new(
+        name: &'static str,
+        time_complexity: &'static str,
+        memory_profile: &'static str,
+        texture_bias: &'static str,
+    ) -> Self {
+        AlgorithmInfo {
+            name,
+            time_complexity,
+            memory_profile,
+            texture_bias,
+        }
+    }
+
+    /// Placeholder for generators which do not describe themselves
+    pub const UNKNOWN: AlgorithmInfo =
+        AlgorithmInfo::new("Unknown", "unknown", "unknown", "unknown");
+}
@@ -0,0 +1,117 @@
+use ndarray::{Array2, ArrayBase, Data, Ix2};
+
+use crate::prelude::*;
+
+impl Maze {
+    /// Convert the maze into an array of wall bitmasks, e.g. for feeding it into numerical code
+    /// like reinforcement learning environments.
+    ///
+    /// The array has the shape `(height, width)` so it is indexed as `array[[y, x]]`.
+    /// Every element has a bit set for each wall of its field, using the same bits as
+    /// [`Passages`] does for the passages, i.e. `1` for a wall to the north, `2` east, `4` south
+    /// and `8` west.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·
+    /// |S  |
+    /// · ·-·
+    /// |  G|
+    /// ·-·-·
+    /// ").unwrap();
+    /// let array = maze.to_ndarray();
+    ///
+    /// assert_eq!(array.shape(), &[2, 2]);
+    /// assert_eq!(array[[0, 0]], 0b1001);
+    /// assert_eq!(array[[1, 1]], 0b0111);
+    /// assert_eq!(Maze::from_ndarray(&array, maze.start, maze.goal).unwrap(), maze);
+    /// ```
+    #[must_use]
+    pub fn to_ndarray(&self) -> Array2<u8> {
+        let (width, height) = self.size;
+        Array2::from_shape_fn((height.max(0) as usize, width.max(0) as usize), |(y, x)| {
+            let passages = self
+                .passages(&Coordinates::new(x as i32, y as i32))
+                .unwrap_or_default();
+            !passages.bits() & Passages::ALL.bits()
+        })
+    }
+
+    /// Construct a maze from an array of wall bitmasks as produced by
+    /// [`to_ndarray()`](Maze::to_ndarray).
+    ///
+    /// Bits which do not represent a direction are ignored.
+    /// The array is validated like a grid passed to [`Maze::from_wall_grid()`].
+    pub fn from_ndarray<S: Data<Elem = u8>>(
+        array: &ArrayBase<S, Ix2>,
+        start: Coordinates,
+        goal: Coordinates,
+    ) -> Result<Maze, WallGridError> {
+        let has_wall =
+            |bits: u8, direction: Direction| bits & Passages::from(direction).bits() != 0;
+        let grid: Vec<Vec<CellWalls>> = array
+            .rows()
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .map(|bits| CellWalls {
+                        north: has_wall(*bits, Direction::North),
+                        east: has_wall(*bits, Direction::East),
+                        south: has_wall(*bits, Direction::South),
+                        west: has_wall(*bits, Direction::West),
+                    })
+                    .collect()
+            })
+            .collect();
+        Maze::from_wall_grid(&grid, start, goal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ndarray::Array2;
+
+    use crate::prelude::{Coordinates, Direction, Generator, Maze, WallGridError};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_ndarray_round_trip() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([32; 32])).generate(7, 4)?;
+        let array = maze.to_ndarray();
+        assert_eq!(array.dim(), (4, 7));
+        for coordinates in maze.coordinates() {
+            let walls = array[[coordinates.y as usize, coordinates.x as usize]];
+            assert_eq!(
+                walls.count_ones() as usize,
+                4 - maze.passage_count(coordinates)
+            );
+        }
+
+        let restored = Maze::from_ndarray(&array.view(), maze.start, maze.goal)?;
+        assert_eq!(restored.passages, maze.passages);
+        assert_eq!((restored.start, restored.goal), (maze.start, maze.goal));
+
+        let origin = Coordinates::new(0, 0);
+        assert_eq!(
+            Maze::from_ndarray(&Array2::<u8>::zeros((0, 3)), origin, origin),
+            Err(WallGridError::Empty)
+        );
+        // extra bits are ignored
+        assert_eq!(
+            Maze::from_ndarray(&Array2::from_elem((1, 1), 0xff), origin, origin)?.size,
+            (1, 1)
+        );
+        assert_eq!(
+            Maze::from_ndarray(&Array2::from_elem((1, 2), 0b1111), origin, origin),
+            Ok(Maze::new(2, 1, origin, origin))
+        );
+        assert_eq!(
+            Maze::from_ndarray(&Array2::from_elem((1, 1), 0b1011), origin, origin),
+            Err(WallGridError::OpenBorder(origin, Direction::South))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use rand::prelude::*;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+
+use crate::prelude::*;
+
+/// Generate `count` mazes of every size in `sizes` on `threads` worker threads.
+///
+/// Every maze is generated by its own generator which is created by calling `generator_factory`
+/// with a random seed.
+/// Passing `0` as `threads` uses one thread per CPU core.
+///
+/// The mazes are returned in the order of `sizes`, i.e. the first `count` mazes have the first
+/// size and so on.
+/// If any maze can not be generated, the first such error is returned.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let mazes = generate_batch(RbGenerator::new, &[(10, 10), (20, 5)], 50, 4).unwrap();
+///
+/// assert_eq!(mazes.len(), 100);
+/// assert_eq!(mazes[49].size, (10, 10));
+/// assert_eq!(mazes[50].size, (20, 5));
+/// ```
+pub fn generate_batch<G, F>(
+    generator_factory: F,
+    sizes: &[(i32, i32)],
+    count: usize,
+    threads: usize,
+) -> Result<Vec<Maze>, MazeError>
+where
+    G: Generator,
+    F: Fn(Option<Seed>) -> G + Sync,
+{
+    let mut rng = thread_rng();
+    let jobs: Vec<((i32, i32), Seed)> = sizes
+        .iter()
+        .flat_map(|size| (0..count).map(move |_| *size))
+        .map(|size| (size, rng.gen()))
+        .collect();
+
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map_err(|error| MazeError::WorkerThreads(error.to_string()))?;
+    pool.install(|| {
+        jobs.into_par_iter()
+            .map(|((width, height), seed)| generator_factory(Some(seed)).generate(width, height))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::generate_batch;
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_batch_sizes() -> anyhow::Result<()> {
+        let mazes = generate_batch(PrimsGenerator::new, &[(3, 4), (5, 2), (1, 1)], 7, 2)?;
+
+        assert_eq!(mazes.len(), 21);
+        for (i, maze) in mazes.iter().enumerate() {
+            let expected = [(3, 4), (5, 2), (1, 1)][i / 7];
+            assert_eq!(maze.size, expected);
+        }
+        assert!(generate_batch(PrimsGenerator::new, &[], 10, 0)?.is_empty());
+        Ok(())
+    }
+}
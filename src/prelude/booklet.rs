@@ -0,0 +1,269 @@
+use crate::prelude::pdf::{write_pdf, PdfPage};
+use crate::prelude::*;
+
+/// Number of mazes which are generated for every maze of a booklet to choose the one whose
+/// difficulty fits its preset best
+const CANDIDATES: usize = 5;
+
+/// How hard the mazes of a section of a booklet are
+///
+/// Harder presets generate larger mazes.
+/// Since the [`difficulty()`](Maze::difficulty) of a maze mostly depends on the algorithm which
+/// generated it, every maze of a booklet is chosen from multiple candidates of the same size:
+/// the easiest one for [`Easy`](DifficultyPreset::Easy), the median one for
+/// [`Medium`](DifficultyPreset::Medium) and the hardest one for
+/// [`Hard`](DifficultyPreset::Hard).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DifficultyPreset {
+    /// Mazes of 10x10 fields
+    Easy,
+    /// Mazes of 20x20 fields
+    Medium,
+    /// Mazes of 30x30 fields
+    Hard,
+}
+
+impl DifficultyPreset {
+    /// Name of the preset as printed in a booklet
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Medium => "Medium",
+            DifficultyPreset::Hard => "Hard",
+        }
+    }
+
+    /// Width and height of the mazes of this preset
+    #[must_use]
+    pub fn size(&self) -> (i32, i32) {
+        match self {
+            DifficultyPreset::Easy => (10, 10),
+            DifficultyPreset::Medium => (20, 20),
+            DifficultyPreset::Hard => (30, 30),
+        }
+    }
+
+    /// Generate a maze of this preset with `generator`
+    fn generate<G: Generator + ?Sized>(&self, generator: &mut G) -> Result<Maze, MazeError> {
+        let (width, height) = self.size();
+        let mut candidates = (0..CANDIDATES)
+            .map(|_| {
+                let maze = generator.generate(width, height)?;
+                Ok((maze.difficulty().score, maze))
+            })
+            .collect::<Result<Vec<_>, MazeError>>()?;
+        candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let i_chosen = match self {
+            DifficultyPreset::Easy => 0,
+            DifficultyPreset::Medium => CANDIDATES / 2,
+            DifficultyPreset::Hard => CANDIDATES - 1,
+        };
+        Ok(candidates.swap_remove(i_chosen).1)
+    }
+}
+
+/// Options for generating a booklet via [`booklet_to_pdf()`]
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = BookletOptions::new().title("Rainy day mazes");
+/// assert_eq!(options.pdf.mazes_per_page(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BookletOptions {
+    /// Title printed on the cover page, default: "Mazes"
+    pub title: String,
+    /// Page size and layout of the maze and solution pages, default: [`PdfOptions::new()`]
+    pub pdf: PdfOptions,
+}
+
+impl BookletOptions {
+    /// Create a default BookletOptions object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the title printed on the cover page
+    #[must_use]
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the page size and layout of the maze and solution pages
+    #[must_use]
+    pub fn pdf(mut self, pdf: PdfOptions) -> Self {
+        self.pdf = pdf;
+        self
+    }
+}
+
+impl Default for BookletOptions {
+    fn default() -> Self {
+        BookletOptions {
+            title: String::from("Mazes"),
+            pdf: PdfOptions::new(),
+        }
+    }
+}
+
+/// Generate a printable puzzle booklet as a PDF document.
+///
+/// For every `(preset, count)` in `sections`, `count` mazes of the [`DifficultyPreset`] are
+/// generated with `generator`.
+/// The booklet starts with a cover page showing the title and the contents, followed by the
+/// mazes of every section and finally the solutions, in which the shortest route from start to
+/// goal is drawn into every maze.
+/// Every section starts on a new page and all mazes are numbered in the headings of the pages.
+/// Seeded generators generate identical booklets.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::prims_algorithm::PrimsGenerator;
+///
+/// let mut generator = PrimsGenerator::new(Some([42; 32]));
+/// let sections = [(DifficultyPreset::Easy, 4), (DifficultyPreset::Hard, 2)];
+/// let options = BookletOptions::new()
+///     .title("Rainy day mazes")
+///     .pdf(PdfOptions::new().columns(2).rows(2));
+/// let pdf = booklet_to_pdf(&mut generator, &sections, &options).unwrap();
+///
+/// assert!(pdf.starts_with(b"%PDF-"));
+/// ```
+pub fn booklet_to_pdf<G: Generator + ?Sized>(
+    generator: &mut G,
+    sections: &[(DifficultyPreset, usize)],
+    options: &BookletOptions,
+) -> Result<Vec<u8>, MazeError> {
+    let mazes = sections
+        .iter()
+        .map(|(preset, count)| {
+            (0..*count)
+                .map(|_| preset.generate(generator))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let solutions: Vec<Vec<Vec<Coordinates>>> = mazes
+        .iter()
+        .map(|section| {
+            section
+                .iter()
+                .map(|maze| {
+                    maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::BreadthFirst)
+                        .path
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .collect();
+
+    // the mazes of every section are split into pages which are labeled with the numbers of
+    // their mazes
+    let per_page = options.pdf.mazes_per_page();
+    let mut chunks = Vec::new();
+    let mut number = 1;
+    for (i_section, section) in mazes.iter().enumerate() {
+        for (i_chunk, chunk) in section.chunks(per_page).enumerate() {
+            let numbers = if chunk.len() == 1 {
+                format!("maze {}", number)
+            } else {
+                format!("mazes {}-{}", number, number + chunk.len() - 1)
+            };
+            let first = i_chunk * per_page;
+            let route = &solutions[i_section][first..first + chunk.len()];
+            chunks.push((sections[i_section].0, numbers, chunk, route));
+            number += chunk.len();
+        }
+    }
+
+    let mut contents: Vec<String> = sections
+        .iter()
+        .map(|(preset, count)| {
+            let noun = if *count == 1 { "maze" } else { "mazes" };
+            format!("{} {} {}", count, preset.name().to_lowercase(), noun)
+        })
+        .collect();
+    contents.push(format!("Solutions from page {}", chunks.len() + 2));
+    let mut pages = vec![PdfPage::Text(
+        std::iter::once(options.title.clone())
+            .chain(contents)
+            .collect(),
+    )];
+    for (preset, numbers, chunk, _) in chunks.iter() {
+        pages.push(PdfPage::Mazes {
+            heading: Some(format!("{}: {}", preset.name(), numbers)),
+            mazes: chunk,
+            solutions: None,
+        });
+    }
+    for (_, numbers, chunk, route) in chunks.iter() {
+        pages.push(PdfPage::Mazes {
+            heading: Some(format!("Solutions: {}", numbers)),
+            mazes: chunk,
+            solutions: Some(route),
+        });
+    }
+
+    Ok(write_pdf(&pages, &options.pdf))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{booklet_to_pdf, BookletOptions, DifficultyPreset, Generator, PdfOptions};
+    use crate::recursive_backtracking::RbGenerator;
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|w| w == &needle)
+            .count()
+    }
+
+    #[test]
+    fn test_booklet_pages() -> anyhow::Result<()> {
+        let sections = [(DifficultyPreset::Easy, 3), (DifficultyPreset::Hard, 1)];
+        let options = BookletOptions::new()
+            .title("Test (1)")
+            .pdf(PdfOptions::new().rows(2));
+        let pdf = booklet_to_pdf(&mut RbGenerator::new(Some([3; 32])), &sections, &options)?;
+
+        // a cover, three pages of mazes and three pages of solutions
+        assert_eq!(count(&pdf, b"/Type /Page\n"), 7);
+        assert_eq!(count(&pdf, b"/BaseFont /Helvetica"), 1);
+        assert_eq!(count(&pdf, b"(Test (1)) Tj"), 1);
+        assert_eq!(count(&pdf, b"(3 easy mazes) Tj"), 1);
+        assert_eq!(count(&pdf, b"(1 hard maze) Tj"), 1);
+        assert_eq!(count(&pdf, b"(Solutions from page 5) Tj"), 1);
+        assert_eq!(count(&pdf, b"(Easy: mazes 1-2) Tj"), 1);
+        assert_eq!(count(&pdf, b"(Easy: maze 3) Tj"), 1);
+        assert_eq!(count(&pdf, b"(Hard: maze 4) Tj"), 1);
+        assert_eq!(count(&pdf, b"(Solutions: maze 4) Tj"), 1);
+        // only the solutions are drawn in red
+        assert_eq!(count(&pdf, b"0.8 0 0 RG"), 4);
+
+        let again = booklet_to_pdf(&mut RbGenerator::new(Some([3; 32])), &sections, &options)?;
+        assert_eq!(pdf, again);
+        Ok(())
+    }
+
+    #[test]
+    fn test_presets_choose_by_difficulty() -> anyhow::Result<()> {
+        let mut generator = RbGenerator::new(Some([9; 32]));
+        let easy = DifficultyPreset::Easy.generate(&mut generator)?;
+        assert_eq!(easy.size, (10, 10));
+
+        // the easy maze is the easiest of the candidates which the same generator yields
+        let mut generator = RbGenerator::new(Some([9; 32]));
+        for _ in 0..super::CANDIDATES {
+            let candidate = generator.generate(10, 10)?;
+            assert!(easy.difficulty().score <= candidate.difficulty().score);
+        }
+        Ok(())
+    }
+}
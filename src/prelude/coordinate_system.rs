@@ -0,0 +1,131 @@
+use std::convert::TryFrom;
+
+use crate::prelude::*;
+
+/// Orientation of the `y` axis in the coordinates of an application which embeds a maze
+///
+/// This crate always places the origin in the north-western corner of a maze with
+/// [`North`](Direction::North) pointing towards decreasing `y` values, which matches the
+/// screen space of most 2D graphics libraries.
+/// Engines and world coordinates often use an upwards pointing `y` axis instead.
+/// A coordinate system makes this mapping explicit by converting coordinates and steps between
+/// the convention of the application and the one of this crate.
+///
+/// Only the coordinates are converted, [`North`](Direction::North) is always at the top of a
+/// rendered maze.
+/// Use [`Maze::mirror_vertical()`] to flip the maze itself instead.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let maze = Maze::from_ascii("·-·-·\n|S  |\n·-· ·\n|G  |\n·-·-·\n").unwrap();
+/// let world = CoordinateSystem::YUp;
+///
+/// // the start lies in the upper row, which is the row with the larger y value in the world
+/// assert_eq!(world.from_maze(&maze, maze.start), Coordinates::new(0, 1));
+/// assert_eq!(world.to_maze(&maze, Coordinates::new(0, 0)), maze.goal);
+/// assert_eq!(world.delta(&Direction::North), (0, 1));
+/// assert_eq!(world.direction_from_delta((0, -1)), Some(Direction::South));
+/// ```
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoordinateSystem {
+    /// Origin in the north-western corner with `y` growing towards the
+    /// [`South`](Direction::South), the convention of this crate
+    #[default]
+    YDown,
+    /// Origin in the south-western corner with `y` growing towards the
+    /// [`North`](Direction::North)
+    YUp,
+}
+
+impl CoordinateSystem {
+    /// The change of `(x, y)` coordinates in this coordinate system when moving one step into
+    /// `direction`
+    #[must_use]
+    pub fn delta(&self, direction: &Direction) -> (i32, i32) {
+        let (dx, dy) = direction.delta();
+        match self {
+            CoordinateSystem::YDown => (dx, dy),
+            CoordinateSystem::YUp => (dx, -dy),
+        }
+    }
+
+    /// The direction of a single step by `(dx, dy)` in this coordinate system, `None` if it is
+    /// not a step towards one of the four directions
+    #[must_use]
+    pub fn direction_from_delta(&self, (dx, dy): (i32, i32)) -> Option<Direction> {
+        let delta = match self {
+            CoordinateSystem::YDown => (dx, dy),
+            CoordinateSystem::YUp => (dx, -dy),
+        };
+        Direction::try_from(delta).ok()
+    }
+
+    /// Convert `coordinates` of a field of `maze` into this coordinate system
+    #[must_use]
+    pub fn from_maze(&self, maze: &Maze, coordinates: Coordinates) -> Coordinates {
+        self.flip(maze, coordinates)
+    }
+
+    /// Convert `coordinates` in this coordinate system into those of the field of `maze`, e.g.
+    /// for passing them to [`Maze::get_field()`]
+    #[must_use]
+    pub fn to_maze(&self, maze: &Maze, coordinates: Coordinates) -> Coordinates {
+        self.flip(maze, coordinates)
+    }
+
+    /// Mirroring the rows is its own inverse, so converting works the same in both directions
+    fn flip(&self, maze: &Maze, coordinates: Coordinates) -> Coordinates {
+        match self {
+            CoordinateSystem::YDown => coordinates,
+            CoordinateSystem::YUp => {
+                Coordinates::new(coordinates.x, maze.size.1 - 1 - coordinates.y)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{CoordinateSystem, Coordinates, Direction, Generator, MoveResult};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_conversions_are_consistent() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([17; 32])).generate(5, 3)?;
+        let mirrored = maze.mirror_vertical();
+
+        for system in [CoordinateSystem::YDown, CoordinateSystem::YUp].iter() {
+            for coordinates in maze.coordinates() {
+                let converted = system.from_maze(&maze, coordinates);
+                assert_eq!(system.to_maze(&maze, converted), coordinates);
+                if *system == CoordinateSystem::YUp {
+                    assert!(mirrored.are_coordinates_inside(&converted));
+                    assert_eq!(
+                        mirrored
+                            .get_field(&converted)
+                            .map(|f| f.passage_directions().count()),
+                        maze.get_field(&coordinates)
+                            .map(|f| f.passage_directions().count())
+                    );
+                }
+
+                // moving in the maze matches stepping by the delta in the coordinate system
+                for direction in Direction::iter() {
+                    if let MoveResult::Moved(to) = maze.try_move(&coordinates, &direction) {
+                        let (dx, dy) = system.delta(&direction);
+                        let stepped = Coordinates::new(converted.x + dx, converted.y + dy);
+                        assert_eq!(system.to_maze(&maze, stepped), to);
+                        assert_eq!(system.direction_from_delta((dx, dy)), Some(direction));
+                    }
+                }
+            }
+        }
+
+        assert_eq!(CoordinateSystem::default(), CoordinateSystem::YDown);
+        assert_eq!(CoordinateSystem::YUp.direction_from_delta((1, 1)), None);
+        Ok(())
+    }
+}
@@ -1,9 +1,11 @@
 use std::fmt::{Debug, Display, Formatter};
+use std::ops::{Add, Sub};
 
 use crate::prelude::*;
 
 /// Two-Dimensional coordinates used for addressing fields in a maze.
 #[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinates {
     /// X component
     pub x: i32,
@@ -13,27 +15,64 @@ pub struct Coordinates {
 
 impl Coordinates {
     /// Create a new instance from the specified coordinate components
+    #[must_use]
     pub fn new(x: i32, y: i32) -> Self {
         Coordinates { x, y }
     }
 
     /// Returns the next neighboring coordinates in a specific direction
+    #[must_use]
     pub fn next(&self, direction: &Direction) -> Self {
+        let (dx, dy) = direction.delta();
         Self {
-            x: self.x
-                + match direction {
-                    Direction::East => 1,
-                    Direction::West => -1,
-                    _ => 0,
-                },
-            y: self.y
-                + match direction {
-                    Direction::North => -1,
-                    Direction::South => 1,
-                    _ => 0,
-                },
+            x: self.x + dx,
+            y: self.y + dy,
         }
     }
+
+    /// Number of steps between both coordinates when only moving horizontally or vertically
+    ///
+    /// This is the shortest possible route through a maze and thus an admissible heuristic for
+    /// searches like A*.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let a = Coordinates::new(1, 2);
+    /// let b = Coordinates::new(4, 0);
+    /// assert_eq!(a.manhattan_distance(&b), 5);
+    /// assert_eq!(a.chebyshev_distance(&b), 3);
+    /// assert_eq!(b - a, Coordinates::new(3, -2));
+    /// ```
+    #[must_use]
+    pub fn manhattan_distance(&self, other: &Coordinates) -> u32 {
+        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+    }
+
+    /// Number of steps between both coordinates when diagonal moves are allowed as well
+    #[must_use]
+    pub fn chebyshev_distance(&self, other: &Coordinates) -> u32 {
+        (self.x - other.x)
+            .unsigned_abs()
+            .max((self.y - other.y).unsigned_abs())
+    }
+}
+
+impl Add for Coordinates {
+    type Output = Coordinates;
+
+    fn add(self, other: Coordinates) -> Self::Output {
+        Coordinates::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Coordinates {
+    type Output = Coordinates;
+
+    fn sub(self, other: Coordinates) -> Self::Output {
+        Coordinates::new(self.x - other.x, self.y - other.y)
+    }
 }
 
 impl From<Coordinates> for (i32, i32) {
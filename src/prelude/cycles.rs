@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+
+impl Maze {
+    /// Whether every field can be reached from every other field through exactly one route,
+    /// i.e. the maze is connected and has no loops.
+    ///
+    /// All generators produce perfect mazes but editing them, e.g. via [`Maze::carve()`],
+    /// [`Maze::carve_room()`] or [`Maze::sparsify()`], can break this.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// assert!(maze.is_perfect());
+    ///
+    /// maze.carve_room(Room::new(Coordinates::new(0, 0), 2, 2)).unwrap();
+    /// assert!(!maze.is_perfect());
+    /// ```
+    #[must_use]
+    pub fn is_perfect(&self) -> bool {
+        let field_count = self.topology().cell_count();
+        self.passages.passage_count() + 1 == field_count
+            && self.breadth_first_search(self.start).len() == field_count
+    }
+
+    /// Find all loops of the maze.
+    ///
+    /// The loops form a fundamental cycle basis: every loop contains exactly one passage that
+    /// no other listed loop contains, and every other loop of the maze can be combined from the
+    /// listed ones.
+    /// Each loop is returned as the fields along it in walking order without repeating the first
+    /// field at the end.
+    /// Fields of a room form many small loops which are all listed as well.
+    ///
+    /// The result is empty for perfect mazes.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// · · · ·
+    /// |  G| |
+    /// ·-·-·-·
+    /// ").unwrap();
+    ///
+    /// let cycles = maze.cycles();
+    /// assert_eq!(cycles.len(), 1);
+    /// assert_eq!(cycles[0].len(), 4);
+    /// assert!(cycles[0].contains(&Coordinates::new(1, 1)));
+    /// ```
+    #[must_use]
+    pub fn cycles(&self) -> Vec<Vec<Coordinates>> {
+        // spanning forest of all fields, even those that are not reachable from the start
+        let mut forest: HashMap<Coordinates, (usize, Option<Coordinates>)> = HashMap::new();
+        for coordinates in self.coordinates() {
+            if !forest.contains_key(&coordinates) {
+                forest.extend(self.breadth_first_search(coordinates));
+            }
+        }
+        let parent = |c: &Coordinates| forest.get(c).and_then(|(_, parent)| *parent);
+        let depth = |c: &Coordinates| forest.get(c).map_or(0, |(depth, _)| *depth);
+
+        let mut cycles = Vec::new();
+        for a in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let b = a.next(direction);
+                if !self.has_passage_between(a, b) || parent(&a) == Some(b) || parent(&b) == Some(a)
+                {
+                    continue;
+                }
+
+                // every passage which is not part of the forest closes exactly one loop through
+                // the closest common ancestor of both fields
+                let mut from_a = vec![a];
+                let mut from_b = vec![b];
+                let (mut i_a, mut i_b) = (a, b);
+                while i_a != i_b {
+                    if depth(&i_a) >= depth(&i_b) {
+                        i_a = parent(&i_a).unwrap_or(i_b);
+                        from_a.push(i_a);
+                    } else {
+                        i_b = parent(&i_b).unwrap_or(i_a);
+                        from_b.push(i_b);
+                    }
+                }
+                // both halves end at the common ancestor which should only be listed once
+                from_b.pop();
+                from_a.extend(from_b.into_iter().rev());
+                cycles.push(from_a);
+            }
+        }
+
+        cycles
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Direction, Generator};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_cycles_match_added_passages() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([6; 32])).generate(8, 6)?;
+        assert!(maze.is_perfect());
+        assert!(maze.cycles().is_empty());
+
+        let mut added = 0;
+        for coordinates in [(1, 1), (4, 2), (6, 4)]
+            .iter()
+            .map(|c| Coordinates::from(*c))
+        {
+            if !maze.has_passage(&coordinates, &Direction::East) {
+                maze.carve(coordinates, Direction::East)?;
+                added += 1;
+            }
+        }
+        assert!(added > 0);
+        assert!(!maze.is_perfect());
+
+        let cycles = maze.cycles();
+        assert_eq!(cycles.len(), added);
+        for cycle in cycles.iter() {
+            // consecutive fields are connected, including the last and the first one
+            for (i, a) in cycle.iter().enumerate() {
+                let b = cycle[(i + 1) % cycle.len()];
+                assert!(maze.has_passage_between(*a, b));
+            }
+        }
+
+        // walling off dead ends leaves the maze loop-free but disconnected
+        let mut sparse = PrimsGenerator::new(Some([6; 32])).generate(8, 6)?;
+        sparse.sparsify(1);
+        assert!(sparse.cycles().is_empty());
+        assert!(!sparse.is_perfect());
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+use rand::prelude::*;
+
+use crate::prelude::*;
+
+impl Maze {
+    /// Coordinates of all dead ends, i.e. fields with exactly one passage, in row-major order
+    ///
+    /// Start and goal are included if they are dead ends.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// assert_eq!(
+    ///     maze.dead_ends(),
+    ///     vec![Coordinates::new(0, 0), Coordinates::new(1, 0), Coordinates::new(0, 2)]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn dead_ends(&self) -> Vec<Coordinates> {
+        self.coordinates()
+            .filter(|c| self.passage_count(*c) == 1)
+            .collect()
+    }
+
+    /// Retract dead-end corridors by walling off every dead end `iterations` times.
+    ///
+    /// Every iteration shortens each dead-end corridor by one field, turning the removed fields
+    /// into unused fields without any passages.
+    /// This is the classic "sparseness" pass used for creating cave-like dungeon layouts.
+    /// Start and goal are never walled off so the goal stays reachable.
+    ///
+    /// Stops early if no dead ends are left and returns how many fields were walled off.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    ///
+    /// let mut maze = PrimsGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let removed = maze.sparsify(3);
+    ///
+    /// assert!(removed > 0);
+    /// assert!(maze.difficulty().solution_length > 0);
+    /// ```
+    pub fn sparsify(&mut self, iterations: usize) -> usize {
+        let mut removed = 0;
+        for _ in 0..iterations {
+            let dead_ends: Vec<_> = self
+                .dead_ends()
+                .into_iter()
+                .filter(|c| *c != self.start && *c != self.goal)
+                .collect();
+            if dead_ends.is_empty() {
+                break;
+            }
+
+            for coordinates in dead_ends {
+                // two dead ends can face each other so the passage may already be gone
+                let next = self.passage_neighbours(coordinates).next();
+                if let Some(next) = next {
+                    self.passages.remove(coordinates, next);
+                }
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// Remove dead ends by connecting each of them to another neighbour with the given
+    /// `chance`, turning a perfect maze into a braid maze with loops.
+    ///
+    /// Dead ends are preferably connected to neighbours which are dead ends themselves so that
+    /// both are removed by a single passage.
+    /// Fields without any passages, e.g. after [`Maze::sparsify()`], are never connected.
+    ///
+    /// Returns how many passages were added.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaChaRng;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let mut rng = ChaChaRng::from_seed([1; 32]);
+    /// let added = maze.braid(1.0, &mut rng);
+    ///
+    /// assert!(maze.dead_ends().is_empty());
+    /// assert_eq!(maze.cycles().len(), added);
+    /// ```
+    pub fn braid<R: Rng + ?Sized>(&mut self, chance: f64, rng: &mut R) -> usize {
+        let chance = chance.clamp(0.0, 1.0);
+        let mut added = 0;
+        for coordinates in self.dead_ends() {
+            // connecting an earlier dead end may already have removed this one
+            if self.passage_count(coordinates) != 1 || !rng.gen_bool(chance) {
+                continue;
+            }
+
+            let candidates: Vec<Coordinates> = self
+                .adjacent_cells(&coordinates)
+                .filter(|next| {
+                    self.passage_count(*next) > 0 && !self.has_passage_between(coordinates, *next)
+                })
+                .collect();
+            let dead_end_candidates: Vec<Coordinates> = candidates
+                .iter()
+                .copied()
+                .filter(|next| self.passage_count(*next) == 1)
+                .collect();
+            let next = dead_end_candidates
+                .choose(rng)
+                .or_else(|| candidates.choose(rng));
+
+            if let Some(next) = next.copied() {
+                self.add_passage(coordinates, next);
+                added += 1;
+            }
+        }
+
+        added
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::Generator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_sparsify_keeps_solution() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([7; 32])).generate(8, 8)?;
+        let solution = maze.find_path(maze.start, maze.goal);
+
+        let removed = maze.sparsify(usize::MAX);
+        assert!(removed > 0);
+        // with enough iterations only the solution is left
+        assert_eq!(maze.find_path(maze.start, maze.goal), solution);
+        assert_eq!(
+            maze.coordinates()
+                .filter(|c| maze.passage_count(*c) > 0)
+                .count(),
+            solution.map_or(0, |path| path.len())
+        );
+        assert_eq!(maze.sparsify(1), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_braid_only_adds_passages() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([18; 32])).generate(9, 6)?;
+        let mut rng = ChaChaRng::from_seed([18; 32]);
+
+        let mut untouched = maze.clone();
+        assert_eq!(untouched.braid(0.0, &mut rng), 0);
+        assert_eq!(untouched, maze);
+
+        let mut braided = maze.clone();
+        let added = braided.braid(0.5, &mut rng);
+        assert!(added > 0);
+        assert!(braided.dead_ends().len() < maze.dead_ends().len());
+        assert_eq!(
+            braided.passages.passage_count(),
+            maze.passages.passage_count() + added
+        );
+        for coordinates in maze.coordinates() {
+            for next in maze.passage_neighbours(coordinates) {
+                assert!(braided.has_passage_between(coordinates, next));
+            }
+        }
+
+        // isolated fields stay isolated
+        let mut sparse = maze;
+        sparse.sparsify(2);
+        let isolated = sparse.unreachable_cells();
+        sparse.braid(1.0, &mut rng);
+        assert_eq!(sparse.unreachable_cells(), isolated);
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use std::ops::RangeInclusive;
+
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Weight of the relative solution length in [`DifficultyScore::score`]
+const SOLUTION_LENGTH_WEIGHT: f64 = 0.4;
+/// Weight of the decision density along the solution in [`DifficultyScore::score`]
+const DECISIONS_WEIGHT: f64 = 0.4;
+/// Weight of the dead-end density in [`DifficultyScore::score`]
+const DEAD_END_WEIGHT: f64 = 0.2;
+
+/// Estimation of how hard it is to solve a [`Maze`].
+///
+/// Next to the combined [`score`](DifficultyScore::score), the individual components it is
+/// made of are exposed as well so that applications can apply their own weighting.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DifficultyScore {
+    /// Number of fields on the shortest route from start to goal, including both of them.
+    ///
+    /// This is `0` if the goal cannot be reached from the start.
+    pub solution_length: usize,
+    /// Number of fields along the solution at which more than one unexplored passage could be
+    /// taken, i.e. at which a user has to make a decision
+    pub decisions: usize,
+    /// Share of fields which are dead ends (fields with exactly one passage), in range `0..=1`
+    pub dead_end_density: f64,
+    /// Combined and normalized difficulty in range `0..=1` with higher values being harder
+    pub score: f64,
+}
+
+/// Band of acceptable [`DifficultyScore::score`] values
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DifficultyRange {
+    /// Lowest acceptable score (inclusive)
+    pub min: f64,
+    /// Highest acceptable score (inclusive)
+    pub max: f64,
+}
+
+impl DifficultyRange {
+    /// Create a new range which accepts scores between `min` and `max` (both inclusive)
+    #[must_use]
+    pub fn new(min: f64, max: f64) -> Self {
+        DifficultyRange { min, max }
+    }
+
+    /// Whether the given difficulty lies inside this range
+    #[must_use]
+    pub fn contains(&self, difficulty: &DifficultyScore) -> bool {
+        difficulty.score >= self.min && difficulty.score <= self.max
+    }
+}
+
+impl From<RangeInclusive<f64>> for DifficultyRange {
+    fn from(range: RangeInclusive<f64>) -> Self {
+        DifficultyRange::new(*range.start(), *range.end())
+    }
+}
+
+/// Errors which can occur while generating a maze of a specific difficulty
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DifficultyError {
+    /// None of the generated mazes had a difficulty inside the requested range
+    #[error("Could not generate a maze of the requested difficulty in {0} attempts")]
+    AttemptsExhausted(usize),
+}
+
+/// Generate a maze whose difficulty lies inside of `target`.
+///
+/// Mazes are generated with generators created by `new_generator` from seeds drawn from `rng`
+/// until one of them falls into the requested difficulty band or `max_attempts` is reached.
+/// The returned seed can be passed to `new_generator` to reproduce the maze, while passing an
+/// equally seeded `rng` reproduces the whole search.
+///
+/// Besides `width`, `height`, `target` and `max_attempts`, this takes a factory for generators,
+/// since every attempt needs a freshly seeded one, and the `rng` from which the seeds are drawn
+/// instead of the thread-local one so that the search can be reproduced.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaChaRng;
+///
+/// let mut rng = ChaChaRng::from_seed([1; 32]);
+/// let (maze, seed) =
+///     generate_with_difficulty(RbGenerator::new, &mut rng, 10, 10, (0.3..=1.0).into(), 100)
+///         .unwrap();
+/// assert!(maze.difficulty().score >= 0.3);
+/// assert_eq!(RbGenerator::new(Some(seed)).generate(10, 10).unwrap(), maze);
+/// ```
+pub fn generate_with_difficulty<G, F, R>(
+    mut new_generator: F,
+    rng: &mut R,
+    width: i32,
+    height: i32,
+    target: DifficultyRange,
+    max_attempts: usize,
+) -> Result<(Maze, Seed), MazeError>
+where
+    G: Generator,
+    F: FnMut(Option<Seed>) -> G,
+    R: Rng + ?Sized,
+{
+    for _ in 0..max_attempts {
+        let seed: Seed = rng.gen();
+        let maze = new_generator(Some(seed)).generate(width, height)?;
+        if target.contains(&maze.difficulty()) {
+            return Ok((maze, seed));
+        }
+    }
+
+    Err(DifficultyError::AttemptsExhausted(max_attempts).into())
+}
+
+impl Maze {
+    /// Estimate how difficult it is to solve this maze.
+    ///
+    /// The score combines the length of the solution relative to the maze size, how often a
+    /// decision between multiple passages has to be made along the solution and how many dead
+    /// ends the maze contains.
+    #[must_use]
+    pub fn difficulty(&self) -> DifficultyScore {
+        let field_count = (self.size.0 * self.size.1) as usize;
+        let dead_end_density = self.dead_ends().len() as f64 / field_count as f64;
+
+        let solution = self.find_path(self.start, self.goal).unwrap_or_default();
+        let decisions = solution
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                // no decision is needed at the goal and the passage through which a field was
+                // entered is not an option to choose from
+                let entered = if *i == 0 { 0 } else { 1 };
+                *i + 1 < solution.len() && self.passage_count(**c) - entered > 1
+            })
+            .count();
+
+        let length_ratio = if field_count > 1 && !solution.is_empty() {
+            (solution.len() - 1) as f64 / (field_count - 1) as f64
+        } else {
+            0.0
+        };
+        let decision_ratio = if solution.len() > 1 {
+            decisions as f64 / (solution.len() - 1) as f64
+        } else {
+            0.0
+        };
+        // in a perfect maze at most about half of all fields can be dead ends
+        let dead_end_ratio = (dead_end_density * 2.0).min(1.0);
+
+        DifficultyScore {
+            solution_length: solution.len(),
+            decisions,
+            dead_end_density,
+            score: SOLUTION_LENGTH_WEIGHT * length_ratio
+                + DECISIONS_WEIGHT * decision_ratio
+                + DEAD_END_WEIGHT * dead_end_ratio,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::{generate_with_difficulty, DifficultyError, Generator, MazeError};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_difficulty_components() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([42; 32])).generate(3, 3)?;
+        let difficulty = maze.difficulty();
+
+        // see the crate level documentation for a rendering of this maze
+        assert_eq!(difficulty.solution_length, 7);
+        assert_eq!(difficulty.decisions, 1);
+        assert!((difficulty.dead_end_density - 3.0 / 9.0).abs() < f64::EPSILON);
+        assert!(difficulty.score > 0.0 && difficulty.score <= 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_field_maze_is_trivial() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([42; 32])).generate(1, 1)?;
+        let difficulty = maze.difficulty();
+
+        assert_eq!(difficulty.solution_length, 1);
+        assert_eq!(difficulty.decisions, 0);
+        assert_eq!(difficulty.score, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_impossible_difficulty_is_reported() {
+        let mut rng = ChaChaRng::from_seed([3; 32]);
+        let result =
+            generate_with_difficulty(RbGenerator::new, &mut rng, 3, 3, (2.0..=3.0).into(), 5);
+
+        assert_eq!(
+            result.err(),
+            Some(MazeError::Difficulty(DifficultyError::AttemptsExhausted(5)))
+        );
+    }
+
+    #[test]
+    fn test_equally_seeded_rngs_find_the_same_maze() -> anyhow::Result<()> {
+        let target = (0.2..=1.0).into();
+        let mut rng1 = ChaChaRng::from_seed([4; 32]);
+        let mut rng2 = ChaChaRng::from_seed([4; 32]);
+
+        let first = generate_with_difficulty(RbGenerator::new, &mut rng1, 8, 8, target, 50)?;
+        let second = generate_with_difficulty(RbGenerator::new, &mut rng2, 8, 8, target, 50)?;
+        assert_eq!(first, second);
+        Ok(())
+    }
+}
@@ -1,4 +1,10 @@
+use std::convert::TryFrom;
+
+use rand::distributions::{Distribution, Standard};
 use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::gen_index;
 
 /// The four cardinal directions
 ///
@@ -17,6 +23,7 @@ pub enum Direction {
 
 impl Direction {
     /// Return the opposite direction of self
+    #[must_use]
     pub fn opposite(&self) -> Direction {
         match self {
             Direction::North => Direction::South,
@@ -26,6 +33,89 @@ impl Direction {
         }
     }
 
+    /// The change of `(x, y)` coordinates when moving one step into this direction
+    ///
+    /// North points towards smaller `y` values, see
+    /// [`CoordinateSystem`](crate::prelude::CoordinateSystem) for upwards pointing `y` axes.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use maze_generator::prelude::*;
+    ///
+    /// assert_eq!(Direction::North.delta(), (0, -1));
+    /// assert_eq!(Direction::try_from((1, 0)), Ok(Direction::East));
+    /// assert!(Direction::try_from((1, 1)).is_err());
+    /// ```
+    #[must_use]
+    pub fn delta(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    /// The direction after turning 90° clockwise
+    #[must_use]
+    pub fn rotate_cw(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    /// The direction after turning 90° counterclockwise
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Direction {
+        self.opposite().rotate_cw()
+    }
+
+    /// Position of this direction in [`Direction::all()`], e.g. for indexing per-direction
+    /// tables
+    #[must_use]
+    pub fn index(&self) -> usize {
+        match self {
+            Direction::North => 0,
+            Direction::East => 1,
+            Direction::South => 2,
+            Direction::West => 3,
+        }
+    }
+
+    /// Generate a list of all directions in random order in which directions with a higher
+    /// `weight` tend to come first
+    pub(crate) fn gen_weighted_order(
+        rng: &mut impl Rng,
+        weight: impl Fn(&Direction) -> f64,
+    ) -> [Direction; 4] {
+        // weighted sampling without replacement by sorting along random keys whose
+        // distribution is skewed towards 0 for high weights, computed as ln(u^(1/weight)) so
+        // that large weights do not all round to the same key, and directions with a weight of
+        // zero all get a key of minus infinity and are ordered by the random value itself
+        let mut keyed: Vec<((f64, f64), Direction)> = Direction::iter()
+            .map(|direction| {
+                let u: f64 = rng.gen();
+                ((u.ln() / weight(&direction).max(0.0), u), direction)
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut directions = Direction::all();
+        for (slot, (_, direction)) in directions.iter_mut().zip(keyed) {
+            *slot = direction;
+        }
+        directions
+    }
+
+    /// Iterate over all directions, clockwise starting at [`Direction::North`]
+    pub fn iter() -> impl Iterator<Item = Direction> {
+        IntoIterator::into_iter(Self::all())
+    }
+
     /// Generate a list of all collections but in random order
     pub fn gen_random_order(rng: &mut impl Rng) -> [Direction; 4] {
         let mut directions = Self::all();
@@ -34,6 +124,7 @@ impl Direction {
     }
 
     /// Return all directions as array
+    #[must_use]
     pub fn all() -> [Direction; 4] {
         [
             Direction::North,
@@ -44,6 +135,97 @@ impl Direction {
     }
 }
 
+/// Error returned when converting a coordinate delta which is not a single step along one
+/// axis into a [`Direction`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+#[error("({0}, {1}) is not a step into one of the four directions")]
+pub struct InvalidDeltaError(pub i32, pub i32);
+
+impl TryFrom<(i32, i32)> for Direction {
+    type Error = InvalidDeltaError;
+
+    fn try_from((dx, dy): (i32, i32)) -> Result<Self, Self::Error> {
+        Direction::iter()
+            .find(|direction| direction.delta() == (dx, dy))
+            .ok_or(InvalidDeltaError(dx, dy))
+    }
+}
+
+/// Directions can be sampled uniformly via `rng.gen::<Direction>()`
+impl Distribution<Direction> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Direction {
+        Direction::all()[gen_index(rng, 4)]
+    }
+}
+
+/// Relative weights with which carving generators choose between horizontal and vertical
+/// directions
+///
+/// Preferring one orientation results in long corridors running in that orientation, which
+/// controls the "flow" of a maze.
+/// Only the ratio between both weights matters and the default weights both orientations
+/// equally.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let mut generator = RbGenerator::new(Some([42; 32]));
+/// // horizontal rivers
+/// generator.direction_bias = DirectionBias::new(5.0, 1.0);
+/// let maze = generator.generate(10, 10).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DirectionBias {
+    /// Weight of [`Direction::East`] and [`Direction::West`], should not be negative
+    pub horizontal: f64,
+    /// Weight of [`Direction::North`] and [`Direction::South`], should not be negative
+    pub vertical: f64,
+}
+
+impl DirectionBias {
+    /// Create a bias with the given weights
+    #[must_use]
+    pub fn new(horizontal: f64, vertical: f64) -> Self {
+        DirectionBias {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// The weight of `direction`
+    #[must_use]
+    pub fn weight(&self, direction: &Direction) -> f64 {
+        match direction {
+            Direction::East | Direction::West => self.horizontal,
+            Direction::North | Direction::South => self.vertical,
+        }
+    }
+
+    /// Whether all directions are weighted equally
+    pub(crate) fn is_uniform(&self) -> bool {
+        self.horizontal == self.vertical
+    }
+
+    /// Generate a list of all directions in random order in which directions with a higher
+    /// weight tend to come first
+    ///
+    /// With equal weights this is the same as [`Direction::gen_random_order()`].
+    pub fn gen_random_order(&self, rng: &mut impl Rng) -> [Direction; 4] {
+        if self.is_uniform() {
+            return Direction::gen_random_order(rng);
+        }
+        Direction::gen_weighted_order(rng, |direction| self.weight(direction))
+    }
+}
+
+impl Default for DirectionBias {
+    fn default() -> Self {
+        DirectionBias::new(1.0, 1.0)
+    }
+}
+
 impl std::fmt::Display for Direction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
@@ -54,3 +236,30 @@ impl std::fmt::Display for Direction {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use rand::prelude::*;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::Direction;
+
+    #[test]
+    fn test_direction_tables_agree() {
+        for (i, direction) in Direction::iter().enumerate() {
+            assert_eq!(direction.index(), i);
+            assert_eq!(Direction::try_from(direction.delta()), Ok(direction));
+            assert_eq!(direction.rotate_cw().rotate_ccw(), direction);
+            assert_eq!(direction.rotate_cw().rotate_cw(), direction.opposite());
+        }
+
+        let mut rng = ChaChaRng::from_seed([0; 32]);
+        let mut counts = [0; 4];
+        for _ in 0..400 {
+            counts[rng.gen::<Direction>().index()] += 1;
+        }
+        assert!(counts.iter().all(|count| *count > 50));
+    }
+}
@@ -0,0 +1,52 @@
+/// Union-find structure over the fields of a maze
+pub(crate) struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    pub(crate) fn new(len: usize) -> Self {
+        DisjointSets {
+            parents: (0..len).collect(),
+        }
+    }
+
+    /// Representative of the set which contains `i`
+    pub(crate) fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+        i
+    }
+
+    /// Join the sets of `a` and `b`, returning whether they were separate before
+    pub(crate) fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a] = b;
+        a != b
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DisjointSets;
+
+    #[test]
+    fn test_union_merges_whole_sets() {
+        let mut sets = DisjointSets::new(6);
+        assert!((0..6).all(|i| sets.find(i) == i));
+
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(3, 4));
+        assert!(!sets.union(4, 2));
+        assert_eq!(sets.find(0), sets.find(1));
+        assert_eq!(sets.find(2), sets.find(4));
+        assert_ne!(sets.find(1), sets.find(2));
+
+        assert!(sets.union(1, 4));
+        assert!((0..5).all(|i| sets.find(i) == sets.find(0)));
+        assert_ne!(sets.find(5), sets.find(0));
+        assert!(!sets.union(0, 3));
+    }
+}
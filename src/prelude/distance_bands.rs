@@ -0,0 +1,72 @@
+use crate::prelude::*;
+
+impl Maze {
+    /// Group all fields reachable from `from` by their distance from it, e.g. for colouring
+    /// the maze like a rainbow.
+    ///
+    /// Band `i` contains the fields whose distance in steps lies in
+    /// `i * band_width..(i + 1) * band_width`, in the order of [`Maze::bfs_order()`].
+    /// A `band_width` of 0 is treated like 1 so that every distance gets its own band.
+    /// No band is empty except when `from` lies outside of the maze, in which case there are no
+    /// bands at all.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// let bands = maze.distance_bands(maze.start, 3);
+    ///
+    /// assert_eq!(bands[0][0], maze.start);
+    /// assert_eq!(bands.iter().map(Vec::len).sum::<usize>(), 25);
+    /// ```
+    #[must_use]
+    pub fn distance_bands(&self, from: Coordinates, band_width: u32) -> Vec<Vec<Coordinates>> {
+        let band_width = band_width.max(1);
+        let mut bands: Vec<Vec<Coordinates>> = Vec::new();
+        for (coordinates, distance) in self.bfs_order(from) {
+            // the distances never decrease so only the last band can still grow
+            let band = (distance / band_width) as usize;
+            if band == bands.len() {
+                bands.push(Vec::new());
+            }
+            if let Some(fields) = bands.last_mut() {
+                fields.push(coordinates);
+            }
+        }
+
+        bands
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Maze};
+
+    #[test]
+    fn test_bands_cover_distance_ranges() -> anyhow::Result<()> {
+        let maze = Maze::from_ascii(
+            "·-·-·-·-·
+|S      |
+·-·-·-· ·
+|G      |
+·-·-·-·-·
+",
+        )?;
+        let c = Coordinates::new;
+
+        assert_eq!(
+            maze.distance_bands(maze.start, 3),
+            vec![
+                vec![c(0, 0), c(1, 0), c(2, 0)],
+                vec![c(3, 0), c(3, 1), c(2, 1)],
+                vec![c(1, 1), c(0, 1)],
+            ]
+        );
+        assert_eq!(maze.distance_bands(maze.start, 0).len(), 8);
+        assert_eq!(maze.distance_bands(maze.goal, 100).len(), 1);
+        assert!(maze.distance_bands(Coordinates::new(4, 0), 1).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+
+use crate::prelude::*;
+
+/// Marks pairs of fields which are not connected to each other in [`DistanceMatrix::as_slice()`]
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Lengths of the shortest routes between all pairs of fields of a maze, see
+/// [`Maze::shortest_path_lengths_all_pairs()`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DistanceMatrix {
+    size: (i32, i32),
+    /// `n * n` distances for `n` fields, ordered by the row-major indices of both fields
+    distances: Vec<u32>,
+}
+
+impl DistanceMatrix {
+    /// Number of fields, which is both the number of rows and of columns of the matrix
+    #[must_use]
+    pub fn field_count(&self) -> usize {
+        self.size.0.max(0) as usize * self.size.1.max(0) as usize
+    }
+
+    /// Row-major index of the field at `coordinates` inside of the matrix
+    #[must_use]
+    pub fn index(&self, coordinates: &Coordinates) -> Option<usize> {
+        let (width, height) = self.size;
+        if !RectangularTopology::new(width, height).contains(coordinates) {
+            return None;
+        }
+        Some(coordinates.y as usize * width as usize + coordinates.x as usize)
+    }
+
+    /// Number of steps along the shortest route between the fields at `a` and `b`.
+    ///
+    /// Returns `None` for coordinates outside of the maze and for fields which are not connected
+    /// to each other.
+    #[must_use]
+    pub fn distance(&self, a: &Coordinates, b: &Coordinates) -> Option<u32> {
+        let (i_a, i_b) = (self.index(a)?, self.index(b)?);
+        match self.distances[i_a * self.field_count() + i_b] {
+            UNREACHABLE => None,
+            distance => Some(distance),
+        }
+    }
+
+    /// The whole matrix, e.g. for using it as training labels.
+    ///
+    /// Entry `i * n + j` is the distance from the field with [index](DistanceMatrix::index) `i`
+    /// to the one with index `j` for `n` [fields](DistanceMatrix::field_count).
+    /// Pairs which are not connected to each other are marked by `u32::MAX`.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u32] {
+        &self.distances
+    }
+
+    /// Mean number of steps between two different fields which are connected to each other,
+    /// `None` if there are no such fields
+    #[must_use]
+    pub fn average_path_length(&self) -> Option<f64> {
+        let (sum, count) = self
+            .distances
+            .iter()
+            .filter(|distance| **distance != 0 && **distance != UNREACHABLE)
+            .fold((0u64, 0u64), |(sum, count), distance| {
+                (sum + u64::from(*distance), count + 1)
+            });
+        if count == 0 {
+            None
+        } else {
+            Some(sum as f64 / count as f64)
+        }
+    }
+}
+
+impl Maze {
+    /// Compute the lengths of the shortest routes between all pairs of fields, e.g. for
+    /// analytics like the average path length or for labelling training data.
+    ///
+    /// The matrix needs `n²` entries for `n` fields, so this is only meant for small mazes:
+    /// a maze of 100x100 fields already needs 400 MB.
+    /// For loop-free mazes, as produced by all generators, the distances from a field are
+    /// derived from those of its neighbour in the tree of passages in `O(n)` time.
+    /// Mazes with loops fall back to a breadth-first search from every field, which takes
+    /// `O(n)` time per field as well.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// let matrix = maze.shortest_path_lengths_all_pairs();
+    ///
+    /// let solution_length = maze.difficulty().solution_length as u32;
+    /// assert_eq!(matrix.distance(&maze.start, &maze.goal), Some(solution_length - 1));
+    /// assert_eq!(matrix.as_slice().len(), 25 * 25);
+    /// ```
+    #[must_use]
+    pub fn shortest_path_lengths_all_pairs(&self) -> DistanceMatrix {
+        let (width, height) = (self.size.0 as usize, self.size.1 as usize);
+        let n = width * height;
+        let mut distances = vec![UNREACHABLE; n * n];
+        let index =
+            |coordinates: &Coordinates| coordinates.y as usize * width + coordinates.x as usize;
+
+        // depth-first spanning forest so that the subtree of every field forms a contiguous
+        // range of `order`
+        let mut order = Vec::with_capacity(n);
+        let mut parents = vec![None; n];
+        let mut depths = vec![0; n];
+        let mut subtree_sizes = vec![1; n];
+        let mut trees = Vec::new();
+        let mut visited = vec![false; n];
+        let mut tree_passages = 0;
+        for root in self.coordinates() {
+            if visited[index(&root)] {
+                continue;
+            }
+            let tree_start = order.len();
+            visited[index(&root)] = true;
+            let mut stack = vec![root];
+            while let Some(current) = stack.pop() {
+                let i_current = index(&current);
+                order.push(i_current);
+                for next in self.passage_neighbours(current) {
+                    let i_next = index(&next);
+                    if !visited[i_next] {
+                        visited[i_next] = true;
+                        parents[i_next] = Some(i_current);
+                        depths[i_next] = depths[i_current] + 1;
+                        tree_passages += 1;
+                        stack.push(next);
+                    }
+                }
+            }
+            trees.push(tree_start..order.len());
+        }
+        // children are pushed after their parents so summing up in reverse visits them first
+        let mut positions = vec![0; n];
+        for (position, i_field) in order.iter().enumerate() {
+            positions[*i_field] = position;
+        }
+        for i_field in order.iter().rev() {
+            if let Some(parent) = parents[*i_field] {
+                subtree_sizes[parent] += subtree_sizes[*i_field];
+            }
+        }
+
+        if self.passages.passage_count() != tree_passages {
+            // routes are not unique so every row needs its own search
+            let coordinates: Vec<Coordinates> = self.coordinates().collect();
+            for (i_from, from) in coordinates.iter().enumerate() {
+                let row = &mut distances[i_from * n..(i_from + 1) * n];
+                row[i_from] = 0;
+                let mut queue = VecDeque::new();
+                queue.push_back(*from);
+                while let Some(current) = queue.pop_front() {
+                    let distance = row[index(&current)] + 1;
+                    for next in self.passage_neighbours(current) {
+                        if row[index(&next)] == UNREACHABLE {
+                            row[index(&next)] = distance;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+            }
+            return DistanceMatrix {
+                size: self.size,
+                distances,
+            };
+        }
+
+        for tree in trees {
+            let members = &order[tree.clone()];
+            for i_field in members.iter() {
+                let row_start = i_field * n;
+                match parents[*i_field] {
+                    None => {
+                        // the distances from the root are the depths
+                        for i_member in members.iter() {
+                            distances[row_start + i_member] = depths[*i_member];
+                        }
+                    }
+                    Some(parent) => {
+                        // one step further away from everything except the own subtree, which
+                        // comes one step closer instead
+                        let parent_start = parent * n;
+                        for i_member in members.iter() {
+                            distances[row_start + i_member] =
+                                distances[parent_start + i_member] + 1;
+                        }
+                        let subtree =
+                            positions[*i_field]..positions[*i_field] + subtree_sizes[*i_field];
+                        for i_member in order[subtree].iter() {
+                            distances[row_start + i_member] -= 2;
+                        }
+                    }
+                }
+            }
+        }
+
+        DistanceMatrix {
+            size: self.size,
+            distances,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Generator, Maze};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    /// Check every entry of the matrix of `maze` against a breadth-first search
+    fn assert_matches_search(maze: &Maze) {
+        let matrix = maze.shortest_path_lengths_all_pairs();
+        for a in maze.coordinates() {
+            let search = maze.breadth_first_search(a);
+            for b in maze.coordinates() {
+                let expected = search.get(&b).map(|(distance, _)| *distance as u32);
+                assert_eq!(matrix.distance(&a, &b), expected, "{} -> {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_matches_search() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([9; 32])).generate(8, 6)?;
+        assert_matches_search(&maze);
+
+        maze.sparsify(2);
+        assert_matches_search(&maze);
+
+        maze.braid(1.0, &mut rand::rngs::mock::StepRng::new(0, 1));
+        assert!(!maze.cycles().is_empty());
+        assert_matches_search(&maze);
+        Ok(())
+    }
+
+    #[test]
+    fn test_average_path_length() -> anyhow::Result<()> {
+        let corridor = Maze::from_ascii("·-·-·-·\n|S   G|\n·-·-·-·\n")?;
+        let matrix = corridor.shortest_path_lengths_all_pairs();
+        // (1 + 2 + 1) * 2 ordered pairs out of 6
+        assert_eq!(matrix.average_path_length(), Some(8.0 / 6.0));
+        assert_eq!(
+            matrix.distance(&Coordinates::new(0, 0), &Coordinates::new(3, 0)),
+            None
+        );
+        assert_eq!(matrix.as_slice(), &[0, 1, 2, 1, 0, 1, 2, 1, 0]);
+
+        let closed = Maze::from_ascii("·-·\n|S|\n·-·\n")?;
+        assert_eq!(
+            closed
+                .shortest_path_lengths_all_pairs()
+                .average_path_length(),
+            None
+        );
+        Ok(())
+    }
+}
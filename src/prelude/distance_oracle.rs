@@ -0,0 +1,184 @@
+use std::collections::VecDeque;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while building a [`DistanceOracle`] via
+/// [`Maze::build_distance_oracle()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DistanceOracleError {
+    /// The oracle relies on every route being unique which is not the case for mazes with loops
+    #[error("The maze contains {count} loops so routes between fields are not unique")]
+    ContainsLoops {
+        /// Number of independent loops, as listed by [`Maze::cycles()`]
+        count: usize,
+    },
+}
+
+/// Answers distance queries between arbitrary fields of a loop-free maze in `O(log n)` time,
+/// see [`Maze::build_distance_oracle()`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DistanceOracle {
+    size: (i32, i32),
+    /// Distance of every field to the root of its tree in row-major order
+    depths: Vec<usize>,
+    /// Tree to which every field belongs in row-major order
+    trees: Vec<usize>,
+    /// `ancestors[k][i]` is the field `2^k` steps closer to the root than field `i`, or the root
+    /// itself when it is closer than that
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl DistanceOracle {
+    fn index(&self, coordinates: &Coordinates) -> Option<usize> {
+        let (width, height) = self.size;
+        if !RectangularTopology::new(width, height).contains(coordinates) {
+            return None;
+        }
+        Some(coordinates.y as usize * width as usize + coordinates.x as usize)
+    }
+
+    /// Number of steps along the route between the fields at `a` and `b`.
+    ///
+    /// Returns `None` for coordinates outside of the maze and for fields which are not connected
+    /// to each other, e.g. after [`Maze::sparsify()`].
+    #[must_use]
+    pub fn distance(&self, a: &Coordinates, b: &Coordinates) -> Option<usize> {
+        let (mut i_a, mut i_b) = (self.index(a)?, self.index(b)?);
+        if self.trees[i_a] != self.trees[i_b] {
+            return None;
+        }
+        let depth_sum = self.depths[i_a] + self.depths[i_b];
+
+        // lift the deeper field to the depth of the other one, then lift both right below their
+        // lowest common ancestor
+        if self.depths[i_a] < self.depths[i_b] {
+            std::mem::swap(&mut i_a, &mut i_b);
+        }
+        let difference = self.depths[i_a] - self.depths[i_b];
+        for (k, ancestors) in self.ancestors.iter().enumerate() {
+            if difference & (1 << k) != 0 {
+                i_a = ancestors[i_a];
+            }
+        }
+        if i_a != i_b {
+            for ancestors in self.ancestors.iter().rev() {
+                if ancestors[i_a] != ancestors[i_b] {
+                    i_a = ancestors[i_a];
+                    i_b = ancestors[i_b];
+                }
+            }
+            i_a = self.ancestors.first().map_or(i_a, |parents| parents[i_a]);
+        }
+
+        Some(depth_sum - 2 * self.depths[i_a])
+    }
+}
+
+impl Maze {
+    /// Precompute the structure of the maze so that the distance between any two fields can be
+    /// queried quickly, e.g. for agents which repeatedly need to know how far away something is.
+    ///
+    /// Because every route through a loop-free maze is unique, the distance between two fields
+    /// follows from their lowest common ancestor in the tree of passages.
+    /// Building the oracle takes `O(n log n)` time and memory for `n` fields after which every
+    /// query takes `O(log n)` time instead of a search through the whole maze.
+    /// Fields which are not connected to each other are supported.
+    ///
+    /// All generators produce perfect mazes which never contain loops.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::growing_tree::GrowingTreeGenerator;
+    ///
+    /// let maze = GrowingTreeGenerator::new(Some([42; 32])).generate(20, 20).unwrap();
+    /// let oracle = maze.build_distance_oracle().unwrap();
+    ///
+    /// // the solution length counts fields while the distance counts steps between them
+    /// let solution_length = maze.difficulty().solution_length;
+    /// assert_eq!(oracle.distance(&maze.start, &maze.goal), Some(solution_length - 1));
+    /// ```
+    pub fn build_distance_oracle(&self) -> Result<DistanceOracle, DistanceOracleError> {
+        let field_count = self.topology().cell_count();
+        let mut oracle = DistanceOracle {
+            size: self.size,
+            depths: vec![0; field_count],
+            trees: vec![usize::MAX; field_count],
+            ancestors: Vec::new(),
+        };
+        let mut parents: Vec<usize> = (0..field_count).collect();
+
+        // spanning forest of all fields, even those that are not reachable from the start
+        let mut tree_passages = 0;
+        let mut queue = VecDeque::new();
+        for (tree, root) in self.coordinates().enumerate() {
+            match oracle.index(&root) {
+                Some(i) if oracle.trees[i] == usize::MAX => oracle.trees[i] = tree,
+                _ => continue,
+            }
+            queue.push_back(root);
+            while let Some(i_coords) = queue.pop_front() {
+                let i = oracle.index(&i_coords).unwrap_or_default();
+                for i_next in self.passage_neighbours(i_coords) {
+                    match oracle.index(&i_next) {
+                        Some(i_n) if oracle.trees[i_n] == usize::MAX => {
+                            oracle.trees[i_n] = tree;
+                            oracle.depths[i_n] = oracle.depths[i] + 1;
+                            parents[i_n] = i;
+                            tree_passages += 1;
+                            queue.push_back(i_next);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // every passage which is not part of the forest closes a loop
+        let loops = self.passages.passage_count() - tree_passages;
+        if loops > 0 {
+            return Err(DistanceOracleError::ContainsLoops { count: loops });
+        }
+
+        let max_depth = oracle.depths.iter().copied().max().unwrap_or(0);
+        oracle.ancestors.push(parents);
+        while 1 << oracle.ancestors.len() <= max_depth {
+            let previous = &oracle.ancestors[oracle.ancestors.len() - 1];
+            let next = previous.iter().map(|i| previous[*i]).collect();
+            oracle.ancestors.push(next);
+        }
+
+        Ok(oracle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, DistanceOracleError, Generator, Room};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_oracle_matches_search() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([8; 32])).generate(9, 7)?;
+        maze.sparsify(2);
+        let oracle = maze.build_distance_oracle()?;
+
+        for a in maze.coordinates() {
+            let search = maze.breadth_first_search(a);
+            for b in maze.coordinates() {
+                let expected = search.get(&b).map(|(distance, _)| *distance);
+                assert_eq!(oracle.distance(&a, &b), expected);
+            }
+        }
+        assert_eq!(oracle.distance(&maze.start, &Coordinates::new(9, 0)), None);
+
+        maze.carve_room(Room::new(Coordinates::new(0, 0), 2, 2))?;
+        assert!(matches!(
+            maze.build_distance_oracle(),
+            Err(DistanceOracleError::ContainsLoops { count }) if count >= 1
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+/// Options for generating GraphViz DOT output
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = DotOptions::new().name("labyrinth").positions(false);
+/// assert_eq!(options.name, "labyrinth");
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct DotOptions {
+    /// Name of the generated graph, default: "maze"
+    pub name: String,
+    /// Whether to pin nodes to their grid position (honored by the `neato` and `fdp` layout
+    /// engines), default: true
+    pub positions: bool,
+    /// Fill colour of the start node, default: "red"
+    pub startcol: String,
+    /// Fill colour of the goal node, default: "blue"
+    pub goalcol: String,
+}
+
+impl DotOptions {
+    /// Create a default DotOptions object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the name of the generated graph
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set whether nodes are pinned to their grid position
+    #[must_use]
+    pub fn positions(mut self, positions: bool) -> Self {
+        self.positions = positions;
+        self
+    }
+
+    /// Set the fill colour of the start node
+    #[must_use]
+    pub fn startcol(mut self, startcol: impl Into<String>) -> Self {
+        self.startcol = startcol.into();
+        self
+    }
+
+    /// Set the fill colour of the goal node
+    #[must_use]
+    pub fn goalcol(mut self, goalcol: impl Into<String>) -> Self {
+        self.goalcol = goalcol.into();
+        self
+    }
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        DotOptions {
+            name: String::from("maze"),
+            positions: true,
+            startcol: String::from("red"),
+            goalcol: String::from("blue"),
+        }
+    }
+}
+
+/// Escape the characters which may not appear inside of a quoted DOT identifier
+fn escape_id(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Maze {
+    /// Generate a GraphViz DOT version of the passage graph.
+    ///
+    /// Every field becomes a node labeled with its coordinates and every passage becomes an edge.
+    /// Start and goal nodes are highlighted with the colours configured in `options`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let dot = maze.to_dot(&DotOptions::new());
+    ///
+    /// assert!(dot.starts_with("graph \"maze\" {"));
+    /// assert!(dot.contains("\"0,0\" -- \"0,1\";"));
+    /// ```
+    #[must_use]
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        let mut dot = format!("graph \"{}\" {{\n", escape_id(&options.name));
+        dot.push_str("    node [shape=box];\n");
+
+        for coordinates in self.coordinates() {
+            let mut attributes = vec![format!("label=\"{}\"", coordinates)];
+            if options.positions {
+                // DOT places positive y upwards while the maze grows downwards
+                attributes.push(format!("pos=\"{},{}!\"", coordinates.x, -coordinates.y));
+            }
+            if coordinates == self.start {
+                attributes.push(format!(
+                    "style=filled, fillcolor=\"{}\"",
+                    escape_id(&options.startcol)
+                ));
+            } else if coordinates == self.goal {
+                attributes.push(format!(
+                    "style=filled, fillcolor=\"{}\"",
+                    escape_id(&options.goalcol)
+                ));
+            }
+            dot.push_str(&format!(
+                "    \"{},{}\" [{}];\n",
+                coordinates.x,
+                coordinates.y,
+                attributes.join(", ")
+            ));
+        }
+
+        for coordinates in self.coordinates() {
+            // only look east and south so that every passage is emitted exactly once
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if self.has_passage_between(coordinates, next) {
+                    dot.push_str(&format!(
+                        "    \"{},{}\" -- \"{},{}\";\n",
+                        coordinates.x, coordinates.y, next.x, next.y
+                    ));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{DotOptions, Generator};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_quotes_and_backslashes_are_escaped() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([42; 32])).generate(2, 2)?;
+        let options = DotOptions::new()
+            .name(r#"my "maze" \ 1"#)
+            .startcol(r#"red" shape="circle"#)
+            .goalcol(r#"blue\"#);
+
+        let dot = maze.to_dot(&options);
+
+        assert!(dot.starts_with(r#"graph "my \"maze\" \\ 1" {"#));
+        assert!(dot.contains(r#"fillcolor="red\" shape=\"circle"]"#));
+        assert!(dot.contains(r#"fillcolor="blue\\""#));
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while editing a maze via [`Maze::carve()`] or [`Maze::add_wall()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EditError {
+    /// The field or its neighbour in the given direction does not lie inside of the maze
+    #[error("The wall {1} of field {0} is part of the maze border")]
+    OutsideMaze(Coordinates, Direction),
+    /// Adding the wall would make the goal unreachable from the start
+    #[error("Adding a wall {1} of field {0} would disconnect the start from the goal")]
+    Disconnects(Coordinates, Direction),
+}
+
+impl Maze {
+    /// Remove the wall between the field at `coordinates` and its neighbour in `direction`.
+    ///
+    /// Carving where a passage already exists does nothing.
+    /// Note that carving into a perfect maze (which all generators produce) creates a loop.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let start = maze.start;
+    ///
+    /// maze.carve(start, Direction::East).unwrap();
+    /// assert!(maze.has_passage(&start, &Direction::East));
+    /// assert_eq!(
+    ///     maze.carve(start, Direction::North),
+    ///     Err(EditError::OutsideMaze(start, Direction::North))
+    /// );
+    /// ```
+    pub fn carve(
+        &mut self,
+        coordinates: Coordinates,
+        direction: Direction,
+    ) -> Result<(), EditError> {
+        if self
+            .passages
+            .insert(coordinates, coordinates.next(&direction))
+        {
+            Ok(())
+        } else {
+            Err(EditError::OutsideMaze(coordinates, direction))
+        }
+    }
+
+    /// Put a wall between the field at `coordinates` and its neighbour in `direction`.
+    ///
+    /// The wall is refused if it would make the goal unreachable from the start, leaving the
+    /// maze unchanged.
+    /// Use [`add_wall_unchecked()`](Maze::add_wall_unchecked) to skip that check, e.g. when
+    /// start and goal are moved afterwards anyway.
+    /// Adding a wall where one already exists does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let start = maze.start;
+    ///
+    /// // the only passage leading away from the start
+    /// assert_eq!(
+    ///     maze.add_wall(start, Direction::South),
+    ///     Err(EditError::Disconnects(start, Direction::South))
+    /// );
+    /// assert!(maze.has_passage(&start, &Direction::South));
+    /// ```
+    pub fn add_wall(
+        &mut self,
+        coordinates: Coordinates,
+        direction: Direction,
+    ) -> Result<(), EditError> {
+        let had_passage = self.has_passage(&coordinates, &direction);
+        self.add_wall_unchecked(coordinates, direction)?;
+
+        if had_passage && self.find_path(self.start, self.goal).is_none() {
+            self.passages
+                .insert(coordinates, coordinates.next(&direction));
+            return Err(EditError::Disconnects(coordinates, direction));
+        }
+
+        Ok(())
+    }
+
+    /// Put a wall between the field at `coordinates` and its neighbour in `direction` even if
+    /// this makes the goal unreachable.
+    ///
+    /// Only verifies that both fields lie inside of the maze.
+    pub fn add_wall_unchecked(
+        &mut self,
+        coordinates: Coordinates,
+        direction: Direction,
+    ) -> Result<(), EditError> {
+        if self
+            .passages
+            .remove(coordinates, coordinates.next(&direction))
+        {
+            Ok(())
+        } else {
+            Err(EditError::OutsideMaze(coordinates, direction))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Direction, EditError, Generator};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_walls_can_not_disconnect_goal() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([3; 32])).generate(6, 4)?;
+        let path = maze
+            .find_path(maze.start, maze.goal)
+            .ok_or_else(|| anyhow::anyhow!("goal not reachable"))?;
+
+        // in a perfect maze every passage along the solution is essential
+        for step in path.windows(2) {
+            let direction = Direction::all()
+                .iter()
+                .copied()
+                .find(|direction| step[0].next(direction) == step[1])
+                .ok_or_else(|| anyhow::anyhow!("path is not continuous"))?;
+            assert_eq!(
+                maze.add_wall(step[0], direction),
+                Err(EditError::Disconnects(step[0], direction))
+            );
+            assert!(maze.has_passage(&step[0], &direction));
+        }
+
+        // unchecked walls may disconnect the goal until the passage is carved again
+        let (first, second) = (path[0], path[1]);
+        let direction = Direction::all()
+            .iter()
+            .copied()
+            .find(|direction| first.next(direction) == second)
+            .ok_or_else(|| anyhow::anyhow!("path is not continuous"))?;
+        maze.add_wall_unchecked(first, direction)?;
+        assert!(maze.find_path(maze.start, maze.goal).is_none());
+        maze.carve(second, direction.opposite())?;
+        assert!(maze.has_passage(&first, &direction));
+
+        assert_eq!(
+            maze.carve(Coordinates::new(5, 3), Direction::East),
+            Err(EditError::OutsideMaze(
+                Coordinates::new(5, 3),
+                Direction::East
+            ))
+        );
+        Ok(())
+    }
+}
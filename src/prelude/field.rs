@@ -15,9 +15,10 @@ pub enum FieldType {
 ///
 /// This type is not used in internal representation but provides a nicer API to work with fields
 /// than always querying the [`Maze`].
-#[derive(Clone)]
+#[derive(Copy, Clone)]
 pub struct Field {
-    passages: Vec<Direction>,
+    /// Directions in which passages exist
+    passages: Passages,
     /// Role which this field position serves in the maze
     pub field_type: FieldType,
     /// Where this field is located in the maze
@@ -28,10 +29,10 @@ impl Field {
     pub(crate) fn new(
         field_type: FieldType,
         coordinates: Coordinates,
-        passages: Vec<Direction>,
+        passages: impl IntoIterator<Item = Direction>,
     ) -> Self {
         Field {
-            passages,
+            passages: passages.into_iter().collect(),
             field_type,
             coordinates,
         }
@@ -39,9 +40,54 @@ impl Field {
 
     /// Whether or not a passage (a way) exists from this field to another one which lies in the
     /// specified direction.
+    #[must_use]
     pub fn has_passage(&self, direction: &Direction) -> bool {
         self.passages.contains(direction)
     }
+
+    /// All directions in which passages lead away from this field
+    #[must_use]
+    pub fn passages(&self) -> Passages {
+        self.passages
+    }
+
+    /// Iterate over the directions in which passages lead away from this field
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·
+    /// |S  |
+    /// ·-· ·
+    /// |G  |
+    /// ·-·-·
+    /// ").unwrap();
+    /// let field = maze.get_field(&Coordinates::new(1, 0)).unwrap();
+    ///
+    /// assert_eq!(field.passage_directions().collect::<Vec<_>>(), vec![Direction::South, Direction::West]);
+    /// assert_eq!(field.wall_directions().collect::<Vec<_>>(), vec![Direction::North, Direction::East]);
+    /// assert_eq!(field.neighbours(&maze).len(), 2);
+    /// ```
+    pub fn passage_directions(&self) -> impl Iterator<Item = Direction> {
+        self.passages.directions()
+    }
+
+    /// Iterate over the directions in which walls surround this field
+    pub fn wall_directions(&self) -> impl Iterator<Item = Direction> {
+        let field = *self;
+        IntoIterator::into_iter(Direction::all()).filter(move |d| !field.has_passage(d))
+    }
+
+    /// All fields of `maze` which can be reached from this field through a passage, in the
+    /// order of [`Direction::all()`]
+    #[must_use]
+    pub fn neighbours(&self, maze: &Maze) -> Vec<Field> {
+        self.passage_directions()
+            .filter_map(|direction| maze.get_field(&self.coordinates.next(&direction)))
+            .collect()
+    }
 }
 
 impl std::fmt::Debug for Field {
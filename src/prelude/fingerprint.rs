@@ -0,0 +1,119 @@
+use crate::prelude::*;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64 bit FNV-1a hash which, unlike the hashers of the standard library, is guaranteed to stay
+/// the same across Rust versions and platforms
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+}
+
+/// Hash `bytes` with a hash function which is stable across versions and platforms
+pub(crate) fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+    hasher.write(bytes);
+    hasher.0
+}
+
+impl Maze {
+    /// Compute a hash of the size, the wall layout and the start and goal fields of the maze.
+    ///
+    /// Two mazes have the same fingerprint if they look the same when printed, so this can be
+    /// used to cheaply deduplicate generated mazes.
+    /// The value only depends on what the maze looks like and not on how it is stored, and it
+    /// is stable across versions of this crate, Rust and platforms so it can be persisted.
+    /// As with every hash, different mazes can collide, although that is very unlikely.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze1 = RbGenerator::new(Some([1; 32])).generate(10, 10).unwrap();
+    /// let maze2 = RbGenerator::new(Some([1; 32])).generate(10, 10).unwrap();
+    /// let maze3 = RbGenerator::new(Some([2; 32])).generate(10, 10).unwrap();
+    ///
+    /// assert_eq!(maze1.fingerprint(), maze2.fingerprint());
+    /// assert_ne!(maze1.fingerprint(), maze3.fingerprint());
+    /// ```
+    #[must_use]
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        for value in [
+            self.size.0,
+            self.size.1,
+            self.start.x,
+            self.start.y,
+            self.goal.x,
+            self.goal.y,
+        ]
+        .iter()
+        {
+            hasher.write_i32(*value);
+        }
+
+        // the passages leading east and south of every field, packed into 4 fields per byte
+        let mut byte = 0u8;
+        for (i, coordinates) in self.coordinates().enumerate() {
+            let passages = u8::from(self.has_passage(&coordinates, &Direction::East))
+                | u8::from(self.has_passage(&coordinates, &Direction::South)) << 1;
+            byte |= passages << (2 * (i % 4));
+            if i % 4 == 3 {
+                hasher.write(&[byte]);
+                byte = 0;
+            }
+        }
+        hasher.write(&[byte]);
+
+        hasher.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Direction, Generator, Maze};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_fingerprint_reflects_layout() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([8; 32])).generate(7, 5)?;
+        let fingerprint = maze.fingerprint();
+
+        // the same layout parsed from text has the same fingerprint
+        assert_eq!(
+            Maze::from_ascii(&format!("{:?}", maze))?.fingerprint(),
+            fingerprint
+        );
+
+        let mut moved_goal = maze.clone();
+        moved_goal.goal = moved_goal.start;
+        assert_ne!(moved_goal.fingerprint(), fingerprint);
+
+        let mut edited = maze.clone();
+        let coordinates = Coordinates::new(3, 2);
+        let direction = if edited.has_passage(&coordinates, &Direction::East) {
+            Direction::South
+        } else {
+            Direction::East
+        };
+        edited.carve(coordinates, direction)?;
+        assert_ne!(edited.fingerprint(), fingerprint);
+
+        // this value must never change as fingerprints may be persisted
+        let maze = Maze::from_ascii("·-·-·\n|S  |\n·-· ·\n|G  |\n·-·-·\n")?;
+        assert_eq!(maze.fingerprint(), 0xbcdc_a8fc_ba80_ce95);
+        Ok(())
+    }
+}
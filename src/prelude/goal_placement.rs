@@ -0,0 +1,242 @@
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while placing start and goal via [`Maze::randomize_endpoints()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EndpointError {
+    /// No two connected fields are at least `requested` steps apart
+    #[error("No two fields of the maze are at least {requested} steps apart")]
+    MinDistanceTooLarge {
+        /// The requested minimum distance
+        requested: usize,
+    },
+}
+
+/// Where generators place the start and goal fields of a maze
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum GoalPlacement {
+    /// Let the algorithm decide, which may place both anywhere inside of the maze
+    #[default]
+    Anywhere,
+    /// Place both on the border of the maze as far apart from each other as possible, see
+    /// [`Maze::place_start_and_goal_on_edges()`]
+    EdgesOnly,
+}
+
+impl GoalPlacement {
+    /// Move start and goal of a freshly generated maze according to this placement
+    pub(crate) fn apply(self, maze: &mut Maze) {
+        match self {
+            GoalPlacement::Anywhere => {}
+            GoalPlacement::EdgesOnly => maze.place_start_and_goal_on_edges(),
+        }
+    }
+
+    /// Place the goal of a freshly generated maze according to this placement while keeping
+    /// its start, see [`Generator::generate_from()`]
+    pub(crate) fn apply_keeping_start(self, maze: &mut Maze) {
+        let edge_goal = match self {
+            GoalPlacement::Anywhere => None,
+            GoalPlacement::EdgesOnly => maze.farthest_edge_from(maze.start),
+        };
+        maze.goal = edge_goal.unwrap_or_else(|| maze.farthest_from(maze.start).0);
+    }
+}
+
+impl Maze {
+    /// Whether the field at `coordinates` lies on the border of the maze
+    fn is_on_edge(&self, coordinates: &Coordinates) -> bool {
+        self.are_coordinates_inside(coordinates)
+            && (coordinates.x == 0
+                || coordinates.y == 0
+                || coordinates.x == self.size.0 - 1
+                || coordinates.y == self.size.1 - 1)
+    }
+
+    /// The field on the border which is farthest away from `from`, if any border field can be
+    /// reached from it
+    fn farthest_edge_from(&self, from: Coordinates) -> Option<Coordinates> {
+        self.breadth_first_search(from)
+            .into_iter()
+            .filter(|(coordinates, _)| self.is_on_edge(coordinates))
+            .max_by(|a, b| (a.1).0.cmp(&(b.1).0).then_with(|| b.0.cmp(&a.0)))
+            .map(|(coordinates, _)| coordinates)
+    }
+
+    /// Move start and goal onto the border of the maze so that the route between them is as
+    /// long as possible, as is conventional for printed mazes.
+    ///
+    /// Only fields which can be reached from the current start are considered and nothing is
+    /// changed if none of them lies on the border.
+    /// The result is exact for perfect mazes, which all generators produce.
+    ///
+    /// Generators do this by themselves when their `goal_placement` is set to
+    /// [`GoalPlacement::EdgesOnly`].
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut generator = RbGenerator::new(Some([42; 32]));
+    /// generator.goal_placement = GoalPlacement::EdgesOnly;
+    /// let maze = generator.generate(5, 5).unwrap();
+    ///
+    /// assert!(maze.goal.x == 0 || maze.goal.y == 0 || maze.goal.x == 4 || maze.goal.y == 4);
+    /// ```
+    pub fn place_start_and_goal_on_edges(&mut self) {
+        // in a tree the border field farthest away from any field is an end of the longest
+        // route between two border fields
+        if let Some(start) = self.farthest_edge_from(self.start) {
+            self.start = start;
+            self.goal = self.farthest_edge_from(start).unwrap_or(start);
+        }
+    }
+}
+
+impl Maze {
+    /// Move start and goal to random fields whose route between each other is at least
+    /// `min_distance` steps long, e.g. to replay the same maze with different objectives.
+    ///
+    /// All fields which can be reached from the current start are considered.
+    /// The start is chosen uniformly among the fields which have a partner far enough away and
+    /// the goal uniformly among those partners.
+    /// If no two fields are far enough apart, the maze is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaChaRng;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let mut rng = ChaChaRng::from_seed([1; 32]);
+    /// maze.randomize_endpoints(&mut rng, 20).unwrap();
+    ///
+    /// let difficulty = maze.difficulty();
+    /// assert!(difficulty.solution_length >= 20);
+    /// ```
+    pub fn randomize_endpoints<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        min_distance: usize,
+    ) -> Result<(), EndpointError> {
+        let error = EndpointError::MinDistanceTooLarge {
+            requested: min_distance,
+        };
+        // the longest route of a perfect maze is known exactly, so impossible distances can be
+        // rejected without searching from every field
+        if self.is_perfect() && self.longest_path().2.len() <= min_distance {
+            return Err(error);
+        }
+
+        let mut candidates: Vec<Coordinates> = self
+            .bfs_order(self.start)
+            .into_iter()
+            .map(|(coordinates, _)| coordinates)
+            .collect();
+        candidates.sort_unstable();
+        candidates.shuffle(rng);
+        for start in candidates {
+            let mut goals: Vec<Coordinates> = self
+                .bfs_order(start)
+                .into_iter()
+                .filter(|(_, distance)| *distance as usize >= min_distance)
+                .map(|(coordinates, _)| coordinates)
+                .collect();
+            goals.sort_unstable();
+            if let Some(goal) = goals.choose(rng) {
+                self.start = start;
+                self.goal = *goal;
+                return Ok(());
+            }
+        }
+
+        Err(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ellers_algorithm::EllersGenerator;
+    use crate::growing_tree::GrowingTreeGenerator;
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::{EndpointError, Generator, GoalPlacement, Maze, Room};
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    /// Length of the longest route between two border fields by trying all pairs
+    fn longest_edge_route(maze: &Maze) -> usize {
+        let edges: Vec<_> = maze.coordinates().filter(|c| maze.is_on_edge(c)).collect();
+        edges
+            .iter()
+            .flat_map(|a| maze.breadth_first_search(*a).into_iter())
+            .filter(|(coordinates, _)| maze.is_on_edge(coordinates))
+            .map(|(_, (distance, _))| distance)
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_all_generators_place_on_edges() -> anyhow::Result<()> {
+        let mut rb = RbGenerator::new(Some([3; 32]));
+        rb.goal_placement = GoalPlacement::EdgesOnly;
+        let mut prims = PrimsGenerator::new(Some([3; 32]));
+        prims.goal_placement = GoalPlacement::EdgesOnly;
+        let mut ellers = EllersGenerator::new(Some([3; 32]));
+        ellers.goal_placement = GoalPlacement::EdgesOnly;
+        let mut growing_tree = GrowingTreeGenerator::new(Some([3; 32]));
+        growing_tree.goal_placement = GoalPlacement::EdgesOnly;
+        let mut generators: Vec<Box<dyn Generator>> = vec![
+            Box::new(rb),
+            Box::new(prims),
+            Box::new(ellers),
+            Box::new(growing_tree),
+        ];
+
+        for generator in generators.iter_mut() {
+            for &(width, height) in [(1, 1), (7, 1), (6, 9), (10, 10)].iter() {
+                let maze = generator.generate(width, height)?;
+                assert!(maze.is_on_edge(&maze.start));
+                assert!(maze.is_on_edge(&maze.goal));
+                let distance = maze
+                    .find_path(maze.start, maze.goal)
+                    .map_or(0, |path| path.len() - 1);
+                assert_eq!(distance, longest_edge_route(&maze));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_endpoints_keep_distance() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([4; 32])).generate(8, 8)?;
+        let mut rng = ChaChaRng::from_seed([4; 32]);
+        let longest = maze.longest_path().2.len() - 1;
+
+        for min_distance in 0..=longest {
+            maze.randomize_endpoints(&mut rng, min_distance)?;
+            let path = maze.find_path(maze.start, maze.goal);
+            assert!(path.is_some_and(|path| path.len() > min_distance));
+        }
+
+        // no two fields are farther apart than both ends of the longest route
+        let (start, goal) = (maze.start, maze.goal);
+        assert_eq!(
+            maze.randomize_endpoints(&mut rng, longest + 1),
+            Err(EndpointError::MinDistanceTooLarge {
+                requested: longest + 1
+            })
+        );
+        // rooms create loops so every field has to be searched before failing
+        maze.carve_room(Room::new((0, 0).into(), 2, 2))?;
+        assert!(maze.randomize_endpoints(&mut rng, 1000).is_err());
+        assert_eq!((maze.start, maze.goal), (start, goal));
+        Ok(())
+    }
+}
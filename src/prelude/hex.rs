@@ -0,0 +1,162 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while parsing the hex format of [`Maze::to_hex()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HexParseError {
+    /// A character which is not a hexadecimal digit was encountered
+    #[error("Unexpected character '{character}' in line {line} column {column}")]
+    UnexpectedCharacter {
+        /// Line number (starting at 1) in which the error occurred
+        line: usize,
+        /// Column number (starting at 1) in which the error occurred
+        column: usize,
+        /// The offending character
+        character: char,
+    },
+    /// The digits do not describe a valid maze
+    #[error(transparent)]
+    WallGrid(#[from] WallGridError),
+}
+
+impl Maze {
+    /// Encode the maze with a single character per field, e.g. for diffs and bug reports of
+    /// large mazes.
+    ///
+    /// Every row of the maze becomes a line in which every field is represented by a
+    /// hexadecimal digit of its [`Passages`] bits, i.e. the sum of `1` for a passage to the
+    /// north, `2` east, `4` south and `8` west.
+    /// Start, goal and rooms are not encoded.
+    /// The same format is used by the [`Display`](std::fmt::Display) implementation with
+    /// [`RenderStyle::Hex`].
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// ·-·-· ·
+    /// |G    |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// let hex = maze.to_hex();
+    ///
+    /// assert_eq!(hex, "2ac\n2a9\n");
+    /// assert_eq!(Maze::from_hex(&hex, maze.start, maze.goal).unwrap(), maze);
+    /// ```
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        let mut hex =
+            String::with_capacity((self.size.0.max(0) as usize + 1) * self.size.1.max(0) as usize);
+        for iy in 0..self.size.1 {
+            for ix in 0..self.size.0 {
+                let passages = self.passages(&Coordinates::new(ix, iy)).unwrap_or_default();
+                hex.push(std::char::from_digit(u32::from(passages.bits()), 16).unwrap_or('0'));
+            }
+            hex.push('\n');
+        }
+        hex
+    }
+
+    /// Parse a maze from the format produced by [`to_hex()`](Maze::to_hex).
+    ///
+    /// Digits may be written in lower or upper case and empty lines are ignored.
+    /// The passages are validated like the walls passed to [`Maze::from_wall_grid()`], so
+    /// every passage must be described by both of its fields and none may lead outside of the
+    /// maze.
+    pub fn from_hex(
+        text: &str,
+        start: Coordinates,
+        goal: Coordinates,
+    ) -> Result<Maze, HexParseError> {
+        let mut grid = Vec::new();
+        for (i_line, line) in text.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let row = line
+                .chars()
+                .enumerate()
+                .map(|(i_column, character)| {
+                    let bits =
+                        character
+                            .to_digit(16)
+                            .ok_or(HexParseError::UnexpectedCharacter {
+                                line: i_line + 1,
+                                column: i_column + 1,
+                                character,
+                            })?;
+                    let passages = Passages::from_bits_truncate(bits as u8);
+                    Ok(CellWalls {
+                        north: !passages.contains(&Direction::North),
+                        east: !passages.contains(&Direction::East),
+                        south: !passages.contains(&Direction::South),
+                        west: !passages.contains(&Direction::West),
+                    })
+                })
+                .collect::<Result<Vec<CellWalls>, HexParseError>>()?;
+            grid.push(row);
+        }
+        Ok(Maze::from_wall_grid(&grid, start, goal)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{
+        Coordinates, Direction, Generator, HexParseError, Maze, RenderStyle, WallGridError,
+    };
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_hex_round_trip() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([40; 32])).generate(9, 6)?;
+        let hex = maze.to_hex();
+        assert_eq!(hex.lines().count(), 6);
+        assert!(hex.lines().all(|line| line.len() == 9));
+        assert_eq!(Maze::from_hex(&hex, maze.start, maze.goal)?, maze);
+        assert_eq!(
+            Maze::from_hex(&hex.to_uppercase(), maze.start, maze.goal)?,
+            maze
+        );
+
+        maze.set_render_style(RenderStyle::Hex);
+        assert_eq!(maze.to_string(), hex);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_hex() {
+        let origin = Coordinates::new(0, 0);
+        assert_eq!(
+            Maze::from_hex("24\n1x\n", origin, origin),
+            Err(HexParseError::UnexpectedCharacter {
+                line: 2,
+                column: 2,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            Maze::from_hex("", origin, origin),
+            Err(HexParseError::WallGrid(WallGridError::Empty))
+        );
+        assert_eq!(
+            Maze::from_hex("20\n", origin, origin),
+            Err(HexParseError::WallGrid(WallGridError::InconsistentWall(
+                origin,
+                Direction::East
+            )))
+        );
+        assert_eq!(
+            Maze::from_hex("1\n", origin, origin),
+            Err(HexParseError::WallGrid(WallGridError::OpenBorder(
+                origin,
+                Direction::North
+            )))
+        );
+    }
+}
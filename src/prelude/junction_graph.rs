@@ -0,0 +1,175 @@
+use std::collections::{HashMap, HashSet};
+
+use petgraph::graph::{Graph, NodeIndex};
+use petgraph::Undirected;
+
+use crate::prelude::*;
+
+/// Topological skeleton of a maze, see [`Maze::junction_graph()`]
+///
+/// Every node holds the coordinates of its field and every edge the number of steps along the
+/// corridor which it replaces.
+pub type JunctionGraph = Graph<Coordinates, usize, Undirected>;
+
+impl Maze {
+    /// Collapse the corridors of the maze into single edges between its junctions and dead
+    /// ends, e.g. for high-level planners which do not care about every single field.
+    ///
+    /// Nodes are all fields which do not have exactly two passages as well as the start and
+    /// the goal.
+    /// Each edge is weighted by the length of its corridor in steps, so the shortest routes in
+    /// the graph are as long as those in the maze.
+    /// Mazes with loops may have multiple edges between the same nodes, and a closed ring
+    /// without any junction becomes a single node with an edge to itself.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// · ·-·-·
+    /// |  G  |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// let graph = maze.junction_graph();
+    ///
+    /// // a single corridor between two dead ends which is split by the start and the goal
+    /// assert_eq!(graph.node_count(), 4);
+    /// let mut lengths: Vec<usize> = graph.raw_edges().iter().map(|edge| edge.weight).collect();
+    /// lengths.sort();
+    /// assert_eq!(lengths, vec![1, 2, 2]);
+    /// ```
+    #[must_use]
+    pub fn junction_graph(&self) -> JunctionGraph {
+        let mut graph = JunctionGraph::default();
+        let mut nodes: HashMap<Coordinates, NodeIndex> = HashMap::new();
+        let mut pending = Vec::new();
+        for coordinates in self.coordinates() {
+            if coordinates == self.start
+                || coordinates == self.goal
+                || self.passage_count(coordinates) != 2
+            {
+                nodes.insert(coordinates, graph.add_node(coordinates));
+                pending.push(coordinates);
+            }
+        }
+
+        // corridors are followed from both of their ends, so the first step of each walk is
+        // remembered to only add every corridor once
+        let mut walked = HashSet::new();
+        let mut in_corridor = HashSet::new();
+        // rings without any node get one of their fields as node once everything else is done
+        let mut fields = self.coordinates();
+        while let Some(from) = pending.pop().or_else(|| {
+            fields.find(|coordinates| {
+                !nodes.contains_key(coordinates) && !in_corridor.contains(coordinates)
+            })
+        }) {
+            let i_from = *nodes.entry(from).or_insert_with(|| graph.add_node(from));
+            for first in self.passage_neighbours(from) {
+                if !walked.insert((from, first)) {
+                    continue;
+                }
+                let (mut previous, mut current, mut length) = (from, first, 1);
+                while !nodes.contains_key(&current) {
+                    in_corridor.insert(current);
+                    match self
+                        .passage_neighbours(current)
+                        .find(|next| *next != previous)
+                    {
+                        Some(next) => {
+                            previous = current;
+                            current = next;
+                            length += 1;
+                        }
+                        None => break,
+                    }
+                }
+                walked.insert((current, previous));
+                if let Some(i_to) = nodes.get(&current) {
+                    graph.add_edge(i_from, *i_to, length);
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use petgraph::algo;
+    use petgraph::visit::EdgeRef;
+
+    use crate::prelude::{Coordinates, Generator, Maze};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_corridors_are_collapsed() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([18; 32])).generate(12, 9)?;
+        for braided in [false, true].iter() {
+            if *braided {
+                maze.braid(1.0, &mut rand::rngs::mock::StepRng::new(0, 1));
+            }
+            let graph = maze.junction_graph();
+            let passages = maze.passages.passage_count();
+            assert_eq!(
+                graph
+                    .raw_edges()
+                    .iter()
+                    .map(|edge| edge.weight)
+                    .sum::<usize>(),
+                passages
+            );
+            assert_eq!(algo::connected_components(&graph), 1);
+            if !*braided {
+                assert_eq!(graph.edge_count(), graph.node_count() - 1);
+            }
+
+            for i_node in graph.node_indices() {
+                let coordinates = graph[i_node];
+                // loops back to the same node use two of its passages
+                let degree: usize = graph
+                    .edges(i_node)
+                    .map(|edge| if edge.source() == edge.target() { 2 } else { 1 })
+                    .sum();
+                assert_eq!(degree, maze.passage_count(coordinates));
+                assert!(
+                    maze.passage_count(coordinates) != 2
+                        || coordinates == maze.start
+                        || coordinates == maze.goal
+                );
+            }
+
+            // the weights keep the lengths of the shortest routes
+            let index = |coordinates: Coordinates| {
+                graph
+                    .node_indices()
+                    .find(|i_node| graph[*i_node] == coordinates)
+                    .ok_or_else(|| anyhow::anyhow!("{} is not a node", coordinates))
+            };
+            let (start, goal) = (index(maze.start)?, index(maze.goal)?);
+            let distances = algo::dijkstra(&graph, start, Some(goal), |edge| *edge.weight());
+            let solution = maze
+                .find_path(maze.start, maze.goal)
+                .ok_or_else(|| anyhow::anyhow!("goal not reachable"))?;
+            assert_eq!(distances.get(&goal), Some(&(solution.len() - 1)));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ring_without_junctions() -> anyhow::Result<()> {
+        let maze = Maze::from_ascii("·-·-·-·\n|S|   |\n· · · ·\n|G|   |\n·-·-·-·\n")?;
+        let graph = maze.junction_graph();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+
+        let mut lengths: Vec<usize> = graph.raw_edges().iter().map(|edge| edge.weight).collect();
+        lengths.sort_unstable();
+        assert_eq!(lengths, vec![1, 4]);
+        Ok(())
+    }
+}
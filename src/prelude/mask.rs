@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while loading a [`Mask`] from an image
+#[derive(Error, Debug)]
+pub enum MaskError {
+    /// The image file could not be read
+    #[error("Could not read the image: {0}")]
+    Io(#[from] io::Error),
+
+    /// The data is not a valid PNG image
+    #[error("Could not decode the image: {0}")]
+    Decoding(#[from] png::DecodingError),
+
+    /// The mask would not contain any fields
+    #[error("The mask has an invalid size of {width}x{height}")]
+    InvalidDimensions {
+        /// The requested width
+        width: i32,
+        /// The requested height
+        height: i32,
+    },
+
+    /// The image does not contain any pixels
+    #[error("The image is empty")]
+    EmptyImage,
+}
+
+/// Fields which are left out of a maze, e.g. to generate mazes in the shape of a logo or text
+///
+/// Pass it to [`MazeBuilder::mask()`](crate::builder::MazeBuilder::mask) together with its
+/// size.
+///
+/// # Examples
+/// ```no_run
+/// use maze_generator::prelude::*;
+/// use maze_generator::builder::MazeBuilder;
+///
+/// // carve the maze into the dark shape of a logo
+/// let mut mask = Mask::from_png_file("logo.png", 60, 30, 128).unwrap();
+/// mask.invert();
+/// let maze = MazeBuilder::new(mask.width, mask.height).mask(mask).build().unwrap();
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Mask {
+    /// Number of fields in x direction
+    pub width: i32,
+    /// Number of fields in y direction
+    pub height: i32,
+    /// The fields which are left out of the maze
+    pub blocked: HashSet<Coordinates>,
+}
+
+impl Mask {
+    /// Load a mask from the PNG image in `bytes`, see [`Mask::from_png_file()`]
+    pub fn from_png(
+        bytes: &[u8],
+        width: i32,
+        height: i32,
+        threshold: u8,
+    ) -> Result<Self, MaskError> {
+        Mask::from_png_reader(bytes, width, height, threshold)
+    }
+
+    /// Load a mask of `width` x `height` fields from the PNG image at `path`.
+    ///
+    /// The image is scaled to the size of the mask and every field covers a block of pixels.
+    /// A field is blocked if the average brightness of its pixels lies below `threshold`, so
+    /// dark areas of the image are left out of the maze.
+    /// Transparent pixels count as white, i.e. as part of the maze.
+    pub fn from_png_file(
+        path: impl AsRef<Path>,
+        width: i32,
+        height: i32,
+        threshold: u8,
+    ) -> Result<Self, MaskError> {
+        let file = File::open(path)?;
+        Mask::from_png_reader(BufReader::new(file), width, height, threshold)
+    }
+
+    /// Swap blocked and free fields, e.g. for carving a maze into dark shapes instead of
+    /// around them
+    pub fn invert(&mut self) {
+        let topology = RectangularTopology::new(self.width, self.height);
+        self.blocked = topology
+            .cells()
+            .into_iter()
+            .filter(|coordinates| !self.blocked.contains(coordinates))
+            .collect();
+    }
+
+    fn from_png_reader(
+        reader: impl Read,
+        width: i32,
+        height: i32,
+        threshold: u8,
+    ) -> Result<Self, MaskError> {
+        if width <= 0 || height <= 0 {
+            return Err(MaskError::InvalidDimensions { width, height });
+        }
+
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let frame = reader.next_frame(&mut buffer)?;
+        let (image_width, image_height) = (frame.width as usize, frame.height as usize);
+        if image_width == 0 || image_height == 0 {
+            return Err(MaskError::EmptyImage);
+        }
+        let samples = frame.color_type.samples();
+        let brightness = |x: usize, y: usize| -> u64 {
+            let pixel = &buffer[y * frame.line_size + x * samples..][..samples];
+            let (gray, alpha) = match pixel {
+                [gray] => (u64::from(*gray), 255),
+                [gray, alpha] => (u64::from(*gray), u64::from(*alpha)),
+                [r, g, b] => (luma(*r, *g, *b), 255),
+                [r, g, b, alpha, ..] => (luma(*r, *g, *b), u64::from(*alpha)),
+                [] => (255, 255),
+            };
+            // blend over a white background
+            (gray * alpha + 255 * (255 - alpha)) / 255
+        };
+
+        // every field covers at least one pixel, even if the image is smaller than the mask
+        let span = |i_field: i32, fields: i32, pixels: usize| {
+            let first = i_field as usize * pixels / fields as usize;
+            let end = ((i_field as usize + 1) * pixels / fields as usize).max(first + 1);
+            first.min(pixels - 1)..end.min(pixels)
+        };
+        let mut blocked = HashSet::new();
+        for iy in 0..height {
+            let rows = span(iy, height, image_height);
+            for ix in 0..width {
+                let columns = span(ix, width, image_width);
+                let mut sum = 0;
+                for y in rows.clone() {
+                    sum += columns.clone().map(|x| brightness(x, y)).sum::<u64>();
+                }
+                let count = (rows.len() * columns.len()) as u64;
+                if sum < u64::from(threshold) * count {
+                    blocked.insert(Coordinates::new(ix, iy));
+                }
+            }
+        }
+
+        Ok(Mask {
+            width,
+            height,
+            blocked,
+        })
+    }
+}
+
+/// Perceived brightness of a color
+fn luma(r: u8, g: u8, b: u8) -> u64 {
+    (299 * u64::from(r) + 587 * u64::from(g) + 114 * u64::from(b)) / 1000
+}
+
+impl From<Mask> for HashSet<Coordinates> {
+    fn from(mask: Mask) -> Self {
+        mask.blocked
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::builder::MazeBuilder;
+    use crate::prelude::{Coordinates, Mask, MaskError};
+
+    /// Encode `pixels` as an RGBA image with `width` pixels per row
+    fn encode(pixels: &[[u8; 4]], width: u32) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        {
+            let height = pixels.len() as u32 / width;
+            let mut encoder = png::Encoder::new(&mut bytes, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&pixels.concat())?;
+        }
+        Ok(bytes)
+    }
+
+    #[test]
+    fn test_dark_pixels_are_blocked() -> anyhow::Result<()> {
+        const BLACK: [u8; 4] = [0, 0, 0, 255];
+        const WHITE: [u8; 4] = [255, 255, 255, 255];
+        const CLEAR: [u8; 4] = [0, 0, 0, 0];
+        // 4x2 pixels: a dark left half, a white column and a transparent column
+        let png = encode(
+            &[
+                BLACK, BLACK, WHITE, CLEAR, //
+                BLACK, BLACK, WHITE, CLEAR,
+            ],
+            4,
+        )?;
+
+        let mask = Mask::from_png(&png, 4, 2, 128)?;
+        let dark_half: HashSet<Coordinates> = [(0, 0), (1, 0), (0, 1), (1, 1)]
+            .iter()
+            .map(|(x, y)| Coordinates::new(*x, *y))
+            .collect();
+        assert_eq!(mask.blocked, dark_half);
+
+        // downsampled to two fields, the transparent pixels keep the right one free
+        let downsampled = Mask::from_png(&png, 2, 1, 128)?;
+        assert_eq!(downsampled.blocked.len(), 1);
+        assert!(downsampled.blocked.contains(&Coordinates::new(0, 0)));
+
+        // upsampled so that multiple fields share a pixel
+        let upsampled = Mask::from_png(&png, 8, 4, 128)?;
+        assert_eq!(upsampled.blocked.len(), 16);
+
+        let mut inverted = mask.clone();
+        inverted.invert();
+        assert_eq!(inverted.blocked.len(), 4);
+        assert!(inverted.blocked.is_disjoint(&mask.blocked));
+
+        let maze = MazeBuilder::new(mask.width, mask.height)
+            .mask(mask.clone())
+            .build()?;
+        for coordinates in mask.blocked.iter() {
+            assert_eq!(maze.passage_count(*coordinates), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_images() -> anyhow::Result<()> {
+        assert!(matches!(
+            Mask::from_png(b"not an image", 4, 4, 128),
+            Err(MaskError::Decoding(_))
+        ));
+        let png = encode(&[[0, 0, 0, 255]], 1)?;
+        assert!(matches!(
+            Mask::from_png(&png, 0, 4, 128),
+            Err(MaskError::InvalidDimensions {
+                width: 0,
+                height: 4
+            })
+        ));
+        assert!(matches!(
+            Mask::from_png_file("does/not/exist.png", 4, 4, 128),
+            Err(MaskError::Io(_))
+        ));
+        Ok(())
+    }
+}
@@ -1,27 +1,59 @@
-use std::fmt::Write;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 
-use anyhow::{anyhow, Result};
 use petgraph::algo::is_isomorphic;
 use petgraph::graphmap::GraphMap;
 use petgraph::stable_graph::DefaultIx;
 use petgraph::Undirected;
+use rand::Rng;
 
+use crate::prelude::passage_grid::PassageGrid;
 use crate::prelude::*;
 
-pub(crate) type MazeGraph = GraphMap<Coordinates, (), Undirected>;
+/// Undirected graph of a maze which contains every field as a node and every passage as an edge
+///
+/// Convert a maze into it via [`From`] to run any of the graph algorithms of [`petgraph`] on it.
+/// `petgraph` is re-exported as [`maze_generator::petgraph`](crate::petgraph) so that the
+/// graph can be used without depending on a matching version of it.
+///
+/// # Examples
+/// ```
+/// use maze_generator::petgraph::algo;
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+/// let graph = MazeGraph::from(maze);
+///
+/// assert_eq!(graph.node_count(), 9);
+/// assert_eq!(algo::connected_components(&graph), 1);
+/// ```
+pub type MazeGraph = GraphMap<Coordinates, (), Undirected>;
 
 /// A collection of [`Field`]s with passages between them.
 ///
 /// Use one of the provided [`Generator`]s to create an instance of this type.
+///
+/// Two mazes are equal if they have the same size, start, goal, rooms and passages, regardless
+/// of their [`provenance()`](Maze::provenance).
+/// Use [`Maze::is_isomorphic_to()`] to compare only the shape of the passages instead.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Maze {
-    pub(crate) graph: MazeGraph,
+    pub(crate) passages: PassageGrid,
     /// At which coordinates the start field lies
     pub start: Coordinates,
     /// At which coordinates the goal field lies
     pub goal: Coordinates,
     /// How large the maze is in (width, height) format
     pub size: (i32, i32),
+    pub(crate) rooms: Vec<Room>,
+    pub(crate) render_style: RenderStyle,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) provenance: Option<Provenance>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    carving_record: Option<Vec<(Coordinates, Coordinates)>>,
 }
 
 impl Maze {
@@ -30,25 +62,76 @@ impl Maze {
         debug_assert!(height > 0, "maze height should be >0");
 
         Maze {
-            graph: GraphMap::with_capacity((width * height) as usize, 0),
+            passages: PassageGrid::new(width, height),
             size: (width, height),
             start,
             goal,
+            rooms: Vec::new(),
+            render_style: RenderStyle::default(),
+            provenance: None,
+            carving_record: None,
         }
     }
 
+    /// Remove all passages and rooms and adapt the storage to [`size`](Maze::size) so that a
+    /// generator can start from scratch
+    pub(crate) fn reset(&mut self) {
+        let (width, height) = self.size;
+        debug_assert!(width > 0, "maze width should be >0");
+        debug_assert!(height > 0, "maze height should be >0");
+
+        self.passages.reset(width, height);
+        self.rooms.clear();
+        self.start = Coordinates::default();
+        self.goal = Coordinates::default();
+        self.provenance = None;
+        if let Some(record) = &mut self.carving_record {
+            record.clear();
+        }
+    }
+
+    /// Start recording every passage which is carved into this maze from now on, e.g. to
+    /// animate the generation with [`Maze::to_animated_svg()`]
+    ///
+    /// Generators which generate into an existing maze via
+    /// [`Generator::generate_into()`] clear the record when they start so that it only contains
+    /// the passages of the latest generation, in the order in which they were carved.
+    /// This holds for all generators of this crate.
+    /// The record is neither compared, hashed nor serialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    ///
+    /// let mut generator = PrimsGenerator::new(Some([42; 32]));
+    /// let mut maze = generator.generate(4, 4).unwrap();
+    /// maze.record_carving();
+    /// generator.generate_into(&mut maze).unwrap();
+    ///
+    /// // a perfect maze of 16 fields has 15 passages
+    /// assert_eq!(maze.take_carving_record().len(), 15);
+    /// ```
+    pub fn record_carving(&mut self) {
+        self.carving_record.get_or_insert_with(Vec::new);
+    }
+
+    /// Stop recording carved passages and return those recorded since
+    /// [`Maze::record_carving()`] or the latest generation, whichever came last
+    ///
+    /// Returns an empty list if carving was not recorded.
+    #[must_use]
+    pub fn take_carving_record(&mut self) -> Vec<(Coordinates, Coordinates)> {
+        self.carving_record.take().unwrap_or_default()
+    }
+
     /// Retrieve the [`Field`] which is located at `coordinates`
+    #[must_use]
     pub fn get_field(&self, coordinates: &Coordinates) -> Option<Field> {
         if self.are_coordinates_inside(coordinates) {
             // figure out in which directions passages exist
-            let passages: Vec<_> = Direction::all()
-                .iter()
-                .filter(|dir| {
-                    self.graph
-                        .contains_edge(*coordinates, coordinates.next(dir))
-                })
-                .copied()
-                .collect();
+            let passages = IntoIterator::into_iter(Direction::all())
+                .filter(|direction| self.has_passage(coordinates, direction));
 
             let field_type = if &self.start == coordinates {
                 FieldType::Start
@@ -64,197 +147,397 @@ impl Maze {
         }
     }
 
+    /// Whether a passage leads from the field at `coordinates` into `direction`.
+    ///
+    /// This is a cheaper alternative to [`get_field()`](Maze::get_field) when only a single
+    /// passage is of interest.
+    /// Coordinates outside of the maze never have any passages.
+    #[must_use]
+    pub fn has_passage(&self, coordinates: &Coordinates, direction: &Direction) -> bool {
+        self.passages
+            .contains(*coordinates, coordinates.next(direction))
+    }
+
+    /// Iterate over the fields which can be reached from `coordinates` in a single step, i.e.
+    /// through a passage, in the order of [`Direction::all()`]
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii("·-·-·\n|S  |\n·-· ·\n|G  |\n·-·-·\n").unwrap();
+    /// let neighbours: Vec<Coordinates> = maze.reachable_neighbors(&Coordinates::new(1, 0)).collect();
+    /// assert_eq!(neighbours, vec![Coordinates::new(1, 1), Coordinates::new(0, 0)]);
+    /// ```
+    pub fn reachable_neighbors(
+        &self,
+        coordinates: &Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        self.passage_neighbours(*coordinates)
+    }
+
+    /// Iterate over the fields inside the maze which lie next to `coordinates`, regardless of
+    /// whether a wall separates them, in the order of [`Direction::all()`].
+    ///
+    /// Coordinates outside of the maze have no adjacent cells.
+    pub fn adjacent_cells(
+        &self,
+        coordinates: &Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        let inside = self.are_coordinates_inside(coordinates);
+        let coordinates = *coordinates;
+        Direction::iter()
+            .filter(move |_| inside)
+            .map(move |direction| coordinates.next(&direction))
+            .filter(move |next| self.are_coordinates_inside(next))
+    }
+
+    /// The shape of this maze for use with algorithms which are generic over [`Topology`]
+    #[must_use]
+    pub fn topology(&self) -> RectangularTopology {
+        RectangularTopology::new(self.size.0, self.size.1)
+    }
+
     pub(crate) fn are_coordinates_inside(&self, coordinates: &Coordinates) -> bool {
-        coordinates.x >= 0
-            && coordinates.x < self.size.0
-            && coordinates.y >= 0
-            && coordinates.y < self.size.1
+        self.topology().contains(coordinates)
     }
-}
 
-impl std::fmt::Debug for Maze {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        for iy in 0..self.size.1 {
-            // print top passage
-            for ix in 0..self.size.0 {
-                f.write_str("·")?;
-                if self
-                    .get_field(&(ix, iy).into())
-                    .ok_or(std::fmt::Error {})?
-                    .has_passage(&Direction::North)
-                {
-                    f.write_str(" ")?;
-                } else {
-                    f.write_str("-")?;
-                }
+    /// Iterate over all coordinates of the maze in row-major order
+    pub(crate) fn coordinates(&self) -> impl Iterator<Item = Coordinates> {
+        let (width, height) = self.size;
+        (0..height).flat_map(move |iy| (0..width).map(move |ix| Coordinates::new(ix, iy)))
+    }
+
+    /// Carve a passage between two adjacent fields
+    pub(crate) fn add_passage(&mut self, a: Coordinates, b: Coordinates) {
+        let inserted = self.passages.insert(a, b);
+        debug_assert!(
+            inserted,
+            "passages can only be carved between adjacent fields inside the maze"
+        );
+        if let Some(record) = &mut self.carving_record {
+            if inserted {
+                record.push((a, b));
             }
-            f.write_str("·\n")?;
-
-            // print left passage and room icon
-            for ix in 0..self.size.0 {
-                let field = self.get_field(&(ix, iy).into()).ok_or(std::fmt::Error {})?;
-                if field.has_passage(&Direction::West) {
-                    f.write_str(" ")?;
-                } else {
-                    f.write_str("|")?;
-                }
+        }
+    }
+
+    /// Whether a passage connects the two fields
+    pub(crate) fn has_passage_between(&self, a: Coordinates, b: Coordinates) -> bool {
+        self.passages.contains(a, b)
+    }
 
-                f.write_str(match field.field_type {
-                    FieldType::Start => "S",
-                    FieldType::Goal => "G",
-                    _ => " ",
-                })?;
+    /// Iterate over the coordinates which can be reached from `coordinates` through a passage
+    pub(crate) fn passage_neighbours(
+        &self,
+        coordinates: Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        self.passages.neighbours(coordinates)
+    }
+
+    /// Build the graph representation of this maze which contains every field as a node and
+    /// every passage as an edge
+    pub(crate) fn to_graph(&self) -> MazeGraph {
+        let mut graph = GraphMap::with_capacity(
+            (self.size.0 * self.size.1) as usize,
+            self.passages.passage_count(),
+        );
+        for coordinates in self.coordinates() {
+            graph.add_node(coordinates);
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if self.has_passage_between(coordinates, next) {
+                    graph.add_edge(coordinates, next, ());
+                }
             }
-            f.write_str("|\n")?;
+        }
+        graph
+    }
 
-            // print bottom line
-            if iy == self.size.1 - 1 {
-                for _ix in 0..self.size.0 {
-                    f.write_str("·-")?;
+    /// How many passages lead away from the field at `coordinates`
+    pub(crate) fn passage_count(&self, coordinates: Coordinates) -> usize {
+        self.passage_neighbours(coordinates).count()
+    }
+
+    /// Breadth-first search starting at `from`.
+    ///
+    /// Returns the distance (in steps) of every reachable field together with the coordinates
+    /// from which that field was first reached.
+    pub(crate) fn breadth_first_search(
+        &self,
+        from: Coordinates,
+    ) -> HashMap<Coordinates, (usize, Option<Coordinates>)> {
+        let mut result = HashMap::new();
+        let mut queue = VecDeque::new();
+        result.insert(from, (0, None));
+        queue.push_back(from);
+
+        while let Some(i_coords) = queue.pop_front() {
+            let distance = result[&i_coords].0;
+            for i_next in self.passage_neighbours(i_coords) {
+                if let Entry::Vacant(entry) = result.entry(i_next) {
+                    entry.insert((distance + 1, Some(i_coords)));
+                    queue.push_back(i_next);
                 }
-                f.write_str("·\n")?;
             }
         }
 
-        Ok(())
+        result
     }
-}
 
-impl Maze {
-    /// Generate an SVG version of the maze, returned as a String which you can then write to a file or use directly
-    pub fn to_svg(&self, svgoptions: SvgOptions) -> Result<String> {
-        // Get the options for convenience
-        let padding = svgoptions.padding; // Pad the maze all around by this amount.
-        let markersize = svgoptions.markersize; // Size of the Start and Goal markers
-        let mut height = match svgoptions.height {
-            // Height and width of the maze image (excluding padding), in pixels
-            None => (2 + self.size.1) * padding,
-            Some(h) => h,
-        };
-        let mut width = height * self.size.0 / self.size.1; // Derive width based on height
-
-        // Scaling factors mapping maze coordinates to image/svg coordinates
-        let scx = width / self.size.0;
-        let scx2 = scx / 2;
-        let scy = height / self.size.1;
-        let scy2 = scy / 2;
-        // Recalculate integer width, height now that we have the actual elements
-        width = scx * self.size.0;
-        height = scy * self.size.1;
-        let mut x1;
-        let mut x2;
-        let mut y1;
-        let mut y2;
-
-        // Write the SVG to the return String
-        let mut svg = String::new();
-        writeln!(svg, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
-        writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\"")?;
-        writeln!(svg, "    xmlns:xlink=\"http://www.w3.org/1999/xlink\"")?;
-        writeln!(
-            svg,
-            "    width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
-            width + 2 * padding,
-            height + 2 * padding,
-            -padding,
-            -padding,
-            width + 2 * padding,
-            height + 2 * padding
-        )?;
-
-        writeln!(svg, "<defs>\n<style type=\"text/css\"><![CDATA[")?;
-        writeln!(svg, "line {{")?;
-        writeln!(
-            svg,
-            "    stroke: {};\n    stroke-linecap: square;",
-            svgoptions.strokecol
-        )?;
-        writeln!(svg, "    stroke-width: {};\n}}", svgoptions.strokewidth)?;
-        writeln!(svg, "]]></style>\n</defs>")?;
-
-        for iy in 0..self.size.1 {
-            // print top passage
-            for ix in 0..self.size.0 {
-                if self
-                    .get_field(&(ix, iy).into())
-                    .ok_or_else(|| {
-                        anyhow!("Could not get maze field at coordinates {},{}", ix, iy)
-                    })?
-                    .has_passage(&Direction::North)
-                {
-                    // Do nothing. This code structure keeps the SVG output aligned with the original text debug output
-                } else {
-                    x1 = ix * scx;
-                    y1 = iy * scy;
-                    x2 = (ix + 1) * scx;
-                    y2 = iy * scy;
-                    writeln!(
-                        svg,
-                        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
-                        x1, y1, x2, y2
-                    )?;
+    /// List all fields reachable from `from` in the order in which a breadth-first search
+    /// reaches them, together with their distance from `from` in steps.
+    ///
+    /// The distances never decrease along the list, so it can directly drive a flood-fill
+    /// animation.
+    /// Neighbours of a field are visited in the order of [`Direction::all()`].
+    /// The list is empty if `from` lies outside of the maze.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let order = maze.bfs_order(maze.start);
+    ///
+    /// assert_eq!(order.len(), 9);
+    /// assert_eq!(order[0], (maze.start, 0));
+    /// assert!(order.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    /// ```
+    #[must_use]
+    pub fn bfs_order(&self, from: Coordinates) -> Vec<(Coordinates, u32)> {
+        if !self.are_coordinates_inside(&from) {
+            return Vec::new();
+        }
+        let mut order = vec![(from, 0)];
+        let mut visited = HashSet::new();
+        visited.insert(from);
+
+        // the list itself is the queue of the search
+        let mut i = 0;
+        while let Some(&(i_coords, depth)) = order.get(i) {
+            for i_next in self.passage_neighbours(i_coords) {
+                if visited.insert(i_next) {
+                    order.push((i_next, depth + 1));
                 }
             }
+            i += 1;
+        }
+
+        order
+    }
 
-            // print left passage and room markers
-            for ix in 0..self.size.0 {
-                let field = self.get_field(&(ix, iy).into()).ok_or_else(|| {
-                    anyhow!("Could not get maze field at coordinates {},{}", ix, iy)
-                })?;
-                if field.has_passage(&Direction::West) {
-                    // Do nothing
-                } else {
-                    x1 = ix * scx;
-                    y1 = iy * scy;
-                    x2 = ix * scx;
-                    y2 = (iy + 1) * scy;
-                    writeln!(
-                        svg,
-                        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
-                        x1, y1, x2, y2
-                    )?;
+    /// The parent of every field in the tree of shortest routes towards `root`, e.g. for
+    /// uploading the maze to a GPU and reconstructing routes without any search.
+    ///
+    /// Entry `y * width + x` holds the field from which the field at `(x, y)` is one step
+    /// closer to `root`, so following the parents from any field leads to `root` on a shortest
+    /// route.
+    /// The root itself, fields which can not reach it and all fields of a root outside of the
+    /// maze have no parent.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(4, 3).unwrap();
+    /// let parents = maze.to_parent_array(maze.goal);
+    ///
+    /// let mut route = vec![maze.start];
+    /// let index = |c: &Coordinates| (c.y * 4 + c.x) as usize;
+    /// while let Some(parent) = route.last().and_then(|c| parents[index(c)]) {
+    ///     route.push(parent);
+    /// }
+    /// assert_eq!(route.last(), Some(&maze.goal));
+    /// ```
+    #[must_use]
+    pub fn to_parent_array(&self, root: Coordinates) -> Vec<Option<Coordinates>> {
+        let mut parents = vec![None; self.topology().cell_count()];
+        if !self.are_coordinates_inside(&root) {
+            return parents;
+        }
+
+        let width = self.size.0;
+        for (coordinates, (_, parent)) in self.breadth_first_search(root) {
+            parents[(coordinates.y * width + coordinates.x) as usize] = parent;
+        }
+        parents
+    }
+
+    /// Coordinates of all fields which can not be reached from the start in row-major order.
+    ///
+    /// Mazes created by the generators never contain such fields but editing a maze, e.g. via
+    /// [`Maze::sparsify()`], can cut fields off.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S  | |
+    /// ·-·-·-·
+    /// |G    |
+    /// ·-·-·-·
+    /// ").unwrap();
+    ///
+    /// assert_eq!(
+    ///     maze.unreachable_cells(),
+    ///     vec![
+    ///         Coordinates::new(2, 0),
+    ///         Coordinates::new(0, 1),
+    ///         Coordinates::new(1, 1),
+    ///         Coordinates::new(2, 1),
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn unreachable_cells(&self) -> Vec<Coordinates> {
+        let reachable = self.breadth_first_search(self.start);
+        self.coordinates()
+            .filter(|c| !reachable.contains_key(c))
+            .collect()
+    }
+
+    /// Find the shortest route between two fields.
+    ///
+    /// The returned path includes both `from` and `to` or is `None` if `to` is not reachable.
+    pub(crate) fn find_path(&self, from: Coordinates, to: Coordinates) -> Option<Vec<Coordinates>> {
+        let search = self.breadth_first_search(from);
+        let mut path = vec![to];
+        let mut current = search.get(&to)?;
+        while let (_, Some(previous)) = current {
+            path.push(*previous);
+            current = &search[previous];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Uniformly chosen coordinates of a field inside of the maze
+    pub(crate) fn random_coordinates<R: Rng + ?Sized>(&self, rng: &mut R) -> Coordinates {
+        Coordinates::new(rng.gen_range(0, self.size.0), rng.gen_range(0, self.size.1))
+    }
+
+    /// Breadth-first search over the field indices `y * width + x` of the maze starting at `from`.
+    ///
+    /// Returns the distance of every field from `from` together with the index of the field from
+    /// which it was reached, both being `u32::MAX` for fields which are not reachable.
+    /// The parent of `from` itself is `u32::MAX` as well.
+    /// This is far cheaper than [`Maze::breadth_first_search`] on large mazes.
+    fn indexed_search(&self, from: Coordinates) -> (Vec<u32>, Vec<u32>) {
+        let width = self.size.0;
+        let index = |c: Coordinates| (c.y * width + c.x) as usize;
+        let mut distances = vec![u32::MAX; self.topology().cell_count()];
+        let mut parents = vec![u32::MAX; self.topology().cell_count()];
+        if !self.are_coordinates_inside(&from) {
+            return (distances, parents);
+        }
+
+        distances[index(from)] = 0;
+        let mut queue = VecDeque::from(vec![from]);
+        while let Some(current) = queue.pop_front() {
+            let current_index = index(current);
+            let distance = distances[current_index] + 1;
+            for neighbour in self.passage_neighbours(current) {
+                let neighbour_index = index(neighbour);
+                if distances[neighbour_index] == u32::MAX {
+                    distances[neighbour_index] = distance;
+                    parents[neighbour_index] = current_index as u32;
+                    queue.push_back(neighbour);
                 }
-                // Special cells
-                match field.field_type {
-                    FieldType::Start => {
-                        x1 = ix * scx + scx2;
-                        y1 = iy * scy + scy2;
-                        writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" stroke=\"{}\" stroke-width=\"{}\" fill=\"{}\" />", x1, y1, markersize, svgoptions.startcol, markersize + 1, svgoptions.startcol)?;
-                    }
-                    FieldType::Goal => {
-                        x1 = ix * scx + scx2;
-                        y1 = iy * scy + scy2;
-                        writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" stroke=\"{}\" stroke-width=\"{}\" fill=\"{}\" />", x1, y1, markersize, svgoptions.goalcol, markersize + 1, svgoptions.goalcol)?;
-                    }
-                    _ => continue,
-                };
             }
+        }
+        (distances, parents)
+    }
+
+    /// The reachable field with the largest distance in the result of [`Maze::indexed_search`].
+    ///
+    /// Ties are broken in favour of the smallest coordinates so that the result is deterministic.
+    fn farthest_in(&self, distances: &[u32], from: Coordinates) -> (Coordinates, usize) {
+        self.coordinates()
+            .zip(distances)
+            .filter(|(_, distance)| **distance != u32::MAX)
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(&a.0)))
+            .map_or((from, 0), |(coordinates, distance)| {
+                (coordinates, *distance as usize)
+            })
+    }
+
+    /// The field which is farthest away from `from` together with its distance in steps.
+    ///
+    /// Ties are broken in favour of the smallest coordinates so that the result is deterministic.
+    pub(crate) fn farthest_from(&self, from: Coordinates) -> (Coordinates, usize) {
+        let (distances, _) = self.indexed_search(from);
+        self.farthest_in(&distances, from)
+    }
 
-            // print bottom border line
-            x1 = 0;
-            y1 = (self.size.1) * scy;
-            x2 = (self.size.0) * scx;
-            y2 = (self.size.1) * scy;
-            writeln!(
-                svg,
-                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
-                x1, y1, x2, y2
-            )?;
-
-            // print right border line
-            x1 = (self.size.0) * scx;
-            y1 = 0;
-            x2 = (self.size.0) * scx;
-            y2 = (self.size.1) * scy;
-            writeln!(
-                svg,
-                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
-                x1, y1, x2, y2
-            )?;
+    /// Find the longest route through the maze, i.e. the diameter of the passage graph.
+    ///
+    /// Returns both ends of the route together with the route itself which includes both ends.
+    /// The route is computed by searching the field which is farthest away from the start field
+    /// and then the field which is farthest away from that one, whose search also yields the route.
+    /// This is exact for perfect mazes, which all generators produce, while only fields reachable
+    /// from the start are considered if the maze was edited to be disconnected.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let (from, to, path) = maze.longest_path();
+    ///
+    /// assert_eq!((from, to), (Coordinates::new(0, 2), Coordinates::new(0, 0)));
+    /// assert_eq!(path.len(), 7);
+    /// ```
+    #[must_use]
+    pub fn longest_path(&self) -> (Coordinates, Coordinates, Vec<Coordinates>) {
+        let (from, _) = self.farthest_from(self.start);
+        let (distances, parents) = self.indexed_search(from);
+        let (to, _) = self.farthest_in(&distances, from);
+
+        let width = self.size.0;
+        let mut path = vec![to];
+        let mut current = (to.y * width + to.x) as usize;
+        while let Some(&parent) = parents.get(current).filter(|p| **p != u32::MAX) {
+            current = parent as usize;
+            path.push(Coordinates::new(
+                current as i32 % width,
+                current as i32 / width,
+            ));
         }
-        writeln!(svg, "</svg>")?;
+        path.reverse();
+        (from, to, path)
+    }
 
-        Ok(svg)
+    /// Whether both mazes have the same size, start, goal and rooms and their passages connect
+    /// the fields in the same shape, even if the passages lie at different places.
+    ///
+    /// This is much more expensive than comparing two mazes with `==`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze1 = Maze::from_ascii("·-·-·-·\n|S  |G|\n·-·-·-·\n").unwrap();
+    /// let maze2 = Maze::from_ascii("·-·-·-·\n|S|  G|\n·-·-·-·\n").unwrap();
+    ///
+    /// assert_ne!(maze1, maze2);
+    /// assert!(maze1.is_isomorphic_to(&maze2));
+    /// ```
+    #[must_use]
+    pub fn is_isomorphic_to(&self, other: &Maze) -> bool {
+        self.start == other.start
+            && self.goal == other.goal
+            && self.size == other.size
+            && self.rooms == other.rooms
+            && is_isomorphic(
+                &self.to_graph().into_graph::<DefaultIx>(),
+                &other.to_graph().into_graph::<DefaultIx>(),
+            )
     }
 }
 
@@ -262,20 +545,214 @@ impl Maze {
 // can not be guaranteed (size, start, goal could be made invalid).
 impl From<Maze> for MazeGraph {
     fn from(m: Maze) -> Self {
-        m.graph
+        m.to_graph()
     }
 }
 
+// the render style only affects how a maze is displayed and the provenance how it was made, so
+// neither is part of its identity
 impl PartialEq for Maze {
     fn eq(&self, other: &Self) -> bool {
-        self.start == other.start
+        self.passages == other.passages
+            && self.start == other.start
             && self.goal == other.goal
             && self.size == other.size
-            && is_isomorphic(
-                &self.graph.clone().into_graph::<DefaultIx>(),
-                &other.graph.clone().into_graph::<DefaultIx>(),
-            )
+            && self.rooms == other.rooms
     }
 }
 
 impl Eq for Maze {}
+
+impl Hash for Maze {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.passages.hash(state);
+        self.start.hash(state);
+        self.goal.hash(state);
+        self.size.hash(state);
+        self.rooms.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ellers_algorithm::EllersGenerator;
+    use crate::growing_tree::GrowingTreeGenerator;
+    use crate::prelude::{Coordinates, Direction, Generator, Maze};
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_maze_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Maze>();
+    }
+
+    #[test]
+    fn test_equality_compares_passages() -> anyhow::Result<()> {
+        // both mazes are a single corridor through all fields but take different routes
+        let maze1 = Maze::from_ascii("·-·-·\n|S  |\n· · ·\n| |G|\n·-·-·\n")?;
+        let maze2 = Maze::from_ascii("·-·-·\n|S| |\n· · ·\n|  G|\n·-·-·\n")?;
+        assert_ne!(maze1, maze2);
+        assert!(maze1.is_isomorphic_to(&maze2));
+        assert_eq!(maze1, maze1.clone());
+
+        let mut moved_goal = maze1.clone();
+        moved_goal.goal = Coordinates::new(0, 1);
+        assert!(!moved_goal.is_isomorphic_to(&maze1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_longest_path_is_the_diameter() -> anyhow::Result<()> {
+        // three branches meet below the start so the field farthest away from the start,
+        // which used to be chosen as the goal, only ends a route of four fields
+        let maze =
+            Maze::from_ascii("·-·-·-·\n|     |\n·-· ·-·\n| |S| |\n· · · ·\n|G    |\n·-·-·-·\n")?;
+        assert_eq!(maze.farthest_from(maze.start), (Coordinates::new(0, 1), 3));
+
+        let (from, to, path) = maze.longest_path();
+        let diameter = maze
+            .coordinates()
+            .map(|c| maze.farthest_from(c).1)
+            .max()
+            .unwrap_or_default();
+        assert_eq!(diameter, 5);
+        assert_eq!((from, to), (Coordinates::new(0, 1), Coordinates::new(0, 0)));
+        assert_eq!(path.len(), diameter + 1);
+        assert_eq!((path.first(), path.last()), (Some(&from), Some(&to)));
+        assert!(path
+            .windows(2)
+            .all(|w| maze.has_passage_between(w[0], w[1])));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bfs_order_matches_distances() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([12; 32])).generate(9, 7)?;
+        maze.sparsify(2);
+
+        let order = maze.bfs_order(maze.goal);
+        let search = maze.breadth_first_search(maze.goal);
+        assert_eq!(order.len(), search.len());
+        for (coordinates, depth) in order.iter() {
+            assert_eq!(
+                search.get(coordinates).map(|(d, _)| *d as u32),
+                Some(*depth)
+            );
+        }
+        assert!(maze.bfs_order(Coordinates::new(-1, 0)).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparsified_fields_are_unreachable() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([12; 32])).generate(9, 7)?;
+        assert!(maze.unreachable_cells().is_empty());
+
+        let removed = maze.sparsify(3);
+        let unreachable = maze.unreachable_cells();
+        assert_eq!(unreachable.len(), removed);
+        assert!(unreachable.iter().all(|c| maze.passage_count(*c) == 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_passage_matches_field() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(6, 5)?;
+
+        for coordinates in maze.coordinates() {
+            let field = maze.get_field(&coordinates);
+            for direction in Direction::all().iter() {
+                assert_eq!(
+                    field.map(|f| f.has_passage(direction)),
+                    Some(maze.has_passage(&coordinates, direction))
+                );
+            }
+        }
+        assert!(!maze.has_passage(&Coordinates::new(-1, 0), &Direction::East));
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbour_iterators() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([6; 32])).generate(5, 4)?;
+
+        for coordinates in maze.coordinates() {
+            let field = maze
+                .get_field(&coordinates)
+                .ok_or_else(|| anyhow::anyhow!("field is missing"))?;
+            let reachable: Vec<Coordinates> = maze.reachable_neighbors(&coordinates).collect();
+            let expected: Vec<Coordinates> = field
+                .neighbours(&maze)
+                .iter()
+                .map(|neighbour| neighbour.coordinates)
+                .collect();
+            assert_eq!(reachable, expected);
+
+            let adjacent: Vec<Coordinates> = maze.adjacent_cells(&coordinates).collect();
+            assert!(reachable.iter().all(|c| adjacent.contains(c)));
+        }
+
+        let corner: Vec<Coordinates> = maze.adjacent_cells(&Coordinates::new(0, 0)).collect();
+        assert_eq!(corner, vec![Coordinates::new(1, 0), Coordinates::new(0, 1)]);
+        assert_eq!(maze.adjacent_cells(&Coordinates::new(2, 1)).count(), 4);
+        assert_eq!(maze.adjacent_cells(&Coordinates::new(-1, 0)).count(), 0);
+        assert_eq!(maze.reachable_neighbors(&Coordinates::new(5, 0)).count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parent_array_leads_to_root() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([19; 32])).generate(7, 5)?;
+        maze.braid(1.0, &mut rand::rngs::mock::StepRng::new(0, 1));
+        let parents = maze.to_parent_array(maze.goal);
+        assert_eq!(parents.len(), 7 * 5);
+
+        let search = maze.breadth_first_search(maze.goal);
+        for (i_field, coordinates) in maze.coordinates().enumerate() {
+            match parents[i_field] {
+                Some(parent) => {
+                    assert!(maze.has_passage_between(coordinates, parent));
+                    assert_eq!(search[&parent].0 + 1, search[&coordinates].0);
+                }
+                None => assert_eq!(coordinates, maze.goal),
+            }
+        }
+
+        let outside = maze.to_parent_array(Coordinates::new(7, 0));
+        assert!(outside.iter().all(Option::is_none));
+        Ok(())
+    }
+
+    #[test]
+    fn test_carving_record_follows_the_generation() -> anyhow::Result<()> {
+        let generators: Vec<Box<dyn Generator>> = vec![
+            Box::new(EllersGenerator::new(Some([7; 32]))),
+            Box::new(GrowingTreeGenerator::new(Some([7; 32]))),
+            Box::new(PrimsGenerator::new(Some([7; 32]))),
+            Box::new(RbGenerator::new(Some([7; 32]))),
+        ];
+
+        for mut generator in generators {
+            let mut maze = generator.generate(6, 4)?;
+            assert!(maze.take_carving_record().is_empty());
+
+            maze.record_carving();
+            let previous = maze.clone();
+            generator.generate_into(&mut maze)?;
+            assert_ne!(maze, previous);
+            let record = maze.take_carving_record();
+            assert_eq!(record.len(), 6 * 4 - 1);
+            assert!(record.iter().all(|&(a, b)| maze.has_passage_between(a, b)));
+
+            // regenerating clears the record and taking it stops the recording
+            maze.record_carving();
+            generator.generate_into(&mut maze)?;
+            generator.generate_into(&mut maze)?;
+            assert_eq!(maze.take_carving_record().len(), 6 * 4 - 1);
+            generator.generate_into(&mut maze)?;
+            assert!(maze.take_carving_record().is_empty());
+        }
+        Ok(())
+    }
+}
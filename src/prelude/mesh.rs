@@ -0,0 +1,135 @@
+use std::fmt::Write;
+
+use crate::prelude::*;
+
+/// Wavefront OBJ document built one quad at a time
+struct ObjWriter {
+    obj: String,
+    vertex_count: usize,
+}
+
+impl ObjWriter {
+    /// Append a quad whose vertices are given counter-clockwise when viewed from the outside
+    fn quad(&mut self, vertices: [(f64, f64, f64); 4]) {
+        // writing into a string never fails
+        for (x, y, z) in vertices.iter() {
+            let _ = writeln!(self.obj, "v {} {} {}", x, y, z);
+        }
+        let first = self.vertex_count + 1;
+        let _ = writeln!(
+            self.obj,
+            "f {} {} {} {}",
+            first,
+            first + 1,
+            first + 2,
+            first + 3
+        );
+        self.vertex_count += 4;
+    }
+}
+
+/// Range covered by the cell at `index` of a [block grid](Maze::to_block_grid()) along one axis
+/// in which fields are one unit wide and walls are centered on the borders between them
+fn block_span(index: usize, wall_thickness: f64) -> (f64, f64) {
+    let border = (index / 2) as f64;
+    let half = wall_thickness / 2.0;
+    if index % 2 == 1 {
+        (border + half, border + 1.0 - half)
+    } else {
+        (border - half, border + half)
+    }
+}
+
+impl Maze {
+    /// Export the walls of the maze as a Wavefront OBJ mesh, e.g. for game engines or 3D
+    /// printing.
+    ///
+    /// Fields are one unit wide with `x` pointing east, `z` pointing south and `y` pointing
+    /// upwards.
+    /// Walls are centered on the borders between fields, extruded to `wall_height` and
+    /// `wall_thickness` wide, which is clamped to the range `0..=1`.
+    /// The mesh consists of a single floor quad at `y = 0` and the outside faces of the walls,
+    /// leaving out those which are hidden by neighbouring walls.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let obj = maze.to_obj(2.0, 0.2);
+    ///
+    /// assert!(obj.contains("\nv -0.1 0 -0.1\n"));
+    /// assert!(obj.lines().any(|line| line.starts_with("f ")));
+    /// ```
+    #[must_use]
+    pub fn to_obj(&self, wall_height: f64, wall_thickness: f64) -> String {
+        let thickness = wall_thickness.clamp(0.0, 1.0);
+        let grid = self.to_block_grid();
+        let blocked = |x: usize, y: usize| grid.get(y).and_then(|row| row.get(x)) == Some(&true);
+        let mut writer = ObjWriter {
+            obj: String::from("# maze_generator\no maze\n"),
+            vertex_count: 0,
+        };
+
+        let (x0, _) = block_span(0, thickness);
+        let (z0, _) = block_span(0, thickness);
+        let (_, x1) = block_span(2 * self.size.0 as usize, thickness);
+        let (_, z1) = block_span(2 * self.size.1 as usize, thickness);
+        writer.quad([(x0, 0.0, z0), (x0, 0.0, z1), (x1, 0.0, z1), (x1, 0.0, z0)]);
+
+        let h = wall_height;
+        for (iy, row) in grid.iter().enumerate() {
+            for (ix, is_blocked) in row.iter().enumerate() {
+                if !is_blocked {
+                    continue;
+                }
+                let (x0, x1) = block_span(ix, thickness);
+                let (z0, z1) = block_span(iy, thickness);
+
+                writer.quad([(x0, h, z0), (x0, h, z1), (x1, h, z1), (x1, h, z0)]);
+                if ix == 0 || !blocked(ix - 1, iy) {
+                    writer.quad([(x0, 0.0, z0), (x0, 0.0, z1), (x0, h, z1), (x0, h, z0)]);
+                }
+                if !blocked(ix + 1, iy) {
+                    writer.quad([(x1, 0.0, z0), (x1, h, z0), (x1, h, z1), (x1, 0.0, z1)]);
+                }
+                if iy == 0 || !blocked(ix, iy - 1) {
+                    writer.quad([(x0, 0.0, z0), (x0, h, z0), (x1, h, z0), (x1, 0.0, z0)]);
+                }
+                if !blocked(ix, iy + 1) {
+                    writer.quad([(x0, 0.0, z1), (x1, 0.0, z1), (x1, h, z1), (x0, h, z1)]);
+                }
+            }
+        }
+
+        writer.obj
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::Generator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_single_field_mesh() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([17; 32])).generate(1, 1)?;
+        let obj = maze.to_obj(3.0, 0.5);
+
+        // floor, the tops of 8 wall blocks, 12 outer and 4 inner side faces
+        let faces: Vec<&str> = obj.lines().filter(|l| l.starts_with("f ")).collect();
+        assert_eq!(faces.len(), 1 + 8 + 12 + 4);
+        assert_eq!(faces.last(), Some(&"f 97 98 99 100"));
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 100);
+
+        let mut heights = obj
+            .lines()
+            .filter_map(|l| l.strip_prefix("v "))
+            .filter_map(|v| v.split(' ').nth(1));
+        assert!(heights.all(|y| y == "0" || y == "3"));
+        assert!(obj.contains("\nv 1.25 0 1.25\n"));
+        assert!(obj.contains("\nv 0.25 3 0.75\n"));
+        Ok(())
+    }
+}
@@ -1,36 +1,350 @@
 //! Common traits and members
 
-use anyhow::Result;
 use thiserror::Error;
 
+use crate::builder::Algorithm;
+
+pub use algorithm_info::*;
+#[cfg(feature = "rayon")]
+pub use batch::*;
+#[cfg(feature = "pdf")]
+pub use booklet::*;
+pub use coordinate_system::*;
 pub use coordinates::*;
+pub use difficulty::*;
 pub use direction::*;
+pub(crate) use disjoint_sets::DisjointSets;
+pub use distance_matrix::*;
+pub use distance_oracle::*;
+pub use dot::*;
+pub use editing::*;
 pub use field::*;
+pub(crate) use fingerprint::stable_hash;
+pub use goal_placement::*;
+pub use hex::*;
+pub use junction_graph::*;
+#[cfg(feature = "image")]
+pub use mask::*;
 pub use maze::*;
+pub use movement::*;
+pub use obstacles::*;
+pub use parse::*;
+pub use partition::*;
+pub use passages::*;
+pub use path_format::*;
+#[cfg(feature = "pdf")]
+pub use pdf::*;
+pub use provenance::*;
+pub use region::*;
+pub use render::*;
+pub use render_style::*;
+pub(crate) use rng_checkpoint::RngCheckpoint;
+pub use room::*;
+pub use scatter::*;
+pub use solve_trace::*;
+#[cfg(feature = "svg")]
 pub use svgoptions::*;
+pub use texture::*;
+pub use tilemap::*;
+#[cfg(feature = "tmx")]
+pub use tmx::*;
+pub use topology::*;
+pub use validate::*;
+pub use voxel::*;
+pub use wall_grid::*;
+pub use weight_map::*;
 
+mod algorithm_info;
+#[cfg(feature = "ndarray")]
+mod array;
+#[cfg(feature = "rayon")]
+mod batch;
+#[cfg(feature = "pdf")]
+mod booklet;
+mod coordinate_system;
 mod coordinates;
+mod cycles;
+mod dead_ends;
+mod difficulty;
 mod direction;
+mod disjoint_sets;
+mod distance_bands;
+mod distance_matrix;
+mod distance_oracle;
+mod dot;
+mod editing;
 mod field;
+mod fingerprint;
+mod goal_placement;
+mod hex;
+mod junction_graph;
+#[cfg(feature = "image")]
+mod mask;
 mod maze;
+#[cfg(feature = "mesh")]
+mod mesh;
+mod morphology;
+mod movement;
+mod obstacles;
+mod parse;
+mod partition;
+mod passage_grid;
+mod passages;
+mod path_format;
+#[cfg(feature = "pdf")]
+mod pdf;
+mod provenance;
+mod region;
+mod render;
+mod render_style;
+mod rng_checkpoint;
+mod room;
+mod scatter;
+mod solve_trace;
+#[cfg(feature = "svg")]
 mod svgoptions;
+mod texture;
+mod tilemap;
+#[cfg(feature = "tmx")]
+mod tmx;
+mod topology;
+mod transform;
+mod validate;
+mod voxel;
+mod wall_grid;
+mod weight_map;
+
+/// Seed from which generators derive all of their randomness
+pub type Seed = [u8; 32];
 
-/// Generic error type that could be returned by all implemented generators.
+/// Error type returned by all generators and the functions built on top of them
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let error = RbGenerator::new(None).generate(0, 5).unwrap_err();
+/// assert_eq!(error, MazeError::InvalidDimensions { width: 0, height: 5 });
+/// assert!(!error.is_bug());
+/// ```
 #[derive(Error, Debug, Clone, Eq, PartialEq)]
-pub enum GenericGeneratorError {
+#[non_exhaustive]
+pub enum MazeError {
+    /// The requested size can not be generated, e.g. because it is not positive or because
+    /// the generator needs a square maze
+    #[error("Can not generate a maze of size {width}x{height}")]
+    InvalidDimensions {
+        /// The requested width
+        width: i32,
+        /// The requested height
+        height: i32,
+    },
+    /// The requested start field lies outside of the maze, see [`Generator::generate_from()`]
+    #[error("The start {start} lies outside of the {}x{} maze", size.0, size.1)]
+    StartOutsideMaze {
+        /// The requested start field
+        start: Coordinates,
+        /// The size of the maze
+        size: (i32, i32),
+    },
+    /// A theta maze needs at least one ring
+    #[error("A theta maze needs at least one ring")]
+    NoRings,
+    /// A [`MazeStack`](crate::stack::MazeStack) needs at least one level
+    #[error("A maze stack needs at least one level")]
+    NoLevels,
+    /// A custom selection policy of a
+    /// [`GrowingTreeGenerator`](crate::growing_tree::GrowingTreeGenerator) chose a cell which
+    /// does not exist
+    #[error("The selector chose cell {index} of only {candidates} candidates")]
+    InvalidSelection {
+        /// The chosen index
+        index: usize,
+        /// The number of cells it could have chosen from
+        candidates: usize,
+    },
+    /// A paused generation can not be resumed because its state does not describe a partially
+    /// generated maze, e.g. because it was modified after being serialized
+    #[error("The state of the paused generation is invalid")]
+    InvalidState,
+    /// A room could not be carved into the generated maze
+    #[error("Could not carve room into the generated maze: {0}")]
+    Room(#[from] RoomError),
+    /// A region of the maze could not be re-generated, see [`Maze::regenerate_region()`]
+    #[error(transparent)]
+    Region(#[from] RegionError),
+    /// No maze of the requested difficulty could be generated
+    #[error(transparent)]
+    Difficulty(#[from] DifficultyError),
+    /// The maze could not be generated around the given obstacles
+    #[error(transparent)]
+    Obstacles(#[from] ObstacleError),
+    /// The algorithm can not generate mazes with a mask, see
+    /// [`MazeBuilder::mask()`](crate::builder::MazeBuilder::mask)
+    #[error("The {0:?} algorithm does not support masks")]
+    MaskNotSupported(Algorithm),
+    /// The worker threads for generating multiple mazes in parallel could not be started
+    #[error("Could not start worker threads: {0}")]
+    WorkerThreads(String),
     /// Error that can be raised anywhere in a generator that is not otherwise explicitly handled.
     ///
     /// This is used as a way to signal bugs.
     /// They should hopefully never actually be raised but if they are, a bug should be reported.
     #[error("Unknown internal error. If this is reproducible, please report a bug at https://github.com/ftsell/maze_generator/issues/new : {0}")]
     InternalError(String),
+    /// The generator could not connect all fields of the maze.
+    ///
+    /// Like [`InternalError`](MazeError::InternalError) this signals a bug.
+    #[error("The generated maze is incomplete, {} fields could not be connected", missing.len())]
+    IncompleteMaze {
+        /// Coordinates of all fields which are not connected to the rest of the maze
+        missing: Vec<Coordinates>,
+    },
 }
 
+impl MazeError {
+    /// Whether this error is caused by a bug in this crate which should be reported
+    #[must_use]
+    pub fn is_bug(&self) -> bool {
+        matches!(
+            self,
+            MazeError::InternalError(_) | MazeError::IncompleteMaze { .. }
+        )
+    }
+}
+
+/// Previous name of [`MazeError`]
+#[deprecated(since = "3.0.0", note = "renamed to `MazeError`")]
+pub type GenericGeneratorError = MazeError;
+
 /// Generic generator Api implemented by all algorithms to generate a maze
+///
+/// The trait is object safe so that generators can be selected at runtime as
+/// `Box<dyn Generator>`.
 pub trait Generator {
     /// Key function to generate a maze
     ///
     /// The returned [`Maze`] will have the provided width and height.
-    /// It can be any rectangular shape.
-    fn generate(&mut self, width: i32, height: i32) -> Result<Maze>;
+    /// It can be any rectangular shape but both width and height need to be positive, otherwise
+    /// [`MazeError::InvalidDimensions`] is returned.
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError>;
+
+    /// Generate a new maze into an existing one, reusing its allocated storage
+    ///
+    /// This avoids allocations when regenerating mazes repeatedly, e.g. for every level of a game.
+    /// The size of the new maze is taken from [`Maze::size`] so it can be changed between
+    /// generations while everything else, including passages, rooms, start and goal, is
+    /// replaced.
+    /// All generators of this crate generate the same maze as [`generate()`](Generator::generate)
+    /// would when given the same seed.
+    ///
+    /// The default implementation simply replaces `maze` with a newly generated one.
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        *maze = self.generate(maze.size.0, maze.size.1)?;
+        Ok(())
+    }
+
+    /// Generate a maze whose start lies at `start`, e.g. to anchor the maze to a door in a larger
+    /// map.
+    ///
+    /// The goal is placed as far away from the start as possible.
+    /// Generators which carve outwards from a single field start carving at `start` so that the
+    /// texture of the maze spreads out from there.
+    /// It is an error if `start` lies outside of the maze.
+    ///
+    /// The default implementation generates a maze via [`generate()`](Generator::generate) and
+    /// then moves its start and goal.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    ///
+    /// let mut generator = PrimsGenerator::new(Some([42; 32]));
+    /// let maze = generator.generate_from(8, 6, Coordinates::new(7, 3)).unwrap();
+    ///
+    /// assert_eq!(maze.start, Coordinates::new(7, 3));
+    /// assert!(generator.generate_from(8, 6, Coordinates::new(8, 3)).is_err());
+    /// ```
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        ensure_start_inside(width, height, start)?;
+        let mut maze = self.generate(width, height)?;
+        maze.start = start;
+        maze.goal = maze.farthest_from(start).0;
+        Ok(maze)
+    }
+
+    /// Describe the algorithm which is used by this generator
+    ///
+    /// Generators which do not override this return [`AlgorithmInfo::UNKNOWN`].
+    fn info(&self) -> AlgorithmInfo {
+        AlgorithmInfo::UNKNOWN
+    }
+}
+
+/// Boxed generators, e.g. from [`generators::by_name()`](crate::generators::by_name), can be
+/// used wherever a generic generator is expected
+impl<G: Generator + ?Sized> Generator for Box<G> {
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        (**self).generate(width, height)
+    }
+
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        (**self).generate_into(maze)
+    }
+
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        (**self).generate_from(width, height, start)
+    }
+
+    fn info(&self) -> AlgorithmInfo {
+        (**self).info()
+    }
+}
+
+/// Make sure that a maze of the given size can be generated
+pub(crate) fn ensure_valid_size(width: i32, height: i32) -> Result<(), MazeError> {
+    if width > 0 && height > 0 {
+        Ok(())
+    } else {
+        Err(MazeError::InvalidDimensions { width, height })
+    }
+}
+
+/// Make sure that a maze of the given size can start at `start`, see
+/// [`Generator::generate_from()`]
+pub(crate) fn ensure_start_inside(
+    width: i32,
+    height: i32,
+    start: Coordinates,
+) -> Result<(), MazeError> {
+    ensure_valid_size(width, height)?;
+    if RectangularTopology::new(width, height).contains(&start) {
+        Ok(())
+    } else {
+        Err(MazeError::StartOutsideMaze {
+            start,
+            size: (width, height),
+        })
+    }
+}
+
+/// Sample an index into a collection of `len` elements
+///
+/// Sampling a `usize` directly consumes and maps random numbers differently on 32 and 64 bit
+/// targets, so the index is always sampled as a `u64` to generate the same maze from a seed on
+/// every platform.
+pub(crate) fn gen_index<R: rand::Rng + ?Sized>(rng: &mut R, len: usize) -> usize {
+    rng.gen_range(0, len as u64) as usize
 }
@@ -0,0 +1,176 @@
+use rand::prelude::*;
+
+use crate::prelude::*;
+
+impl Maze {
+    /// Remove all walls between the fields of `area`, e.g. to create arenas or caverns.
+    ///
+    /// Unlike [`Maze::carve_room()`] every corridor which leads into the area is kept, so the
+    /// maze usually gains loops, and the area is not recorded as a room.
+    /// Areas may overlap each other as well as rooms.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    ///
+    /// let mut maze = PrimsGenerator::new(Some([42; 32])).generate(8, 8).unwrap();
+    /// maze.open_area(Room::new(Coordinates::new(2, 2), 4, 4)).unwrap();
+    ///
+    /// assert!(maze.has_passage(&Coordinates::new(3, 3), &Direction::East));
+    /// assert!(maze.rooms().is_empty());
+    /// ```
+    pub fn open_area(&mut self, area: Room) -> Result<(), RoomError> {
+        if area.width <= 0 || area.height <= 0 {
+            return Err(RoomError::Empty(area));
+        }
+        let last = Coordinates::new(
+            area.origin.x + area.width - 1,
+            area.origin.y + area.height - 1,
+        );
+        if !self.are_coordinates_inside(&area.origin) || !self.are_coordinates_inside(&last) {
+            return Err(RoomError::OutsideMaze(area));
+        }
+
+        for coordinates in area.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if area.contains(&next) {
+                    self.passages.insert(coordinates, next);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The walls which meet at the corner north-west of the field at `coordinates`, as pairs of
+    /// the fields which they separate
+    fn walls_at_corner(&self, coordinates: Coordinates) -> Vec<(Coordinates, Coordinates)> {
+        let Coordinates { x, y } = coordinates;
+        let (north_west, north_east) = (Coordinates::new(x - 1, y - 1), Coordinates::new(x, y - 1));
+        let (south_west, south_east) = (Coordinates::new(x - 1, y), coordinates);
+        [
+            (north_west, north_east),
+            (south_west, south_east),
+            (north_west, south_west),
+            (north_east, south_east),
+        ]
+        .iter()
+        .copied()
+        .filter(|(a, b)| !self.has_passage_between(*a, *b))
+        .collect()
+    }
+
+    /// Remove wall stubs, i.e. walls which end in a corner without touching any other wall, each
+    /// with the given `chance`.
+    ///
+    /// This opens up the maze into a more arena-like map with loops around the remaining wall
+    /// segments.
+    /// Removing walls never disconnects any fields.
+    /// Only the stubs which exist before calling this are considered so that walls are not
+    /// eroded completely; call it again to shorten them further.
+    ///
+    /// Returns how many walls were removed.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaChaRng;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let mut rng = ChaChaRng::from_seed([1; 32]);
+    /// let removed = maze.remove_pillars(0.5, &mut rng);
+    ///
+    /// assert_eq!(maze.cycles().len(), removed);
+    /// ```
+    pub fn remove_pillars<R: Rng + ?Sized>(&mut self, chance: f64, rng: &mut R) -> usize {
+        let chance = chance.clamp(0.0, 1.0);
+        let (width, height) = self.size;
+        // the corners on the border of the maze always connect to its outer walls
+        let stubs: Vec<_> = (1..height)
+            .flat_map(|iy| (1..width).map(move |ix| Coordinates::new(ix, iy)))
+            .filter_map(|corner| match self.walls_at_corner(corner).as_slice() {
+                [wall] => Some(*wall),
+                _ => None,
+            })
+            .collect();
+
+        let mut removed = 0;
+        for (a, b) in stubs {
+            // a wall between two corners which are both free ends is listed twice
+            if !self.has_passage_between(a, b) && rng.gen_bool(chance) {
+                self.add_passage(a, b);
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::{Coordinates, Generator, Room, RoomError};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_opening_keeps_all_entrances() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([14; 32])).generate(8, 6)?;
+        let area = Room::new(Coordinates::new(1, 1), 3, 3);
+
+        let mut opened = maze.clone();
+        opened.open_area(area)?;
+        let mut roomed = maze.clone();
+        roomed.carve_room(area)?;
+
+        // the area gains the same passages as a room but keeps the redundant entrances
+        for coordinates in maze.coordinates() {
+            for next in maze.passage_neighbours(coordinates) {
+                assert!(opened.has_passage_between(coordinates, next));
+            }
+            for next in roomed.passage_neighbours(coordinates) {
+                assert!(opened.has_passage_between(coordinates, next));
+            }
+        }
+        assert!(opened.cycles().len() >= roomed.cycles().len());
+        assert_eq!(
+            opened.open_area(Room::new(Coordinates::new(6, 4), 3, 1)),
+            Err(RoomError::OutsideMaze(Room::new(
+                Coordinates::new(6, 4),
+                3,
+                1
+            )))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_pillar_removal_only_adds_passages() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([15; 32])).generate(12, 12)?;
+        let mut rng = ChaChaRng::from_seed([15; 32]);
+
+        let mut untouched = maze.clone();
+        assert_eq!(untouched.remove_pillars(0.0, &mut rng), 0);
+        assert_eq!(untouched, maze);
+
+        let mut opened = maze.clone();
+        let removed = opened.remove_pillars(1.0, &mut rng);
+        assert!(removed > 0);
+        assert_eq!(
+            opened.passages.passage_count(),
+            maze.passages.passage_count() + removed
+        );
+        assert!(opened.unreachable_cells().is_empty());
+        assert_eq!(opened.validate(), Ok(()));
+
+        // shortening the stubs leaves new ones behind which are only removed by another pass
+        assert!(opened.remove_pillars(1.0, &mut rng) > 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use crate::prelude::*;
+
+/// Outcome of trying to move through a maze, see [`Maze::try_move()`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MoveResult {
+    /// A passage led into the requested direction and the move ended at the contained field
+    Moved(Coordinates),
+    /// A wall separates the field from its neighbour in the requested direction
+    BlockedByWall,
+    /// The move started outside of the maze or would have left it
+    OutOfBounds,
+}
+
+impl MoveResult {
+    /// The field at which the move ended, `None` if it was not possible
+    #[must_use]
+    pub fn destination(&self) -> Option<Coordinates> {
+        match self {
+            MoveResult::Moved(to) => Some(*to),
+            _ => None,
+        }
+    }
+}
+
+/// A position inside a maze which can only be moved through passages, e.g. the player of a game
+///
+/// The walker does not hold on to the maze so that the maze can still be modified while it is
+/// walked, e.g. by opening doors.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let maze = Maze::from_ascii("·-·-·\n|S  |\n·-· ·\n|G  |\n·-·-·\n").unwrap();
+/// let mut walker = Walker::at_start(&maze);
+///
+/// assert_eq!(walker.step(&maze, Direction::South), MoveResult::BlockedByWall);
+/// assert_eq!(walker.step(&maze, Direction::North), MoveResult::OutOfBounds);
+/// for direction in [Direction::East, Direction::South, Direction::West].iter() {
+///     walker.step(&maze, *direction);
+/// }
+/// assert!(walker.has_reached_goal(&maze));
+/// assert_eq!(walker.steps, 3);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Walker {
+    /// Where the walker currently is
+    pub position: Coordinates,
+    /// How many moves the walker successfully made so far
+    pub steps: usize,
+}
+
+impl Walker {
+    /// Create a walker which is located at `position` and has not moved yet
+    #[must_use]
+    pub fn new(position: Coordinates) -> Self {
+        Walker { position, steps: 0 }
+    }
+
+    /// Create a walker which is located at the start field of `maze`
+    #[must_use]
+    pub fn at_start(maze: &Maze) -> Self {
+        Walker::new(maze.start)
+    }
+
+    /// Try to move one field into `direction` and follow the move if it was possible
+    pub fn step(&mut self, maze: &Maze, direction: Direction) -> MoveResult {
+        let result = maze.try_move(&self.position, &direction);
+        if let MoveResult::Moved(to) = result {
+            self.position = to;
+            self.steps += 1;
+        }
+        result
+    }
+
+    /// Whether the walker is located at the goal field of `maze`
+    #[must_use]
+    pub fn has_reached_goal(&self, maze: &Maze) -> bool {
+        self.position == maze.goal
+    }
+}
+
+impl Maze {
+    /// Try to move from the field at `from` one field into `direction`.
+    ///
+    /// North points towards decreasing `y` and west towards decreasing `x`, the same as for
+    /// [`Coordinates::next()`].
+    /// Leaving the maze counts as [`OutOfBounds`](MoveResult::OutOfBounds) instead of being
+    /// blocked by the outer wall.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii("·-·-·\n|S  |\n·-· ·\n|G  |\n·-·-·\n").unwrap();
+    /// let start = maze.start;
+    ///
+    /// assert_eq!(maze.try_move(&start, &Direction::East), MoveResult::Moved(Coordinates::new(1, 0)));
+    /// assert_eq!(maze.try_move(&start, &Direction::South), MoveResult::BlockedByWall);
+    /// assert_eq!(maze.try_move(&start, &Direction::West), MoveResult::OutOfBounds);
+    /// ```
+    #[must_use]
+    pub fn try_move(&self, from: &Coordinates, direction: &Direction) -> MoveResult {
+        let to = from.next(direction);
+        if !self.are_coordinates_inside(from) || !self.are_coordinates_inside(&to) {
+            MoveResult::OutOfBounds
+        } else if self.has_passage_between(*from, to) {
+            MoveResult::Moved(to)
+        } else {
+            MoveResult::BlockedByWall
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Direction, Generator, MoveResult, Walker};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_walker_follows_solution() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([15; 32])).generate(7, 6)?;
+        let path = maze
+            .find_path(maze.start, maze.goal)
+            .ok_or_else(|| anyhow::anyhow!("goal not reachable"))?;
+
+        let mut walker = Walker::at_start(&maze);
+        for step in path.windows(2) {
+            let direction = Direction::iter()
+                .find(|direction| step[0].next(direction) == step[1])
+                .ok_or_else(|| anyhow::anyhow!("fields are not adjacent"))?;
+            assert_eq!(walker.step(&maze, direction), MoveResult::Moved(step[1]));
+
+            // the other directions agree with the passages of the field
+            for other in Direction::iter().filter(|other| *other != direction) {
+                let result = maze.try_move(&step[0], &other);
+                assert_eq!(
+                    result.destination().is_some(),
+                    maze.has_passage(&step[0], &other)
+                );
+            }
+        }
+        assert!(walker.has_reached_goal(&maze));
+        assert_eq!(walker.steps, path.len() - 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_moves_out_of_bounds() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([16; 32])).generate(3, 3)?;
+        let corner = Coordinates::new(2, 2);
+        assert_eq!(
+            maze.try_move(&corner, &Direction::East),
+            MoveResult::OutOfBounds
+        );
+        assert_eq!(
+            maze.try_move(&corner, &Direction::South),
+            MoveResult::OutOfBounds
+        );
+
+        let mut outside = Walker::new(Coordinates::new(3, 2));
+        assert_eq!(
+            outside.step(&maze, Direction::West),
+            MoveResult::OutOfBounds
+        );
+        assert_eq!(outside, Walker::new(Coordinates::new(3, 2)));
+        Ok(())
+    }
+}
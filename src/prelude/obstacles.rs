@@ -0,0 +1,138 @@
+use std::collections::{HashSet, VecDeque};
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while generating a maze around obstacles, e.g. via
+/// [`RbGenerator::generate_with_obstacles()`](crate::recursive_backtracking::RbGenerator::generate_with_obstacles)
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum ObstacleError {
+    /// The obstacles cover the whole maze
+    #[error("There are no fields which are not covered by obstacles")]
+    NoFreeFields,
+    /// The obstacles split the remaining fields into multiple parts which can not be connected
+    #[error("The obstacles split the maze into {} unconnected parts", .0.len())]
+    Disconnected(
+        /// All connected parts of the remaining fields, see [`ObstacleTopology::components()`]
+        Vec<Vec<Coordinates>>,
+    ),
+}
+
+/// A rectangular grid in which some fields are blocked by obstacles
+///
+/// Blocked fields are not part of the topology so algorithms working on it never carve passages
+/// into them.
+///
+/// # Examples
+/// ```
+/// use std::collections::HashSet;
+/// use maze_generator::prelude::*;
+///
+/// let obstacles: HashSet<_> = [Coordinates::new(1, 0)].iter().copied().collect();
+/// let topology = ObstacleTopology::new(3, 2, &obstacles);
+///
+/// assert_eq!(topology.cell_count(), 5);
+/// assert_eq!(topology.neighbours(&Coordinates::new(0, 0)), vec![Coordinates::new(0, 1)]);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ObstacleTopology<'a> {
+    grid: RectangularTopology,
+    obstacles: &'a HashSet<Coordinates>,
+}
+
+impl<'a> ObstacleTopology<'a> {
+    /// Create a grid with the given number of columns and rows without the fields in `obstacles`
+    ///
+    /// Obstacles outside of the grid are ignored.
+    #[must_use]
+    pub fn new(width: i32, height: i32, obstacles: &'a HashSet<Coordinates>) -> Self {
+        ObstacleTopology {
+            grid: RectangularTopology::new(width, height),
+            obstacles,
+        }
+    }
+
+    /// All groups of fields which are connected to each other but not to any other field.
+    ///
+    /// Every group is sorted in row-major order and the groups are sorted by their first field.
+    #[must_use]
+    pub fn components(&self) -> Vec<Vec<Coordinates>> {
+        let mut components = Vec::new();
+        let mut visited = HashSet::new();
+
+        for cell in self.cells() {
+            if !visited.insert(cell) {
+                continue;
+            }
+            let mut component = vec![cell];
+            let mut queue = VecDeque::new();
+            queue.push_back(cell);
+            while let Some(i_cell) = queue.pop_front() {
+                for i_next in self.neighbours(&i_cell) {
+                    if visited.insert(i_next) {
+                        component.push(i_next);
+                        queue.push_back(i_next);
+                    }
+                }
+            }
+            component.sort_unstable_by_key(|c| (c.y, c.x));
+            components.push(component);
+        }
+
+        components
+    }
+}
+
+impl Topology for ObstacleTopology<'_> {
+    type Cell = Coordinates;
+
+    fn cells(&self) -> Vec<Coordinates> {
+        self.grid
+            .cells()
+            .into_iter()
+            .filter(|cell| !self.obstacles.contains(cell))
+            .collect()
+    }
+
+    fn contains(&self, cell: &Coordinates) -> bool {
+        self.grid.contains(cell) && !self.obstacles.contains(cell)
+    }
+
+    fn neighbours(&self, cell: &Coordinates) -> Vec<Coordinates> {
+        if !self.contains(cell) {
+            return Vec::new();
+        }
+        self.grid
+            .neighbours(cell)
+            .into_iter()
+            .filter(|next| !self.obstacles.contains(next))
+            .collect()
+    }
+
+    fn rendering_hint(&self) -> RenderingHint {
+        self.grid.rendering_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::prelude::{Coordinates, ObstacleTopology, Topology};
+
+    #[test]
+    fn test_components_are_split_by_obstacles() {
+        // a wall of obstacles through the middle column
+        let obstacles: HashSet<_> = (0..3).map(|iy| Coordinates::new(2, iy)).collect();
+        let topology = ObstacleTopology::new(5, 3, &obstacles);
+
+        let components = topology.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0][0], Coordinates::new(0, 0));
+        assert_eq!(components[1][0], Coordinates::new(3, 0));
+        assert!(components.iter().all(|component| component.len() == 6));
+        assert!(!topology.contains(&Coordinates::new(2, 1)));
+        assert!(topology.neighbours(&Coordinates::new(2, 1)).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while parsing the textual representation of a [`Maze`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParseError {
+    /// The text does not consist of an odd number of at least three lines
+    #[error("Expected an odd number of at least 3 lines but found {0}")]
+    InvalidLineCount(usize),
+    /// The first line does not have an odd length of at least 3 characters
+    #[error("First line should have an odd length of at least 3 but has {0} characters")]
+    InvalidFirstLineLength(usize),
+    /// A line does not have the length that was derived from the first line
+    #[error("Line {line} should be {expected} characters long but is {found}")]
+    InvalidLineLength {
+        /// Line number (starting at 1) in which the error occurred
+        line: usize,
+        /// How many characters the line should have
+        expected: usize,
+        /// How many characters the line actually has
+        found: usize,
+    },
+    /// A character which is not allowed at this position was encountered
+    #[error("Unexpected character '{character}' in line {line} column {column}")]
+    UnexpectedCharacter {
+        /// Line number (starting at 1) in which the error occurred
+        line: usize,
+        /// Column number (starting at 1) in which the error occurred
+        column: usize,
+        /// The offending character
+        character: char,
+    },
+    /// No field is marked as start
+    #[error("No field is marked as start")]
+    MissingStart,
+    /// More than one field is marked with the same marker
+    #[error("Marker '{0}' is used more than once")]
+    DuplicateMarker(char),
+}
+
+impl Maze {
+    /// Parse a maze from the text format that is produced by its [`Debug`](std::fmt::Debug)
+    /// implementation.
+    ///
+    /// Corners may be written as `·` or `+`, walls as `-` and `|` and passages as spaces.
+    /// Corners inside of rooms are printed as spaces as well, but only the passages of rooms are
+    /// parsed and not the rooms themselves.
+    /// The start field is marked with `S` and the goal field with `G`.
+    /// If no goal is marked, it is placed on the start field which is also how such a maze is
+    /// printed.
+    /// The outer border has to consist of corners and walls because a maze never has passages
+    /// leading outside of it, so a space in the border is rejected as an unexpected character.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·
+    /// |S  |
+    /// ·-· ·
+    /// |G  |
+    /// ·-·-·
+    /// ").unwrap();
+    ///
+    /// assert_eq!(maze.size, (2, 2));
+    /// assert_eq!(maze.goal, Coordinates::new(0, 1));
+    /// assert!(maze.get_field(&maze.start).unwrap().has_passage(&Direction::East));
+    /// ```
+    pub fn from_ascii(text: &str) -> Result<Maze, ParseError> {
+        let lines: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+        if lines.len() < 3 || lines.len() % 2 != 1 {
+            return Err(ParseError::InvalidLineCount(lines.len()));
+        }
+        let line_length = lines[0].len();
+        if line_length < 3 || line_length % 2 != 1 {
+            return Err(ParseError::InvalidFirstLineLength(line_length));
+        }
+
+        let width = (line_length / 2) as i32;
+        let height = (lines.len() / 2) as i32;
+        let mut start = None;
+        let mut goal = None;
+        let mut passages = Vec::new();
+
+        for (i_line, line) in lines.iter().enumerate() {
+            if line.len() != line_length {
+                return Err(ParseError::InvalidLineLength {
+                    line: i_line + 1,
+                    expected: line_length,
+                    found: line.len(),
+                });
+            }
+
+            let y = (i_line / 2) as i32;
+            for (i_column, character) in line.iter().copied().enumerate() {
+                let x = (i_column / 2) as i32;
+                let is_border = i_line == 0
+                    || i_line == lines.len() - 1
+                    || i_column == 0
+                    || i_column == line_length - 1;
+                match (i_line % 2, i_column % 2, character) {
+                    // corners
+                    (0, 0, '·') | (0, 0, '+') => {}
+                    // corners inside of rooms
+                    (0, 0, ' ') if !is_border => {}
+                    // horizontal walls
+                    (0, 1, '-') => {}
+                    (0, 1, ' ') if !is_border => passages.push(((x, y - 1), (x, y))),
+                    // vertical walls
+                    (1, 0, '|') => {}
+                    (1, 0, ' ') if !is_border => passages.push(((x - 1, y), (x, y))),
+                    // field contents
+                    (1, 1, ' ') => {}
+                    (1, 1, 'S') if start.is_none() => start = Some(Coordinates::new(x, y)),
+                    (1, 1, 'G') if goal.is_none() => goal = Some(Coordinates::new(x, y)),
+                    (1, 1, 'S') | (1, 1, 'G') => {
+                        return Err(ParseError::DuplicateMarker(character))
+                    }
+                    _ => {
+                        return Err(ParseError::UnexpectedCharacter {
+                            line: i_line + 1,
+                            column: i_column + 1,
+                            character,
+                        })
+                    }
+                }
+            }
+        }
+
+        let start = start.ok_or(ParseError::MissingStart)?;
+        let mut maze = Maze::new(width, height, start, goal.unwrap_or(start));
+        for (from, to) in passages {
+            maze.add_passage(from.into(), to.into());
+        }
+
+        Ok(maze)
+    }
+}
+
+impl FromStr for Maze {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Maze::from_ascii(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ellers_algorithm::EllersGenerator;
+    use crate::prelude::{Generator, Maze, ParseError};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_debug_output_round_trip() -> anyhow::Result<()> {
+        let mazes = [
+            EllersGenerator::new(Some([3; 32])).generate(7, 4)?,
+            PrimsGenerator::new(Some([3; 32])).generate(4, 7)?,
+            PrimsGenerator::new(Some([3; 32])).generate(1, 1)?,
+        ];
+
+        for maze in mazes.iter() {
+            let parsed = Maze::from_ascii(&format!("{:?}", maze))?;
+            assert_eq!(format!("{:?}", parsed), format!("{:?}", maze));
+            assert_eq!(parsed.start, maze.start);
+            assert_eq!(parsed.goal, maze.goal);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Maze::from_ascii("·-·\n|S|\n").err(),
+            Some(ParseError::InvalidLineCount(2))
+        );
+        assert_eq!(
+            Maze::from_ascii("·-·\n|S|\n·-·-·\n").err(),
+            Some(ParseError::InvalidLineLength {
+                line: 3,
+                expected: 3,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Maze::from_ascii("·-·\n|x|\n·-·\n").err(),
+            Some(ParseError::UnexpectedCharacter {
+                line: 2,
+                column: 2,
+                character: 'x'
+            })
+        );
+        assert_eq!(
+            Maze::from_ascii("·-·\n S|\n·-·\n").err(),
+            Some(ParseError::UnexpectedCharacter {
+                line: 2,
+                column: 1,
+                character: ' '
+            })
+        );
+        assert_eq!(
+            Maze::from_ascii("·-·-·\n|G  |\n·-·-·\n").err(),
+            Some(ParseError::MissingStart)
+        );
+        assert_eq!(
+            Maze::from_ascii("·-·-·\n|S S|\n·-·-·\n").err(),
+            Some(ParseError::DuplicateMarker('S'))
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::VecDeque;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while partitioning a maze via [`Maze::partition()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PartitionError {
+    /// Every zone needs at least one field so there can only be as many zones as there are
+    /// fields reachable from the start
+    #[error("Can not split {available} reachable fields into {requested} zones")]
+    InvalidZoneCount {
+        /// Number of zones which were requested
+        requested: usize,
+        /// Number of fields reachable from the start
+        available: usize,
+    },
+}
+
+/// A maze split into connected zones, see [`Maze::partition()`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Partition {
+    size: (i32, i32),
+    /// Zone of every field in row-major order
+    zones: Vec<Option<usize>>,
+    /// Field from which each zone was grown, indexed by zone
+    pub seeds: Vec<Coordinates>,
+    /// Passages which connect two different zones, stored with the field of the lower zone first
+    pub connections: Vec<(Coordinates, Coordinates)>,
+}
+
+impl Partition {
+    fn index(&self, coordinates: &Coordinates) -> Option<usize> {
+        let (width, height) = self.size;
+        if !RectangularTopology::new(width, height).contains(coordinates) {
+            return None;
+        }
+        Some(coordinates.y as usize * width as usize + coordinates.x as usize)
+    }
+
+    /// Number of zones
+    #[must_use]
+    pub fn zone_count(&self) -> usize {
+        self.seeds.len()
+    }
+
+    /// Zone to which the field at `coordinates` belongs.
+    ///
+    /// Returns `None` for coordinates outside of the maze and for fields that are not reachable
+    /// from the start, e.g. after [`Maze::sparsify()`].
+    #[must_use]
+    pub fn zone(&self, coordinates: &Coordinates) -> Option<usize> {
+        self.index(coordinates).and_then(|i| self.zones[i])
+    }
+
+    /// All fields which belong to `zone` in row-major order
+    #[must_use]
+    pub fn fields(&self, zone: usize) -> Vec<Coordinates> {
+        let width = self.size.0.max(1) as usize;
+        self.zones
+            .iter()
+            .enumerate()
+            .filter(|(_, i_zone)| **i_zone == Some(zone))
+            .map(|(i, _)| Coordinates::new((i % width) as i32, (i / width) as i32))
+            .collect()
+    }
+}
+
+impl Maze {
+    /// Split the maze into `n_zones` connected regions, e.g. for placing different biomes or
+    /// keys and locks.
+    ///
+    /// The zones are grown from seed fields which are spread out as far as possible: the first
+    /// seed is the start field and every following seed is the field which is farthest away from
+    /// all previous seeds.
+    /// Every field then belongs to the zone of the seed which it is closest to (walking through
+    /// passages), so every zone is connected.
+    /// The result is deterministic.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    ///
+    /// let maze = PrimsGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let partition = maze.partition(4).unwrap();
+    ///
+    /// assert_eq!(partition.zone(&maze.start), Some(0));
+    /// // zones of a perfect maze are only connected through a tree of passages
+    /// assert_eq!(partition.connections.len(), 3);
+    /// ```
+    pub fn partition(&self, n_zones: usize) -> Result<Partition, PartitionError> {
+        let reachable = self.breadth_first_search(self.start).len();
+        if n_zones == 0 || n_zones > reachable {
+            return Err(PartitionError::InvalidZoneCount {
+                requested: n_zones,
+                available: reachable,
+            });
+        }
+
+        let mut partition = Partition {
+            size: self.size,
+            zones: vec![None; self.topology().cell_count()],
+            seeds: Vec::with_capacity(n_zones),
+            connections: Vec::new(),
+        };
+
+        // distance of every field to the closest seed chosen so far
+        let mut distances = vec![usize::MAX; partition.zones.len()];
+        let mut seed = self.start;
+        loop {
+            partition.seeds.push(seed);
+            for (coordinates, (distance, _)) in self.breadth_first_search(seed) {
+                if let Some(i) = partition.index(&coordinates) {
+                    distances[i] = distances[i].min(distance);
+                }
+            }
+            if partition.seeds.len() == n_zones {
+                break;
+            }
+            // all reachable fields which are not yet seeds have a distance of at least 1
+            seed = self
+                .coordinates()
+                .filter_map(|c| partition.index(&c).map(|i| (c, distances[i])))
+                .filter(|(_, distance)| *distance != usize::MAX)
+                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                .map_or(self.start, |(c, _)| c);
+        }
+
+        // grow all zones at the same time so that every field ends up in the closest one
+        let mut queue = VecDeque::new();
+        for (zone, seed) in partition.seeds.iter().enumerate() {
+            if let Some(i) = partition.index(seed) {
+                partition.zones[i] = Some(zone);
+                queue.push_back(*seed);
+            }
+        }
+        while let Some(i_coords) = queue.pop_front() {
+            let zone = partition.zone(&i_coords);
+            for i_next in self.passage_neighbours(i_coords) {
+                match partition.index(&i_next) {
+                    Some(i) if partition.zones[i].is_none() => {
+                        partition.zones[i] = zone;
+                        queue.push_back(i_next);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // every passage is visited from both sides so only record it from its north-western field
+        for coordinates in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if !self.has_passage_between(coordinates, next) {
+                    continue;
+                }
+                match (partition.zone(&coordinates), partition.zone(&next)) {
+                    (Some(a), Some(b)) if a < b => partition.connections.push((coordinates, next)),
+                    (Some(a), Some(b)) if a > b => partition.connections.push((next, coordinates)),
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(partition)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Generator, PartitionError};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_zones_are_connected() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([9; 32])).generate(12, 8)?;
+        let partition = maze.partition(5)?;
+
+        assert_eq!(partition.zone_count(), 5);
+        let mut field_count = 0;
+        for zone in 0..partition.zone_count() {
+            let fields = partition.fields(zone);
+            assert!(fields.contains(&partition.seeds[zone]));
+            field_count += fields.len();
+
+            // every field of a zone can be reached from its seed without leaving the zone
+            for coordinates in fields.iter() {
+                let path = maze.find_path(partition.seeds[zone], *coordinates);
+                assert!(
+                    path.is_some_and(|path| path.iter().all(|c| partition.zone(c) == Some(zone)))
+                );
+            }
+        }
+        assert_eq!(field_count, 12 * 8);
+        assert_eq!(partition.connections.len(), 4);
+
+        assert_eq!(
+            maze.partition(0),
+            Err(PartitionError::InvalidZoneCount {
+                requested: 0,
+                available: 96
+            })
+        );
+        assert!(maze.partition(96).is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use crate::prelude::*;
+
+const BITS_PER_BLOCK: usize = 64;
+const EAST_BIT: usize = 0;
+const SOUTH_BIT: usize = 1;
+
+/// Dense storage of the passages of a rectangular maze.
+///
+/// Every passage connects two adjacent fields so it suffices to store the passages leading east
+/// and south of each field, resulting in 2 bits per field.
+/// Passages leading north or west are stored as the south or east passage of the neighbouring
+/// field.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawPassageGrid"))]
+pub(crate) struct PassageGrid {
+    width: i32,
+    height: i32,
+    blocks: Vec<u64>,
+}
+
+impl PassageGrid {
+    /// Create a grid of the given size without any passages
+    pub(crate) fn new(width: i32, height: i32) -> Self {
+        PassageGrid {
+            width,
+            height,
+            blocks: vec![0; block_count(width, height)],
+        }
+    }
+
+    /// Remove all passages and change the size of the grid while keeping the allocated storage
+    pub(crate) fn reset(&mut self, width: i32, height: i32) {
+        self.width = width;
+        self.height = height;
+        self.blocks.clear();
+        self.blocks.resize(block_count(width, height), 0);
+    }
+
+    fn is_inside(&self, coordinates: Coordinates) -> bool {
+        coordinates.x >= 0
+            && coordinates.x < self.width
+            && coordinates.y >= 0
+            && coordinates.y < self.height
+    }
+
+    /// Determine which bit stores the passage between two fields.
+    ///
+    /// Returns `None` if the fields are not adjacent or not both inside the grid.
+    fn bit_index(&self, a: Coordinates, b: Coordinates) -> Option<usize> {
+        if !self.is_inside(a) || !self.is_inside(b) {
+            return None;
+        }
+        // normalize so that the passage is stored at the north-western field of both
+        let (owner, bit) = match (b.x - a.x, b.y - a.y) {
+            (1, 0) => (a, EAST_BIT),
+            (-1, 0) => (b, EAST_BIT),
+            (0, 1) => (a, SOUTH_BIT),
+            (0, -1) => (b, SOUTH_BIT),
+            _ => return None,
+        };
+        Some((owner.y as usize * self.width as usize + owner.x as usize) * 2 + bit)
+    }
+
+    /// Whether a passage exists between `a` and `b`
+    pub(crate) fn contains(&self, a: Coordinates, b: Coordinates) -> bool {
+        match self.bit_index(a, b) {
+            Some(i) => self.blocks[i / BITS_PER_BLOCK] & (1 << (i % BITS_PER_BLOCK)) != 0,
+            None => false,
+        }
+    }
+
+    /// Add a passage between the adjacent fields `a` and `b`.
+    ///
+    /// Returns whether the passage could be added, which is not the case for fields that are
+    /// not adjacent or lie outside of the grid.
+    pub(crate) fn insert(&mut self, a: Coordinates, b: Coordinates) -> bool {
+        match self.bit_index(a, b) {
+            Some(i) => {
+                self.blocks[i / BITS_PER_BLOCK] |= 1 << (i % BITS_PER_BLOCK);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the passage between `a` and `b`.
+    ///
+    /// Returns whether `a` and `b` are adjacent fields inside of the grid, regardless of whether a
+    /// passage existed between them before.
+    pub(crate) fn remove(&mut self, a: Coordinates, b: Coordinates) -> bool {
+        match self.bit_index(a, b) {
+            Some(i) => {
+                self.blocks[i / BITS_PER_BLOCK] &= !(1 << (i % BITS_PER_BLOCK));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Iterate over all fields which are connected to `coordinates` through a passage
+    pub(crate) fn neighbours(
+        &self,
+        coordinates: Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        IntoIterator::into_iter(Direction::all())
+            .map(move |direction| coordinates.next(&direction))
+            .filter(move |next| self.contains(coordinates, *next))
+    }
+
+    /// The size of the grid in (width, height) format, which can differ from the size of the
+    /// maze if that was changed without resetting the maze
+    pub(crate) fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Number of passages stored in the grid
+    pub(crate) fn passage_count(&self) -> usize {
+        self.blocks.iter().map(|b| b.count_ones() as usize).sum()
+    }
+}
+
+/// Number of blocks which are needed to store the passages of a grid of the given size
+fn block_count(width: i32, height: i32) -> usize {
+    let bit_count = (width.max(0) as usize) * (height.max(0) as usize) * 2;
+    // rounded up without `usize::div_ceil`, which is newer than the minimum supported Rust version
+    (bit_count + BITS_PER_BLOCK - 1) / BITS_PER_BLOCK
+}
+
+/// A [`PassageGrid`] as it is deserialized, before its storage is checked to match its size
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawPassageGrid {
+    width: i32,
+    height: i32,
+    blocks: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl std::convert::TryFrom<RawPassageGrid> for PassageGrid {
+    type Error = String;
+
+    fn try_from(raw: RawPassageGrid) -> Result<Self, Self::Error> {
+        let grid = PassageGrid::new(raw.width, raw.height);
+        if raw.blocks.len() != grid.blocks.len() {
+            return Err(format!(
+                "a {}x{} grid needs {} blocks but {} were given",
+                raw.width,
+                raw.height,
+                grid.blocks.len(),
+                raw.blocks.len()
+            ));
+        }
+
+        let grid = PassageGrid {
+            blocks: raw.blocks,
+            ..grid
+        };
+        let is_set = |i: usize| grid.blocks[i / BITS_PER_BLOCK] & (1 << (i % BITS_PER_BLOCK)) != 0;
+        let (width, height) = (grid.width.max(0) as usize, grid.height.max(0) as usize);
+        if width == 0 || height == 0 {
+            // without fields there are no blocks which could have any bits set
+            return Ok(grid);
+        }
+        // passages east of the last column or south of the last row would lead outside
+        let mut outside = (0..height)
+            .map(|y| (y * width + width - 1) * 2 + EAST_BIT)
+            .chain((0..width).map(|x| ((height - 1) * width + x) * 2 + SOUTH_BIT));
+        if outside.any(is_set) {
+            return Err(format!(
+                "a passage leads outside of the {}x{} grid",
+                grid.width, grid.height
+            ));
+        }
+        if (width * height * 2..grid.blocks.len() * BITS_PER_BLOCK).any(is_set) {
+            return Err(format!(
+                "the blocks of the {}x{} grid have bits set beyond its fields",
+                grid.width, grid.height
+            ));
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PassageGrid;
+    use crate::prelude::Coordinates;
+
+    /// Sizes whose number of fields lies at or next to multiples of the 32 fields in a block,
+    /// together with the number of blocks they need
+    const SIZES: [(i32, i32, usize); 7] = [
+        (1, 1, 1),
+        (31, 1, 1),
+        (4, 8, 1),
+        (3, 11, 2),
+        (1, 63, 2),
+        (8, 8, 2),
+        (5, 13, 3),
+    ];
+
+    /// All pairs of adjacent fields of a grid in row-major order of their north-western field
+    fn adjacent_fields(width: i32, height: i32) -> Vec<(Coordinates, Coordinates)> {
+        let mut pairs = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let field = Coordinates::new(x, y);
+                if x + 1 < width {
+                    pairs.push((field, Coordinates::new(x + 1, y)));
+                }
+                if y + 1 < height {
+                    pairs.push((field, Coordinates::new(x, y + 1)));
+                }
+            }
+        }
+        pairs
+    }
+
+    #[test]
+    fn test_blocks_cover_all_fields() {
+        for &(width, height, blocks) in SIZES.iter() {
+            assert_eq!(PassageGrid::new(width, height).blocks.len(), blocks);
+        }
+
+        // the passages of the 32nd field fill the last two of the 64 bits of the first block
+        let mut grid = PassageGrid::new(33, 2);
+        assert!(grid.insert(Coordinates::new(31, 0), Coordinates::new(32, 0)));
+        assert!(grid.insert(Coordinates::new(31, 0), Coordinates::new(31, 1)));
+        assert_eq!(grid.blocks, vec![0b11 << 62, 0, 0]);
+        assert!(grid.insert(Coordinates::new(32, 0), Coordinates::new(32, 1)));
+        assert_eq!(grid.blocks, vec![0b11 << 62, 0b10, 0]);
+    }
+
+    #[test]
+    fn test_passages_are_independent_across_blocks() {
+        for &(width, height, _) in SIZES.iter() {
+            let mut grid = PassageGrid::new(width, height);
+            let pairs = adjacent_fields(width, height);
+            for (i, &(a, b)) in pairs.iter().enumerate() {
+                assert!(!grid.contains(a, b));
+                assert!(grid.insert(b, a));
+                assert!(grid.contains(a, b) && grid.contains(b, a));
+                assert_eq!(grid.passage_count(), i + 1);
+            }
+            for &(a, b) in pairs.iter().step_by(2) {
+                assert!(grid.remove(a, b));
+            }
+            for (i, &(a, b)) in pairs.iter().enumerate() {
+                assert_eq!(grid.contains(a, b), i % 2 == 1);
+            }
+            assert_eq!(grid.passage_count(), pairs.len() / 2);
+
+            // fields on the last column and row have no passages leading out of the grid
+            let corner = Coordinates::new(width - 1, height - 1);
+            assert!(!grid.insert(corner, Coordinates::new(width, height - 1)));
+            assert!(!grid.insert(corner, Coordinates::new(width - 1, height)));
+            assert_eq!(grid.passage_count(), pairs.len() / 2);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserializing_rejects_bits_outside_of_the_grid() -> anyhow::Result<()> {
+        let parse = |width: i32, height: i32, blocks: Vec<u64>| {
+            serde_json::from_value::<PassageGrid>(serde_json::json!({
+                "width": width,
+                "height": height,
+                "blocks": blocks,
+            }))
+        };
+
+        let mut grid = PassageGrid::new(3, 11);
+        for (a, b) in adjacent_fields(3, 11) {
+            grid.insert(a, b);
+        }
+        assert_eq!(parse(3, 11, grid.blocks.clone())?, grid);
+        assert!(parse(0, 5, Vec::new()).is_ok());
+
+        // east of the last field in the first row and south of the last field of the maze
+        assert!(parse(3, 11, vec![grid.blocks[0] | 1 << 4, grid.blocks[1]]).is_err());
+        assert!(parse(3, 11, vec![grid.blocks[0], grid.blocks[1] | 1 << 1]).is_err());
+        // the 33 fields only use two bits of the second block
+        assert!(parse(3, 11, vec![grid.blocks[0], grid.blocks[1] | 1 << 2]).is_err());
+        assert!(parse(3, 11, vec![grid.blocks[0], grid.blocks[1] | 1 << 63]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+use crate::prelude::*;
+
+/// Set of directions in which passages lead away from a field, stored as bitflags
+///
+/// Retrieve it via [`Maze::passages()`] to query all four directions at once.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let passages = Passages::NORTH | Passages::EAST;
+///
+/// assert!(passages.contains(&Direction::North));
+/// assert!(!passages.contains(&Direction::South));
+/// assert_eq!(passages.count(), 2);
+/// assert_eq!(passages.bits(), 0b0011);
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Passages(u8);
+
+impl Passages {
+    /// No passages at all
+    pub const NONE: Passages = Passages(0);
+    /// Passage leading north
+    pub const NORTH: Passages = Passages(1 << 0);
+    /// Passage leading east
+    pub const EAST: Passages = Passages(1 << 1);
+    /// Passage leading south
+    pub const SOUTH: Passages = Passages(1 << 2);
+    /// Passage leading west
+    pub const WEST: Passages = Passages(1 << 3);
+    /// Passages leading into all four directions
+    pub const ALL: Passages = Passages(0b1111);
+
+    /// The raw bitflags, see the constants for which bit represents which direction
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Create the set from raw bitflags, ignoring bits which do not represent a direction
+    #[must_use]
+    pub const fn from_bits_truncate(bits: u8) -> Self {
+        Passages(bits & Passages::ALL.0)
+    }
+
+    /// Whether a passage leads into `direction`
+    #[must_use]
+    pub fn contains(self, direction: &Direction) -> bool {
+        self.0 & Passages::from(*direction).0 != 0
+    }
+
+    /// Add a passage leading into `direction`
+    pub fn insert(&mut self, direction: &Direction) {
+        self.0 |= Passages::from(*direction).0;
+    }
+
+    /// Remove the passage leading into `direction`
+    pub fn remove(&mut self, direction: &Direction) {
+        self.0 &= !Passages::from(*direction).0;
+    }
+
+    /// Whether there are no passages at all
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Number of passages
+    #[must_use]
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Iterate over the directions of all passages in the order of [`Direction::all()`]
+    pub fn directions(self) -> impl Iterator<Item = Direction> {
+        Direction::iter().filter(move |direction| self.contains(direction))
+    }
+}
+
+impl From<Direction> for Passages {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::North => Passages::NORTH,
+            Direction::East => Passages::EAST,
+            Direction::South => Passages::SOUTH,
+            Direction::West => Passages::WEST,
+        }
+    }
+}
+
+impl FromIterator<Direction> for Passages {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Passages::NONE, |passages, direction| {
+                passages | Passages::from(direction)
+            })
+    }
+}
+
+impl BitOr for Passages {
+    type Output = Passages;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Passages(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Passages {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for Passages {
+    type Output = Passages;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Passages(self.0 & rhs.0)
+    }
+}
+
+impl fmt::Debug for Passages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.directions()).finish()
+    }
+}
+
+impl Maze {
+    /// All passages leading away from the field at `coordinates`.
+    ///
+    /// This queries all four directions in a single call without allocating, unlike
+    /// [`get_field()`](Maze::get_field), and is meant for tight loops like rendering.
+    /// Returns `None` for coordinates outside of the maze.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    ///
+    /// assert_eq!(maze.passages(&Coordinates::new(0, 0)), Some(Passages::SOUTH));
+    /// assert_eq!(maze.passages(&Coordinates::new(3, 0)), None);
+    /// ```
+    #[must_use]
+    pub fn passages(&self, coordinates: &Coordinates) -> Option<Passages> {
+        if !self.are_coordinates_inside(coordinates) {
+            return None;
+        }
+        Some(
+            Direction::iter()
+                .filter(|direction| {
+                    self.passages
+                        .contains(*coordinates, coordinates.next(direction))
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Direction, Generator, Passages};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_passages_match_fields() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([21; 32])).generate(7, 6)?;
+
+        for coordinates in maze.coordinates() {
+            let passages = maze.passages(&coordinates);
+            for direction in Direction::all().iter() {
+                assert_eq!(
+                    passages.map(|p| p.contains(direction)),
+                    Some(maze.has_passage(&coordinates, direction))
+                );
+            }
+            assert_eq!(
+                passages.map(|p| p.count() as usize),
+                Some(maze.passage_count(coordinates))
+            );
+            assert_eq!(passages, maze.get_field(&coordinates).map(|f| f.passages()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_flag_operations() {
+        let mut passages: Passages = [Direction::West, Direction::South]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(
+            passages.directions().collect::<Vec<_>>(),
+            vec![Direction::South, Direction::West]
+        );
+        assert_eq!(format!("{:?}", passages), "{South, West}");
+
+        passages.remove(&Direction::West);
+        passages.insert(&Direction::North);
+        assert_eq!(passages, Passages::NORTH | Passages::SOUTH);
+        assert_eq!(passages & Passages::SOUTH, Passages::SOUTH);
+        assert_eq!(Passages::from_bits_truncate(0xff), Passages::ALL);
+        assert!(Passages::default().is_empty());
+    }
+}
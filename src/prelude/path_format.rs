@@ -0,0 +1,113 @@
+use crate::prelude::*;
+
+/// How [`Maze::format_with_path()`] marks a route in the text output
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PathStyle {
+    /// Mark every field of the route with the given character
+    Fields(char),
+    /// Mark every field of the route and the passages between consecutive fields with the
+    /// given character, resulting in a continuous line
+    Trail(char),
+}
+
+impl Default for PathStyle {
+    fn default() -> Self {
+        PathStyle::Trail('*')
+    }
+}
+
+impl Maze {
+    /// Render the maze into the same text format as its [`Debug`](std::fmt::Debug) output with
+    /// `path` drawn on top of it.
+    ///
+    /// The start and goal markers are never overwritten.
+    /// Coordinates outside of the maze are ignored and walls are kept when marking the
+    /// passages of a [`PathStyle::Trail`], so a route which runs through walls is easy to spot.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let path = maze.longest_path().2;
+    ///
+    /// assert_eq!(maze.format_with_path(&path, PathStyle::default()),
+    /// "·-·-·-·
+    /// |S|   |
+    /// ·*·-· ·
+    /// |*****|
+    /// ·-·-·*·
+    /// |G****|
+    /// ·-·-·-·
+    /// ");
+    /// ```
+    #[must_use]
+    pub fn format_with_path(&self, path: &[Coordinates], style: PathStyle) -> String {
+        let mut lines: Vec<Vec<char>> = format!("{:?}", self)
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        let mut mark = |column: i32, line: i32, marker: char| {
+            let cell = lines
+                .get_mut(line as usize)
+                .and_then(|line| line.get_mut(column as usize));
+            if let Some(cell) = cell {
+                if *cell == ' ' {
+                    *cell = marker;
+                }
+            }
+        };
+
+        let marker = match style {
+            PathStyle::Fields(marker) | PathStyle::Trail(marker) => marker,
+        };
+        for (i, coordinates) in path.iter().enumerate() {
+            if !self.are_coordinates_inside(coordinates) {
+                continue;
+            }
+            mark(2 * coordinates.x + 1, 2 * coordinates.y + 1, marker);
+
+            // the character between two adjacent fields lies in the middle of both
+            if let (PathStyle::Trail(_), Some(next)) = (style, path.get(i + 1)) {
+                if coordinates.manhattan_distance(next) == 1 && self.are_coordinates_inside(next) {
+                    let between = *coordinates + *next;
+                    mark(between.x + 1, between.y + 1, marker);
+                }
+            }
+        }
+
+        lines
+            .into_iter()
+            .map(|line| line.into_iter().chain(Some('\n')).collect::<String>())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Maze, PathStyle};
+
+    #[test]
+    fn test_path_through_wall_is_visible() -> anyhow::Result<()> {
+        let maze = Maze::from_ascii("·-·-·\n|S| |\n· · ·\n|  G|\n·-·-·\n")?;
+        let path: Vec<_> = [(0, 0), (1, 0), (1, 1), (5, 5)]
+            .iter()
+            .map(|c| Coordinates::from(*c))
+            .collect();
+
+        assert_eq!(
+            maze.format_with_path(&path, PathStyle::Trail('o')),
+            "·-·-·\n|S|o|\n· ·o·\n|  G|\n·-·-·\n"
+        );
+        assert_eq!(
+            maze.format_with_path(&path, PathStyle::Fields('o')),
+            "·-·-·\n|S|o|\n· · ·\n|  G|\n·-·-·\n"
+        );
+        assert_eq!(
+            maze.format_with_path(&[], PathStyle::default()),
+            format!("{:?}", maze)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,474 @@
+use std::convert::TryFrom;
+
+use pdf_writer::types::LineCapStyle;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+
+use crate::prelude::*;
+
+/// PDF measures everything in points of which there are 72 per inch
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+/// Factor for approximating a quarter circle with a cubic bezier curve
+const BEZIER_CIRCLE: f64 = 0.552_284_75;
+/// Resource name of the font which is used for all text
+const FONT_NAME: Name<'static> = Name(b"F1");
+/// Font size of the title of a text page in points
+const TITLE_SIZE: f32 = 28.0;
+/// Font size of all other text in points
+const TEXT_SIZE: f32 = 12.0;
+
+/// Size of the pages of a PDF document
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PageSize {
+    /// ISO A4 (210mm x 297mm)
+    A4,
+    /// ISO A5 (148mm x 210mm)
+    A5,
+    /// US Letter (8.5in x 11in)
+    Letter,
+    /// Arbitrary page size in millimetres
+    Custom {
+        /// Width of the page in millimetres
+        width: f64,
+        /// Height of the page in millimetres
+        height: f64,
+    },
+}
+
+impl PageSize {
+    /// Width and height of the page in millimetres
+    #[must_use]
+    pub fn dimensions(&self) -> (f64, f64) {
+        match *self {
+            PageSize::A4 => (210.0, 297.0),
+            PageSize::A5 => (148.0, 210.0),
+            PageSize::Letter => (215.9, 279.4),
+            PageSize::Custom { width, height } => (width, height),
+        }
+    }
+}
+
+/// Options for generating PDF output
+///
+/// Multiple mazes can be placed on a single page by arranging them in a grid of `columns` and
+/// `rows`.
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = PdfOptions::new().page_size(PageSize::Letter).columns(2).rows(3);
+/// assert_eq!(options.mazes_per_page(), 6);
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PdfOptions {
+    /// Size of every page, default: [`PageSize::A4`]
+    pub page_size: PageSize,
+    /// Empty space around the border of every page in millimetres, default: 15
+    pub margin: f64,
+    /// Number of mazes placed next to each other on a page, default: 1
+    pub columns: u32,
+    /// Number of mazes placed below each other on a page, default: 1
+    pub rows: u32,
+    /// Empty space between the mazes of a page in millimetres, default: 10
+    pub spacing: f64,
+    /// Width of wall lines in points, default: 1
+    pub strokewidth: f64,
+    /// Whether to mark the start (filled circle) and goal (empty circle) fields, default: true
+    pub markers: bool,
+}
+
+impl PdfOptions {
+    /// Create a default PdfOptions object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the size of every page
+    #[must_use]
+    pub fn page_size(mut self, page_size: PageSize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Set the empty space around the border of every page
+    #[must_use]
+    pub fn margin(mut self, margin: f64) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Set the number of mazes placed next to each other on a page
+    #[must_use]
+    pub fn columns(mut self, columns: u32) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Set the number of mazes placed below each other on a page
+    #[must_use]
+    pub fn rows(mut self, rows: u32) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Set the empty space between the mazes of a page
+    #[must_use]
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set the width of wall lines
+    #[must_use]
+    pub fn strokewidth(mut self, strokewidth: f64) -> Self {
+        self.strokewidth = strokewidth;
+        self
+    }
+
+    /// Set whether the start and goal fields are marked
+    #[must_use]
+    pub fn markers(mut self, markers: bool) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// How many mazes are placed on a single page
+    #[must_use]
+    pub fn mazes_per_page(&self) -> usize {
+        self.columns.max(1) as usize * self.rows.max(1) as usize
+    }
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        PdfOptions {
+            page_size: PageSize::A4,
+            margin: 15.0,
+            columns: 1,
+            rows: 1,
+            spacing: 10.0,
+            strokewidth: 1.0,
+            markers: true,
+        }
+    }
+}
+
+/// Render multiple mazes into a single PDF document, e.g. for printing a puzzle book.
+///
+/// The mazes are laid out in a grid of `options.columns` by `options.rows` per page, filling
+/// every page row by row before starting the next one.
+/// Every maze is scaled to fit into its slot while keeping square cells.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// let mut generator = RbGenerator::new(Some([42; 32]));
+/// let mazes = vec![generator.generate(10, 10).unwrap(), generator.generate(20, 15).unwrap()];
+/// let pdf = mazes_to_pdf(&mazes, &PdfOptions::new().rows(2));
+///
+/// assert!(pdf.starts_with(b"%PDF-"));
+/// ```
+#[must_use]
+pub fn mazes_to_pdf(mazes: &[Maze], options: &PdfOptions) -> Vec<u8> {
+    // an empty document still gets a single blank page because viewers reject documents without
+    // any pages
+    let pages: Vec<PdfPage> = if mazes.is_empty() {
+        vec![PdfPage::mazes(&[])]
+    } else {
+        mazes
+            .chunks(options.mazes_per_page())
+            .map(PdfPage::mazes)
+            .collect()
+    };
+    write_pdf(&pages, options)
+}
+
+/// Content of a single page of a PDF document written by [`write_pdf()`]
+pub(crate) enum PdfPage<'a> {
+    /// Lines of text starting at a third of the page, the first one being printed as a title
+    Text(Vec<String>),
+    /// Mazes laid out in the grid of the [`PdfOptions`]
+    Mazes {
+        /// Text printed into the top margin of the page
+        heading: Option<String>,
+        mazes: &'a [Maze],
+        /// Route which is drawn into each maze, if any
+        solutions: Option<&'a [Vec<Coordinates>]>,
+    },
+}
+
+impl<'a> PdfPage<'a> {
+    /// Page of mazes without a heading or solutions
+    fn mazes(mazes: &'a [Maze]) -> Self {
+        PdfPage::Mazes {
+            heading: None,
+            mazes,
+            solutions: None,
+        }
+    }
+}
+
+/// Write `pages` into a PDF document
+pub(crate) fn write_pdf(pages: &[PdfPage], options: &PdfOptions) -> Vec<u8> {
+    let (page_width, page_height) = options.page_size.dimensions();
+    let (page_width, page_height) = (page_width * POINTS_PER_MM, page_height * POINTS_PER_MM);
+    let margin = options.margin * POINTS_PER_MM;
+    let spacing = options.spacing * POINTS_PER_MM;
+    let (columns, rows) = (options.columns.max(1), options.rows.max(1));
+    let slot_width =
+        (page_width - 2.0 * margin - spacing * f64::from(columns - 1)) / f64::from(columns);
+    let slot_height =
+        (page_height - 2.0 * margin - spacing * f64::from(rows - 1)) / f64::from(rows);
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    // every page consists of the page object itself followed by its content stream
+    let page_ids: Vec<Ref> = (0..pages.len())
+        .map(|i| Ref::new(3 + 2 * i as i32))
+        .collect();
+    // the font is only embedded into documents which contain text
+    let has_text = pages.iter().any(|page| match page {
+        PdfPage::Text(_) => true,
+        PdfPage::Mazes { heading, .. } => heading.is_some(),
+    });
+    let font_id = Ref::new(3 + 2 * pages.len() as i32);
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    if has_text {
+        pdf.type1_font(font_id)
+            .base_font(Name(b"Helvetica"))
+            .encoding_predefined(Name(b"WinAnsiEncoding"));
+    }
+
+    for (page, page_id) in pages.iter().zip(page_ids) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut content = Content::new();
+
+        match page {
+            PdfPage::Text(lines) => {
+                let mut top = page_height * 2.0 / 3.0;
+                for (i_line, line) in lines.iter().enumerate() {
+                    let size = if i_line == 0 { TITLE_SIZE } else { TEXT_SIZE };
+                    text(&mut content, line, (margin, top), size);
+                    top -= 2.0 * f64::from(size);
+                }
+            }
+            PdfPage::Mazes {
+                heading,
+                mazes,
+                solutions,
+            } => {
+                if let Some(heading) = heading {
+                    let baseline = page_height - margin / 2.0 - f64::from(TEXT_SIZE) / 3.0;
+                    text(&mut content, heading, (margin, baseline), TEXT_SIZE);
+                }
+                content
+                    .set_line_width(options.strokewidth as f32)
+                    .set_line_cap(LineCapStyle::ProjectingSquareCap);
+
+                for (i_maze, maze) in mazes.iter().enumerate() {
+                    let column = (i_maze % columns as usize) as f64;
+                    let row = (i_maze / columns as usize) as f64;
+                    let slot_left = margin + column * (slot_width + spacing);
+                    let slot_top = page_height - margin - row * (slot_height + spacing);
+                    let layout = draw_maze(
+                        &mut content,
+                        maze,
+                        (slot_left, slot_top),
+                        (slot_width, slot_height),
+                        options.markers,
+                    );
+                    if let Some(route) = solutions.and_then(|solutions| solutions.get(i_maze)) {
+                        draw_route(&mut content, &layout, route, options.strokewidth);
+                    }
+                }
+            }
+        }
+
+        {
+            let mut pdf_page = pdf.page(page_id);
+            pdf_page
+                .parent(page_tree_id)
+                .media_box(Rect::new(0.0, 0.0, page_width as f32, page_height as f32))
+                .contents(content_id);
+            if has_text {
+                pdf_page.resources().fonts().pair(FONT_NAME, font_id);
+            }
+        }
+        pdf.stream(content_id, &content.finish());
+    }
+
+    pdf.finish()
+}
+
+/// Print `line` with its baseline starting at `left_bottom`
+///
+/// The standard font only covers Latin-1, every other character is replaced with `?`.
+fn text(content: &mut Content, line: &str, left_bottom: (f64, f64), size: f32) {
+    let encoded: Vec<u8> = line
+        .chars()
+        .map(|c| {
+            u8::try_from(u32::from(c))
+                .ok()
+                .filter(|byte| !byte.is_ascii_control() && !(0x80..0xa0).contains(byte))
+                .unwrap_or(b'?')
+        })
+        .collect();
+    content
+        .begin_text()
+        .set_font(FONT_NAME, size)
+        .next_line(left_bottom.0 as f32, left_bottom.1 as f32)
+        .show(Str(&encoded))
+        .end_text();
+}
+
+/// Position and scale at which a maze is drawn onto a page
+struct MazeLayout {
+    left: f64,
+    top: f64,
+    cell_size: f64,
+}
+
+impl MazeLayout {
+    /// Position of the top left corner of the field at `(ix, iy)`
+    fn corner(&self, ix: i32, iy: i32) -> (f32, f32) {
+        // PDF places positive y upwards while the maze grows downwards
+        (
+            (self.left + f64::from(ix) * self.cell_size) as f32,
+            (self.top - f64::from(iy) * self.cell_size) as f32,
+        )
+    }
+
+    /// Position of the center of the field at `coordinates`
+    fn center(&self, coordinates: Coordinates) -> (f32, f32) {
+        let (x, y) = self.corner(coordinates.x, coordinates.y);
+        let half = (self.cell_size / 2.0) as f32;
+        (x + half, y - half)
+    }
+}
+
+/// Draw `maze` centered into the slot whose top left corner lies at `top_left` and return
+/// where it was drawn
+fn draw_maze(
+    content: &mut Content,
+    maze: &Maze,
+    top_left: (f64, f64),
+    slot_size: (f64, f64),
+    markers: bool,
+) -> MazeLayout {
+    let cell_size =
+        (slot_size.0 / f64::from(maze.size.0)).min(slot_size.1 / f64::from(maze.size.1));
+    let layout = MazeLayout {
+        left: top_left.0 + (slot_size.0 - cell_size * f64::from(maze.size.0)) / 2.0,
+        top: top_left.1 - (slot_size.1 - cell_size * f64::from(maze.size.1)) / 2.0,
+        cell_size,
+    };
+    let corner = |ix: i32, iy: i32| layout.corner(ix, iy);
+    let mut line = |from: (f32, f32), to: (f32, f32)| {
+        content.move_to(from.0, from.1).line_to(to.0, to.1);
+    };
+
+    for coordinates in maze.coordinates() {
+        let (ix, iy) = (coordinates.x, coordinates.y);
+        if !maze.has_passage(&coordinates, &Direction::North) {
+            line(corner(ix, iy), corner(ix + 1, iy));
+        }
+        if !maze.has_passage(&coordinates, &Direction::West) {
+            line(corner(ix, iy), corner(ix, iy + 1));
+        }
+    }
+    line(corner(0, maze.size.1), corner(maze.size.0, maze.size.1));
+    line(corner(maze.size.0, 0), corner(maze.size.0, maze.size.1));
+    content.stroke();
+
+    if markers {
+        let radius = (cell_size / 4.0) as f32;
+        circle(content, layout.center(maze.start), radius);
+        content.fill_nonzero();
+        if maze.goal != maze.start {
+            circle(content, layout.center(maze.goal), radius);
+            content.stroke();
+        }
+    }
+
+    layout
+}
+
+/// Draw `route` as a red line through the centers of its fields
+fn draw_route(content: &mut Content, layout: &MazeLayout, route: &[Coordinates], strokewidth: f64) {
+    let mut centers = route.iter().map(|coordinates| layout.center(*coordinates));
+    let first = match centers.next() {
+        Some(first) => first,
+        None => return,
+    };
+    content
+        .save_state()
+        .set_stroke_rgb(0.8, 0.0, 0.0)
+        .set_line_width((strokewidth * 2.0) as f32)
+        .move_to(first.0, first.1);
+    for (x, y) in centers {
+        content.line_to(x, y);
+    }
+    content.stroke().restore_state();
+}
+
+/// Add a circle path made of four bezier curves to `content`
+fn circle(content: &mut Content, (x, y): (f32, f32), radius: f32) {
+    let k = radius * BEZIER_CIRCLE as f32;
+    content
+        .move_to(x + radius, y)
+        .cubic_to(x + radius, y + k, x + k, y + radius, x, y + radius)
+        .cubic_to(x - k, y + radius, x - radius, y + k, x - radius, y)
+        .cubic_to(x - radius, y - k, x - k, y - radius, x, y - radius)
+        .cubic_to(x + k, y - radius, x + radius, y - k, x + radius, y)
+        .close_path();
+}
+
+impl Maze {
+    /// Render the maze as a single page PDF document.
+    ///
+    /// Use [`mazes_to_pdf()`] to place multiple mazes into one document.
+    #[must_use]
+    pub fn to_pdf(&self, options: &PdfOptions) -> Vec<u8> {
+        mazes_to_pdf(std::slice::from_ref(self), options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{mazes_to_pdf, Generator, PdfOptions};
+    use crate::recursive_backtracking::RbGenerator;
+
+    fn count(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|w| w == &needle)
+            .count()
+    }
+
+    #[test]
+    fn test_multiple_mazes_per_page() -> anyhow::Result<()> {
+        let mut generator = RbGenerator::new(Some([1; 32]));
+        let mazes = (0..5)
+            .map(|_| generator.generate(8, 6))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let pdf = mazes_to_pdf(&mazes, &PdfOptions::new().columns(2).rows(2));
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert_eq!(count(&pdf, b"/Type /Page\n"), 2);
+
+        let pdf = mazes[0].to_pdf(&PdfOptions::new());
+        assert_eq!(count(&pdf, b"/Type /Page\n"), 1);
+
+        let pdf = mazes_to_pdf(&[], &PdfOptions::new());
+        assert_eq!(count(&pdf, b"/Type /Page\n"), 1);
+        Ok(())
+    }
+}
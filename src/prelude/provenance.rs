@@ -0,0 +1,161 @@
+use std::fmt;
+
+use rand_chacha::ChaChaRng;
+
+use crate::builder::Algorithm;
+use crate::prelude::fingerprint::stable_hash;
+use crate::prelude::*;
+
+/// Record of how a maze was generated, see [`Maze::provenance()`]
+///
+/// Together with the size of the maze this is everything needed to generate the same maze
+/// again, e.g. for reproducing a bug report which only contains the maze itself.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Provenance {
+    /// The algorithm which carved the passages
+    pub algorithm: Algorithm,
+    /// The seed from which the maze was generated.
+    ///
+    /// This is also known for mazes generated from entropy.
+    /// It is `None` if the generator had already been used before, because its random
+    /// generator then continued where the previous maze left off.
+    pub seed: Option<Seed>,
+    /// The version of this crate which generated the maze
+    pub crate_version: String,
+    /// Stable hash of the configuration of the generator, e.g. its goal placement and the start
+    /// given to [`Generator::generate_from()`].
+    ///
+    /// Mazes generated with different options have different hashes so that a mismatch can be
+    /// detected when reproducing them.
+    /// Options which are closures, like a custom selector, are only hashed by their presence.
+    pub options_hash: u64,
+}
+
+impl Provenance {
+    /// Provenance of a maze which a generator of `algorithm` starts generating with `rng`,
+    /// which must have been created from `seed`
+    pub(crate) fn new(
+        algorithm: Algorithm,
+        seed: Seed,
+        rng: &ChaChaRng,
+        options: &impl fmt::Debug,
+    ) -> Self {
+        Provenance {
+            algorithm,
+            seed: if rng.get_word_pos() == 0 {
+                Some(seed)
+            } else {
+                None
+            },
+            crate_version: String::from(env!("CARGO_PKG_VERSION")),
+            options_hash: hash_options(options),
+        }
+    }
+}
+
+/// Hash the debug output of `options`, which is stable as long as their types are not changed
+pub(crate) fn hash_options(options: &impl fmt::Debug) -> u64 {
+    stable_hash(format!("{:?}", options).as_bytes())
+}
+
+impl Maze {
+    /// How this maze was generated, `None` if it was not created by one of the generators of
+    /// this crate, e.g. because it was parsed or transformed.
+    ///
+    /// The provenance describes the generation only, later modifications like
+    /// [`braid()`](Maze::braid) are not recorded, except for those done by a
+    /// [`MazeBuilder`](crate::builder::MazeBuilder).
+    /// It is not part of the identity of a maze, so mazes with different provenances can be
+    /// equal.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::{generate, Algorithm};
+    ///
+    /// let maze = generate(Algorithm::Prims, 10, 10, None).unwrap();
+    /// let provenance = maze.provenance().unwrap();
+    ///
+    /// let (width, height) = maze.size;
+    /// let reproduced = generate(provenance.algorithm, width, height, provenance.seed).unwrap();
+    /// assert_eq!(reproduced, maze);
+    /// ```
+    #[must_use]
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::builder::{generate, Algorithm, MazeBuilder};
+    use crate::prelude::{Coordinates, GoalPlacement, Maze};
+
+    #[test]
+    fn test_provenance_reproduces_maze() -> anyhow::Result<()> {
+        for algorithm in Algorithm::ALL.iter() {
+            let mut generator = algorithm.generator(Some([3; 32]));
+            let maze = generator.generate(8, 6)?;
+            let provenance = maze
+                .provenance()
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("maze has no provenance"))?;
+            assert_eq!(provenance.algorithm, *algorithm);
+            assert_eq!(provenance.seed, Some([3; 32]));
+            assert_eq!(provenance.crate_version, env!("CARGO_PKG_VERSION"));
+            assert_eq!(generate(*algorithm, 8, 6, provenance.seed)?, maze);
+
+            // the random generator continues so the seed no longer describes the maze
+            let second = generator.generate(8, 6)?;
+            assert_eq!(second.provenance().map(|p| p.seed), Some(None));
+
+            let moved = generator.generate_from(8, 6, Coordinates::new(2, 3))?;
+            assert_ne!(
+                moved.provenance().map(|p| p.options_hash),
+                Some(provenance.options_hash)
+            );
+        }
+
+        assert!(Maze::from_ascii("·-·\n|S|\n·-·\n")?.provenance().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_builder_provenance() -> anyhow::Result<()> {
+        let builder = MazeBuilder::new(10, 10).algorithm(Algorithm::Ellers);
+        let maze = builder.clone().braid(0.5).build()?;
+        let provenance = maze
+            .provenance()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("maze has no provenance"))?;
+        assert!(provenance.seed.is_some());
+
+        let mut reproduced = builder.clone().braid(0.5);
+        if let Some(seed) = provenance.seed {
+            reproduced = reproduced.seed(seed);
+        }
+        let reproduced = reproduced.build()?;
+        assert_eq!(reproduced, maze);
+        assert_eq!(reproduced.provenance(), Some(&provenance));
+
+        let other = builder.goal(GoalPlacement::EdgesOnly).build()?;
+        assert_ne!(
+            other.provenance().map(|p| p.options_hash),
+            Some(provenance.options_hash)
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_provenance_is_serialized() -> anyhow::Result<()> {
+        let maze = generate(Algorithm::GrowingTree, 5, 5, Some([4; 32]))?;
+        let json = serde_json::to_string(&maze)?;
+        let deserialized: Maze = serde_json::from_str(&json)?;
+        assert_eq!(deserialized.provenance(), maze.provenance());
+        assert!(maze.provenance().is_some());
+        Ok(())
+    }
+}
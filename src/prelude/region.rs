@@ -0,0 +1,200 @@
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::disjoint_sets::DisjointSets;
+use crate::prelude::*;
+
+/// Errors which can occur while re-generating a region via [`Maze::regenerate_region()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RegionError {
+    /// A corner of the region does not lie inside of the maze
+    #[error("The corner {0} of the region lies outside of the maze")]
+    OutsideMaze(Coordinates),
+    /// The region overlaps a room, whose fields would lose their open layout
+    #[error("The region overlaps the room {0:?}")]
+    OverlapsRoom(Room),
+}
+
+impl Maze {
+    /// Replace the passages inside of the rectangle spanned by the corners `a` and `b`,
+    /// including both, by passages which `generator` carves anew, e.g. for letting the user of
+    /// a map editor reroll a part of the map.
+    ///
+    /// Only passages between two fields of the region are replaced, everything outside of it
+    /// stays as it is.
+    /// The passages which lead into the region are kept as openings as long as they are needed
+    /// to reach some part of the maze.
+    /// Openings which would only form a loop through the new passages are walled off, choosing
+    /// randomly via `rng` which ones, so that a perfect maze stays perfect.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let mut rng = rand::thread_rng();
+    /// maze.regenerate_region(
+    ///     &Coordinates::new(2, 2),
+    ///     &Coordinates::new(6, 5),
+    ///     &mut PrimsGenerator::new(None),
+    ///     &mut rng,
+    /// ).unwrap();
+    ///
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub fn regenerate_region<G: Generator + ?Sized>(
+        &mut self,
+        a: &Coordinates,
+        b: &Coordinates,
+        generator: &mut G,
+        rng: &mut impl Rng,
+    ) -> Result<(), MazeError> {
+        for corner in [a, b].iter() {
+            if !self.are_coordinates_inside(corner) {
+                return Err(RegionError::OutsideMaze(**corner).into());
+            }
+        }
+        let origin = Coordinates::new(a.x.min(b.x), a.y.min(b.y));
+        let region = Room::new(
+            origin,
+            a.x.max(b.x) - origin.x + 1,
+            a.y.max(b.y) - origin.y + 1,
+        );
+        if let Some(room) = self.rooms.iter().find(|room| room.overlaps(&region)) {
+            return Err(RegionError::OverlapsRoom(*room).into());
+        }
+
+        // generate first so that the maze stays unchanged if the generator fails
+        let carved = generator.generate(region.width, region.height)?;
+        for coordinates in region.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if region.contains(&next) {
+                    self.passages.remove(coordinates, next);
+                    let local =
+                        Coordinates::new(coordinates.x - origin.x, coordinates.y - origin.y);
+                    if carved.has_passage(&local, direction) {
+                        self.add_passage(coordinates, next);
+                    }
+                }
+            }
+        }
+
+        // join all fields which are connected without crossing the border of the region, then
+        // keep only those openings which connect something new
+        let mut sets = DisjointSets::new(self.topology().cell_count());
+        let width = self.size.0 as usize;
+        let index = |c: Coordinates| c.y as usize * width + c.x as usize;
+        let mut openings = Vec::new();
+        for coordinates in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if !self.has_passage_between(coordinates, next) {
+                    continue;
+                }
+                if region.contains(&coordinates) != region.contains(&next) {
+                    openings.push((coordinates, next));
+                } else {
+                    sets.union(index(coordinates), index(next));
+                }
+            }
+        }
+        openings.shuffle(rng);
+        for (from, to) in openings {
+            if !sets.union(index(from), index(to)) {
+                self.passages.remove(from, to);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::growing_tree::GrowingTreeGenerator;
+    use crate::prelude::{Coordinates, Generator, Maze, MazeError, RegionError, Room};
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_region_is_regenerated() -> anyhow::Result<()> {
+        let original = RbGenerator::new(Some([26; 32])).generate(12, 10)?;
+        let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+        let (a, b) = (Coordinates::new(8, 7), Coordinates::new(3, 2));
+        let inside = |c: &Coordinates| c.x >= 3 && c.x <= 8 && c.y >= 2 && c.y <= 7;
+        // passages outside of the region are untouched and openings are only ever removed
+        let assert_outside_kept = |before: &Maze, after: &Maze| {
+            for coordinates in after.coordinates() {
+                for next in after.adjacent_cells(&coordinates) {
+                    let passage = after.has_passage_between(coordinates, next);
+                    let existed = before.has_passage_between(coordinates, next);
+                    match (inside(&coordinates), inside(&next)) {
+                        (false, false) => assert_eq!(passage, existed),
+                        (true, false) | (false, true) => assert!(!passage || existed),
+                        (true, true) => {}
+                    }
+                }
+            }
+        };
+
+        let mut maze = original.clone();
+        maze.regenerate_region(&a, &b, &mut PrimsGenerator::new(Some([27; 32])), &mut rng)?;
+        assert!(maze.is_perfect());
+        assert_eq!((maze.start, maze.goal), (original.start, original.goal));
+        assert_ne!(maze.passages, original.passages);
+        assert_outside_kept(&original, &maze);
+
+        // a braided maze keeps its loops outside of the region
+        let mut braided = original.clone();
+        braided.braid(1.0, &mut rand::rngs::mock::StepRng::new(0, 1));
+        let mut regenerated = braided.clone();
+        regenerated.regenerate_region(
+            &a,
+            &b,
+            &mut GrowingTreeGenerator::new(Some([28; 32])),
+            &mut rng,
+        )?;
+        assert_outside_kept(&braided, &regenerated);
+        assert_eq!(
+            regenerated.breadth_first_search(regenerated.start).len(),
+            12 * 10
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_regions() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([29; 32])).generate(6, 6)?;
+        let mut generator = RbGenerator::new(Some([30; 32]));
+        let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+        assert_eq!(
+            maze.regenerate_region(
+                &Coordinates::new(1, 1),
+                &Coordinates::new(6, 2),
+                &mut generator,
+                &mut rng
+            ),
+            Err(MazeError::Region(RegionError::OutsideMaze(
+                Coordinates::new(6, 2)
+            )))
+        );
+
+        let room = Room::new(Coordinates::new(4, 4), 2, 2);
+        maze.carve_room(room)?;
+        let unchanged = maze.clone();
+        assert_eq!(
+            maze.regenerate_region(
+                &Coordinates::new(0, 0),
+                &Coordinates::new(4, 4),
+                &mut generator,
+                &mut rng
+            ),
+            Err(MazeError::Region(RegionError::OverlapsRoom(room)))
+        );
+        assert_eq!(maze, unchanged);
+        Ok(())
+    }
+}
@@ -0,0 +1,1133 @@
+#[cfg(feature = "svg")]
+use std::fmt::Write;
+#[cfg(feature = "svg")]
+use std::io;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while strictly rendering a maze via [`Maze::render()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RenderError {
+    /// The start field does not lie inside of the maze
+    #[error("The start field {0} lies outside of the maze")]
+    StartOutside(Coordinates),
+    /// The goal field does not lie inside of the maze
+    #[error("The goal field {0} lies outside of the maze")]
+    GoalOutside(Coordinates),
+    /// A field inside of the maze could not be retrieved
+    #[error("Field {0} is missing from the maze")]
+    MissingField(Coordinates),
+}
+
+/// Mazes with at least this many fields are rendered on multiple threads
+#[cfg(feature = "rayon")]
+const PARALLEL_MIN_FIELDS: usize = 1 << 16;
+
+/// Number of rows which are rendered in parallel before they are written, which bounds the
+/// memory needed for buffering them
+#[cfg(feature = "rayon")]
+const PARALLEL_ROW_BATCH: i32 = 256;
+
+impl Maze {
+    /// Render every row via `render` and pass the results to `write` in order.
+    ///
+    /// With the `rayon` feature the rows of large mazes are rendered in parallel, one batch at a
+    /// time, while they are still written sequentially.
+    fn write_rows<E>(
+        &self,
+        render: impl Fn(i32, &mut String) + Sync,
+        mut write: impl FnMut(&str) -> Result<(), E>,
+    ) -> Result<(), E> {
+        #[cfg(feature = "rayon")]
+        {
+            let field_count = self.size.0.max(0) as usize * self.size.1.max(0) as usize;
+            if field_count >= PARALLEL_MIN_FIELDS {
+                for batch_start in (0..self.size.1).step_by(PARALLEL_ROW_BATCH as usize) {
+                    let batch_end = (batch_start + PARALLEL_ROW_BATCH).min(self.size.1);
+                    let rows: Vec<String> = (batch_start..batch_end)
+                        .into_par_iter()
+                        .map(|iy| {
+                            let mut row = String::new();
+                            render(iy, &mut row);
+                            row
+                        })
+                        .collect();
+                    for row in rows.iter() {
+                        write(row)?;
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        let mut row = String::new();
+        for iy in 0..self.size.1 {
+            row.clear();
+            render(iy, &mut row);
+            write(&row)?;
+        }
+        Ok(())
+    }
+
+    /// Render the top wall and the fields of row `iy` in the format of the
+    /// [`Debug`](std::fmt::Debug) output
+    fn write_ascii_row(&self, iy: i32, out: &mut String) {
+        // print top passage
+        for ix in 0..self.size.0 {
+            out.push(if self.is_inside_room(ix, iy) {
+                ' '
+            } else {
+                '·'
+            });
+            out.push(if self.has_passage(&(ix, iy).into(), &Direction::North) {
+                ' '
+            } else {
+                '-'
+            });
+        }
+        out.push_str("·\n");
+
+        // print left passage and room icon
+        for ix in 0..self.size.0 {
+            // an inconsistent maze should never turn logging into a panic so missing
+            // fields are rendered as "?" instead of returning an error
+            let field = self.get_field(&(ix, iy).into());
+            out.push(
+                if field.is_some_and(|field| field.has_passage(&Direction::West)) {
+                    ' '
+                } else {
+                    '|'
+                },
+            );
+
+            out.push(match field.map(|field| field.field_type) {
+                Some(FieldType::Start) => 'S',
+                Some(FieldType::Goal) => 'G',
+                Some(FieldType::Normal) => ' ',
+                None => '?',
+            });
+        }
+        out.push_str("|\n");
+    }
+}
+
+impl std::fmt::Debug for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        self.write_rows(
+            |iy, out| self.write_ascii_row(iy, out),
+            |row| f.write_str(row),
+        )?;
+
+        // print bottom line
+        if self.size.1 > 0 {
+            for _ix in 0..self.size.0 {
+                f.write_str("·-")?;
+            }
+            f.write_str("·\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Maze {
+    /// Render the maze into the same text format as its [`Debug`](std::fmt::Debug) output but
+    /// verify its consistency first.
+    ///
+    /// While the `Debug` output renders fields that can not be retrieved as `?` and silently
+    /// omits start and goal markers which lie outside of the maze, this reports such problems as
+    /// an error.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// assert_eq!(maze.render().unwrap(), format!("{:?}", maze));
+    ///
+    /// maze.goal = Coordinates::new(5, 0);
+    /// assert_eq!(maze.render(), Err(RenderError::GoalOutside(maze.goal)));
+    /// ```
+    pub fn render(&self) -> Result<String, RenderError> {
+        if !self.are_coordinates_inside(&self.start) {
+            return Err(RenderError::StartOutside(self.start));
+        }
+        if !self.are_coordinates_inside(&self.goal) {
+            return Err(RenderError::GoalOutside(self.goal));
+        }
+        if let Some(coordinates) = self.coordinates().find(|c| self.get_field(c).is_none()) {
+            return Err(RenderError::MissingField(coordinates));
+        }
+
+        Ok(format!("{:?}", self))
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Maze {
+    /// Generate an SVG version of the maze, returned as a String which you can then write to a file or use directly
+    ///
+    /// This is a convenience wrapper around [`write_svg()`](Maze::write_svg) which should be
+    /// preferred for large mazes.
+    pub fn to_svg(&self, svgoptions: SvgOptions) -> io::Result<String> {
+        let mut svg = Vec::new();
+        self.write_svg(&mut svg, &svgoptions)?;
+        String::from_utf8(svg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write an SVG version of the maze into `w`.
+    ///
+    /// The output is written in many small pieces so `w` should be buffered, e.g. by wrapping it
+    /// in a [`BufWriter`](std::io::BufWriter).
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let mut svg = Vec::new();
+    /// maze.write_svg(&mut svg, &SvgOptions::new()).unwrap();
+    ///
+    /// assert!(svg.ends_with(b"</svg>\n"));
+    /// ```
+    pub fn write_svg<W: io::Write>(&self, w: W, svgoptions: &SvgOptions) -> io::Result<()> {
+        self.write_svg_with_overlay(w, svgoptions, &SvgOverlay::default())
+    }
+
+    /// Split the SVG version of the maze into tiles of `page_width` x `page_height` pixels, e.g.
+    /// for printing a giant maze as poster on many pages.
+    ///
+    /// The tiles are returned row by row, each as a complete SVG image.
+    /// Adjacent tiles overlap by [`SvgOptions::tile_overlap`] pixels and crop marks in the
+    /// middle of the overlap show where to cut the pages before gluing them together.
+    /// The image is laid out exactly as by [`to_svg()`](Maze::to_svg), so the tile size should
+    /// be chosen together with [`SvgOptions::cell_size`].
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if a page is not larger than the overlap.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(60, 40).unwrap();
+    /// let options = SvgOptions::new().cell_size(20.0).padding(10).tile_overlap(20.0);
+    /// // the image is 1220 x 820 pixels large
+    /// let tiles = maze.to_svg_tiles(options, 595.0, 842.0).unwrap();
+    ///
+    /// assert_eq!(tiles.len(), 3);
+    /// ```
+    pub fn to_svg_tiles(
+        &self,
+        svgoptions: SvgOptions,
+        page_width: f64,
+        page_height: f64,
+    ) -> io::Result<Vec<String>> {
+        let overlap = svgoptions.tile_overlap.max(0.0);
+        if !(page_width > overlap && page_height > overlap) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "pages of {} x {} pixels are not larger than their overlap of {} pixels",
+                    page_width, page_height, overlap
+                ),
+            ));
+        }
+
+        // the content is the same for all tiles, only the visible part differs
+        let mut content = Vec::new();
+        self.write_svg_content(&mut content, &svgoptions, &SvgOverlay::default())?;
+        let (width, height) = self.svg_size(&svgoptions);
+        let padding = f64::from(svgoptions.padding);
+        let tile_count = |length: f64, page: f64| {
+            (((length - overlap) / (page - overlap)).ceil() as usize).max(1)
+        };
+        let columns = tile_count(width + 2.0 * padding, page_width);
+        let rows = tile_count(height + 2.0 * padding, page_height);
+
+        let mut tiles = Vec::with_capacity(columns * rows);
+        for row in 0..rows {
+            for column in 0..columns {
+                let x = -padding + column as f64 * (page_width - overlap);
+                let y = -padding + row as f64 * (page_height - overlap);
+                let mut tile = Vec::new();
+                self.write_svg_header(&mut tile, &svgoptions, (page_width, page_height), (x, y))?;
+                tile.extend_from_slice(&content);
+
+                // an L at every corner of the area between the middles of the overlaps
+                let (left, top) = (x + overlap / 2.0, y + overlap / 2.0);
+                let (right, bottom) = (
+                    x + page_width - overlap / 2.0,
+                    y + page_height - overlap / 2.0,
+                );
+                let mark = (overlap / 2.0).max(5.0);
+                let mut d = String::new();
+                for (cx, cy, dx, dy) in [
+                    (left, top, -mark, -mark),
+                    (right, top, mark, -mark),
+                    (left, bottom, -mark, mark),
+                    (right, bottom, mark, mark),
+                ]
+                .iter()
+                {
+                    let _ = write!(d, "M{},{}V{}M{},{}H{}", cx, cy, cy + dy, cx, cy, cx + dx);
+                }
+                let crop_style = if svgoptions.cssclasses {
+                    String::from("class=\"maze-crop-mark\"")
+                } else {
+                    String::from("stroke=\"#000000\" stroke-width=\"0.5\"")
+                };
+                let footer = format!(
+                    "<path d=\"{}\" fill=\"none\" {} />\n</svg>\n",
+                    d, crop_style
+                );
+                tile.extend_from_slice(footer.as_bytes());
+                tiles.push(
+                    String::from_utf8(tile)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+                );
+            }
+        }
+        Ok(tiles)
+    }
+
+    /// Generate an SVG image which animates how the maze was generated, e.g. for documentation
+    /// or teaching.
+    ///
+    /// `events` are the passages in the order in which they were carved, as recorded by
+    /// [`Maze::record_carving()`] during [`Generator::generate_into()`] or returned by
+    /// [`RbGenerator::carve()`](crate::recursive_backtracking::RbGenerator::carve).
+    /// The image starts with a wall between every pair of fields of an event and removes one
+    /// wall after another via SMIL animations, every [`SvgOptions::animation_step`] seconds.
+    /// Passages of the maze which are not part of `events` are open from the beginning and
+    /// events which do not connect adjacent fields are ignored.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::ellers_algorithm::EllersGenerator;
+    ///
+    /// let mut generator = EllersGenerator::new(Some([42; 32]));
+    /// let mut maze = generator.generate(5, 5).unwrap();
+    /// maze.record_carving();
+    /// generator.generate_into(&mut maze).unwrap();
+    ///
+    /// let events = maze.take_carving_record();
+    /// let svg = maze.to_animated_svg(&events, SvgOptions::new()).unwrap();
+    ///
+    /// assert_eq!(svg.matches("<set ").count(), 24);
+    /// ```
+    pub fn to_animated_svg(
+        &self,
+        events: &[(Coordinates, Coordinates)],
+        svgoptions: SvgOptions,
+    ) -> io::Result<String> {
+        let overlay = SvgOverlay {
+            carved: events,
+            ..SvgOverlay::default()
+        };
+
+        let mut svg = Vec::new();
+        self.write_svg_with_overlay(&mut svg, &svgoptions, &overlay)?;
+        String::from_utf8(svg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write an SVG version of the maze into `w` with additional cell fills drawn below the
+    /// walls and a path drawn above them
+    pub(crate) fn write_svg_with_overlay<W: io::Write>(
+        &self,
+        mut w: W,
+        svgoptions: &SvgOptions,
+        overlay: &SvgOverlay<'_>,
+    ) -> io::Result<()> {
+        let padding = f64::from(svgoptions.padding);
+        let (width, height) = self.svg_size(svgoptions);
+        self.write_svg_header(
+            &mut w,
+            svgoptions,
+            (width + 2.0 * padding, height + 2.0 * padding),
+            (-padding, -padding),
+        )?;
+        self.write_svg_content(&mut w, svgoptions, overlay)?;
+        writeln!(w, "</svg>")
+    }
+
+    /// Edge length of a single cell in SVG output
+    ///
+    /// It is calculated in floating point so that neither the cells nor the image get distorted
+    /// by rounding for mazes with extreme aspect ratios.
+    fn svg_cell_size(&self, svgoptions: &SvgOptions) -> f64 {
+        match (svgoptions.cell_size, svgoptions.height) {
+            (Some(cell_size), _) => cell_size,
+            (None, Some(height)) => f64::from(height) / f64::from(self.size.1),
+            (None, None) => {
+                f64::from((2 + self.size.1) * svgoptions.padding) / f64::from(self.size.1)
+            }
+        }
+    }
+
+    /// Size of the maze in SVG output as `(width, height)`, excluding the padding
+    fn svg_size(&self, svgoptions: &SvgOptions) -> (f64, f64) {
+        let cell_size = self.svg_cell_size(svgoptions);
+        (
+            cell_size * f64::from(self.size.0),
+            cell_size * f64::from(self.size.1),
+        )
+    }
+
+    /// Open an SVG image of `size` pixels which shows the area starting at `origin` and write
+    /// the styles of `svgoptions`
+    fn write_svg_header<W: io::Write>(
+        &self,
+        mut w: W,
+        svgoptions: &SvgOptions,
+        (width, height): (f64, f64),
+        (x, y): (f64, f64),
+    ) -> io::Result<()> {
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+        writeln!(w, "<svg xmlns=\"http://www.w3.org/2000/svg\"")?;
+        writeln!(w, "    xmlns:xlink=\"http://www.w3.org/1999/xlink\"")?;
+        writeln!(
+            w,
+            "    width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+            width, height, x, y, width, height
+        )?;
+
+        if !svgoptions.cssclasses {
+            writeln!(w, "<defs>\n<style type=\"text/css\"><![CDATA[")?;
+            writeln!(w, "line {{")?;
+            writeln!(
+                w,
+                "    stroke: {};\n    stroke-linecap: square;",
+                svgoptions.strokecol
+            )?;
+            writeln!(w, "    stroke-width: {};\n}}", svgoptions.strokewidth)?;
+            writeln!(w, "]]></style>\n</defs>")?;
+        }
+        Ok(())
+    }
+
+    /// Write all elements of the SVG image, i.e. everything between the header and the closing
+    /// tag
+    fn write_svg_content<W: io::Write>(
+        &self,
+        mut w: W,
+        svgoptions: &SvgOptions,
+        overlay: &SvgOverlay<'_>,
+    ) -> io::Result<()> {
+        // Get the options for convenience
+        let padding = svgoptions.padding; // Pad the maze all around by this amount.
+        let markersize = svgoptions.markersize; // Size of the Start and Goal markers
+
+        let cell_size = self.svg_cell_size(svgoptions);
+        let (width, height) = self.svg_size(svgoptions);
+        // Position of a grid corner in image/svg coordinates
+        let corner = |ix: i32, iy: i32| (f64::from(ix) * cell_size, f64::from(iy) * cell_size);
+        let padding = f64::from(padding);
+        // Either a class attribute or the given inline presentation attributes
+        let style = |class: &str, inline: String| {
+            if svgoptions.cssclasses {
+                format!("class=\"{}\"", escape_attribute(class))
+            } else {
+                inline
+            }
+        };
+
+        // print background and cell fills
+        let mut write_rect = |(x, y): (f64, f64), (width, height): (f64, f64), attributes| {
+            writeln!(
+                w,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {} />",
+                x, y, width, height, attributes
+            )
+        };
+        if let Some(background) = &svgoptions.background {
+            write_rect(
+                (-padding, -padding),
+                (width + 2.0 * padding, height + 2.0 * padding),
+                style(
+                    "maze-background",
+                    format!("fill=\"{}\"", escape_attribute(background)),
+                ),
+            )?;
+        }
+        if let Some(cellfill) = &svgoptions.cellfill {
+            write_rect(
+                (0.0, 0.0),
+                (width, height),
+                style(
+                    "maze-cell",
+                    format!("fill=\"{}\"", escape_attribute(cellfill)),
+                ),
+            )?;
+        }
+        if !svgoptions.bandfills.is_empty() {
+            let bands = self.distance_bands(self.start, svgoptions.bandwidth);
+            let colours = svgoptions.bandfills.iter().enumerate().cycle();
+            for (fields, (i, bandfill)) in bands.iter().zip(colours) {
+                let attributes = style(
+                    &format!("maze-cell maze-band-{}", i),
+                    format!("fill=\"{}\"", escape_attribute(bandfill)),
+                );
+                for coordinates in fields {
+                    write_rect(
+                        corner(coordinates.x, coordinates.y),
+                        (cell_size, cell_size),
+                        attributes.clone(),
+                    )?;
+                }
+            }
+        }
+        for (coordinates, class, fill) in overlay.fills.iter().filter(|_| !overlay.animated) {
+            write_rect(
+                corner(coordinates.x, coordinates.y),
+                (cell_size, cell_size),
+                style(class, format!("fill=\"{}\"", escape_attribute(fill))),
+            )?;
+        }
+        if let Some(roomfill) = &svgoptions.roomfill {
+            for room in self.rooms.iter() {
+                write_rect(
+                    corner(room.origin.x, room.origin.y),
+                    (
+                        cell_size * f64::from(room.width),
+                        cell_size * f64::from(room.height),
+                    ),
+                    style(
+                        "maze-cell maze-room",
+                        format!("fill=\"{}\"", escape_attribute(roomfill)),
+                    ),
+                )?;
+            }
+        }
+        let special_fills = [
+            (self.start, &svgoptions.startfill, "maze-cell maze-start"),
+            (self.goal, &svgoptions.goalfill, "maze-cell maze-goal"),
+        ];
+        for (coordinates, fill, class) in special_fills.iter() {
+            if let Some(fill) = fill {
+                write_rect(
+                    corner(coordinates.x, coordinates.y),
+                    (cell_size, cell_size),
+                    style(class, format!("fill=\"{}\"", escape_attribute(fill))),
+                )?;
+            }
+        }
+
+        // print animated fills above all others so that they stay visible while they appear one
+        // after another
+        if overlay.animated {
+            for (i, (coordinates, class, fill)) in overlay.fills.iter().enumerate() {
+                let (x, y) = corner(coordinates.x, coordinates.y);
+                writeln!(
+                    w,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" visibility=\"hidden\" {}>{}</rect>",
+                    x,
+                    y,
+                    cell_size,
+                    cell_size,
+                    style(class, format!("fill=\"{}\"", escape_attribute(fill))),
+                    animation_set("visible", i + 1, svgoptions)
+                )?;
+            }
+        }
+
+        // print the debug grid below the walls so that it only shows up where there is a passage
+        if svgoptions.show_grid {
+            let mut d = String::new();
+            for ix in 1..self.size.0 {
+                let (x, _) = corner(ix, 0);
+                let _ = write!(d, "M{},0V{}", x, height);
+            }
+            for iy in 1..self.size.1 {
+                let (_, y) = corner(0, iy);
+                let _ = write!(d, "M0,{}H{}", y, width);
+            }
+            if !d.is_empty() {
+                writeln!(
+                    w,
+                    "<path d=\"{}\" fill=\"none\" {} />",
+                    d,
+                    style(
+                        "maze-grid",
+                        format!(
+                            "stroke=\"#cccccc\" stroke-width=\"{}\"",
+                            (cell_size / 40.0).max(0.5)
+                        )
+                    )
+                )?;
+            }
+        }
+
+        // print walls, every row into its own buffer so that large mazes can be rendered in
+        // parallel
+        let wall_style = style("maze-wall", String::new());
+        let line = |out: &mut String, (x1, y1): (f64, f64), (x2, y2): (f64, f64)| {
+            // writing into a string never fails
+            let _ = writeln!(
+                out,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}{}/>",
+                x1,
+                y1,
+                x2,
+                y2,
+                if wall_style.is_empty() { "" } else { " " },
+                wall_style
+            );
+        };
+        self.write_rows(
+            |iy, out| {
+                // print top passage
+                for ix in 0..self.size.0 {
+                    if !self.has_passage(&(ix, iy).into(), &Direction::North) {
+                        line(out, corner(ix, iy), corner(ix + 1, iy));
+                    }
+                }
+
+                // print left passage
+                for ix in 0..self.size.0 {
+                    if !self.has_passage(&(ix, iy).into(), &Direction::West) {
+                        line(out, corner(ix, iy), corner(ix, iy + 1));
+                    }
+                }
+            },
+            |row| w.write_all(row.as_bytes()),
+        )?;
+
+        // print bottom and right border lines
+        let mut borders = String::new();
+        line(
+            &mut borders,
+            corner(0, self.size.1),
+            corner(self.size.0, self.size.1),
+        );
+        line(
+            &mut borders,
+            corner(self.size.0, 0),
+            corner(self.size.0, self.size.1),
+        );
+        w.write_all(borders.as_bytes())?;
+
+        // print the carved walls which disappear one after another
+        let mut step = 0;
+        for (from, to) in overlay.carved.iter() {
+            let direction = match Direction::iter().find(|direction| from.next(direction) == *to) {
+                Some(direction) if self.has_passage(from, &direction) => direction,
+                _ => continue,
+            };
+            step += 1;
+            let (ix, iy) = match direction {
+                Direction::North | Direction::West => (from.x, from.y),
+                Direction::East => (from.x + 1, from.y),
+                Direction::South => (from.x, from.y + 1),
+            };
+            let ((x1, y1), (x2, y2)) = match direction {
+                Direction::North | Direction::South => (corner(ix, iy), corner(ix + 1, iy)),
+                Direction::East | Direction::West => (corner(ix, iy), corner(ix, iy + 1)),
+            };
+            writeln!(
+                w,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}{}>{}</line>",
+                x1,
+                y1,
+                x2,
+                y2,
+                if wall_style.is_empty() { "" } else { " " },
+                wall_style,
+                animation_set("hidden", step, svgoptions)
+            )?;
+        }
+
+        // print the debug labels into the upper left corner of every field
+        if svgoptions.show_coordinates {
+            let font_size = cell_size * 0.25;
+            let attributes = style(
+                "maze-coordinates",
+                format!("font-size=\"{}\" fill=\"#999999\"", font_size),
+            );
+            for coordinates in self.coordinates() {
+                let (x, y) = corner(coordinates.x, coordinates.y);
+                writeln!(
+                    w,
+                    "<text x=\"{}\" y=\"{}\" dominant-baseline=\"hanging\" {}>{},{}</text>",
+                    x + font_size / 2.0,
+                    y + font_size / 2.0,
+                    attributes,
+                    coordinates.x,
+                    coordinates.y
+                )?;
+            }
+        }
+
+        // print the path through the centers of its fields
+        if !overlay.path.is_empty() {
+            let points: Vec<String> = overlay
+                .path
+                .iter()
+                .map(|coordinates| {
+                    let (x, y) = corner(coordinates.x, coordinates.y);
+                    format!("{},{}", x + cell_size / 2.0, y + cell_size / 2.0)
+                })
+                .collect();
+            let attributes = style(
+                "maze-path",
+                format!(
+                    "stroke=\"{}\" stroke-width=\"{}\"",
+                    escape_attribute(&svgoptions.startcol),
+                    svgoptions.markersize
+                ),
+            );
+            if overlay.animated {
+                // the path appears once all fills are visible
+                writeln!(
+                    w,
+                    "<polyline points=\"{}\" fill=\"none\" visibility=\"hidden\" {}>{}</polyline>",
+                    points.join(" "),
+                    attributes,
+                    animation_set("visible", overlay.fills.len() + 1, svgoptions)
+                )?;
+            } else {
+                writeln!(
+                    w,
+                    "<polyline points=\"{}\" fill=\"none\" {} />",
+                    points.join(" "),
+                    attributes
+                )?;
+            }
+        }
+
+        // print start and goal markers, the goal is hidden if it lies on the start field
+        let mut markers = vec![(
+            self.start,
+            svgoptions.startmarker,
+            &svgoptions.startcol,
+            String::from("maze-marker maze-start"),
+            "S",
+        )];
+        if self.goal != self.start {
+            markers.push((
+                self.goal,
+                svgoptions.goalmarker,
+                &svgoptions.goalcol,
+                String::from("maze-marker maze-goal"),
+                "G",
+            ));
+        }
+        for (coordinates, marker) in svgoptions.markers.iter() {
+            if self.are_coordinates_inside(coordinates) {
+                let class = match &marker.class {
+                    Some(class) => format!("maze-marker {}", class),
+                    None => String::from("maze-marker"),
+                };
+                markers.push((
+                    *coordinates,
+                    marker.shape,
+                    &marker.colour,
+                    class,
+                    &marker.letter,
+                ));
+            }
+        }
+        for (coordinates, shape, markercol, class, letter) in markers {
+            let (x, y) = corner(coordinates.x, coordinates.y);
+            let (cx, cy) = (x + cell_size / 2.0, y + cell_size / 2.0);
+            let markercol = escape_attribute(markercol);
+            let colours = format!(
+                "stroke=\"{}\" stroke-width=\"{}\" fill=\"{}\"",
+                markercol,
+                markersize + 1,
+                markercol
+            );
+            match shape {
+                MarkerShape::Circle => writeln!(
+                    w,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {} />",
+                    cx,
+                    cy,
+                    markersize,
+                    style(&class, colours)
+                )?,
+                MarkerShape::Square => writeln!(
+                    w,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {} />",
+                    cx - f64::from(markersize),
+                    cy - f64::from(markersize),
+                    2 * markersize,
+                    2 * markersize,
+                    style(&class, colours)
+                )?,
+                MarkerShape::Letter => writeln!(
+                    w,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+                    cx,
+                    cy,
+                    cell_size * 0.6,
+                    style(&class, format!("fill=\"{}\"", markercol)),
+                    escape_text(letter)
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Additional content of an SVG image which is not part of the maze itself
+#[cfg(feature = "svg")]
+#[derive(Default)]
+pub(crate) struct SvgOverlay<'a> {
+    /// Fields which are filled with a colour, together with the class and the colour
+    pub(crate) fills: Vec<(Coordinates, &'static str, String)>,
+    /// Fields through which a line is drawn in order
+    pub(crate) path: &'a [Coordinates],
+    /// Passages whose walls are drawn and then removed one after another by an animation
+    pub(crate) carved: &'a [(Coordinates, Coordinates)],
+    /// Whether the fills appear one after another and the path after them
+    pub(crate) animated: bool,
+}
+
+/// An SVG animation element which sets the visibility of its parent element to `visibility` at
+/// the given step of an animation
+#[cfg(feature = "svg")]
+fn animation_set(visibility: &str, step: usize, svgoptions: &SvgOptions) -> String {
+    // rounded to milliseconds so that the times do not show floating point noise
+    let time = (step as f64 * svgoptions.animation_step * 1000.0).round() / 1000.0;
+    format!(
+        "<set attributeName=\"visibility\" to=\"{}\" begin=\"{}s\" fill=\"freeze\" />",
+        visibility, time
+    )
+}
+
+/// Escape the characters which may not appear inside of an XML text node
+#[cfg(feature = "svg")]
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape the characters which may not appear inside of a quoted XML attribute value
+#[cfg(feature = "svg")]
+fn escape_attribute(text: &str) -> String {
+    escape_text(text).replace('"', "&quot;")
+}
+
+#[cfg(all(test, feature = "svg"))]
+mod test {
+    use std::io;
+
+    use crate::ellers_algorithm::EllersGenerator;
+    use crate::prelude::{
+        Coordinates, Direction, Generator, MarkerShape, MarkerStyle, Maze, Room, SvgOptions,
+    };
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    /// Writer which fails once more than `capacity` bytes are written into it
+    struct LimitedWriter {
+        capacity: usize,
+    }
+
+    impl io::Write for LimitedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if buf.len() > self.capacity {
+                return Err(io::Error::new(io::ErrorKind::WriteZero, "writer is full"));
+            }
+            self.capacity -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_svg_propagates_errors() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(6, 5)?;
+        let svg = maze.to_svg(SvgOptions::new())?;
+        assert!(svg.starts_with("<?xml"));
+
+        let writer = LimitedWriter { capacity: 100 };
+        let error = maze.write_svg(writer, &SvgOptions::new()).err();
+        assert_eq!(error.map(|e| e.kind()), Some(io::ErrorKind::WriteZero));
+        Ok(())
+    }
+
+    /// Extract the numeric value of the first occurrence of `attribute` in `svg`
+    fn svg_attribute(svg: &str, attribute: &str) -> Option<f64> {
+        let start = svg.find(&format!(" {}=\"", attribute))? + attribute.len() + 3;
+        let end = start + svg[start..].find('"')?;
+        svg[start..end].parse().ok()
+    }
+
+    #[test]
+    fn test_svg_extreme_aspect_ratios() -> anyhow::Result<()> {
+        let options = SvgOptions::new().padding(0).height(100);
+        for &(width, height) in [(1, 200), (200, 1), (7, 3), (3, 7)].iter() {
+            let maze = PrimsGenerator::new(Some([5; 32])).generate(width, height)?;
+            let svg = maze.to_svg(options.clone())?;
+
+            let svg_width = svg_attribute(&svg, "width");
+            let svg_height = svg_attribute(&svg, "height");
+            assert_eq!(svg_height, Some(100.0));
+            let expected_width = 100.0 * f64::from(width) / f64::from(height);
+            assert!(svg_width.is_some_and(|w| (w - expected_width).abs() < 1e-9));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_cell_size() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(4, 2)?;
+        let svg = maze.to_svg(SvgOptions::new().padding(1).height(1000).cell_size(2.5))?;
+
+        assert_eq!(svg_attribute(&svg, "width"), Some(12.0));
+        assert_eq!(svg_attribute(&svg, "height"), Some(7.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_fills_and_markers() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(4, 3)?;
+        let options = SvgOptions::new()
+            .background("white")
+            .cellfill("#eeeeee")
+            .startfill("pink")
+            .startmarker(MarkerShape::Square)
+            .goalmarker(MarkerShape::Letter);
+        let svg = maze.to_svg(options.clone())?;
+
+        assert!(svg.contains("fill=\"white\""));
+        assert!(svg.contains("fill=\"#eeeeee\""));
+        assert!(svg.contains("fill=\"pink\""));
+        assert!(!svg.contains("<circle"));
+        assert!(svg.contains(">G</text>"));
+
+        let svg = maze.to_svg(options.cssclasses(true))?;
+        assert!(!svg.contains("<style"));
+        assert!(!svg.contains("fill="));
+        assert!(svg.contains("class=\"maze-background\""));
+        assert!(svg.contains("class=\"maze-wall\""));
+        assert!(svg.contains("class=\"maze-marker maze-start\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_highlights_rooms() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([4; 32])).generate(10, 8)?;
+        maze.carve_room(Room::new(Coordinates::new(3, 2), 4, 3))?;
+
+        let svg = maze.to_svg(SvgOptions::new().roomfill("grey").cssclasses(true))?;
+        assert_eq!(svg.matches("maze-room").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_fills_distance_bands() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(6, 6)?;
+        let bands = maze.distance_bands(maze.start, 2);
+        assert!(bands.len() > 2);
+
+        let svg = maze.to_svg(
+            SvgOptions::new()
+                .bandfills(vec!["red", "blue"])
+                .bandwidth(2),
+        )?;
+        let even: usize = bands.iter().step_by(2).map(Vec::len).sum();
+        // the start marker is red as well
+        assert_eq!(svg.matches("fill=\"red\"").count(), even + 1);
+        assert_eq!(svg.matches("fill=\"blue\"").count(), 36 - even + 1);
+        assert_eq!(svg.matches("<rect").count(), 36);
+
+        let classes = SvgOptions::new().bandfills(vec!["red"]).cssclasses(true);
+        let svg = maze.to_svg(classes)?;
+        assert_eq!(svg.matches("maze-band-0").count(), 36);
+        assert!(!svg.contains("red"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_debug_overlays() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(4, 3)?;
+        let plain = maze.to_svg(SvgOptions::new())?;
+        assert!(!plain.contains("<text"));
+        assert!(!plain.contains("<path"));
+
+        let options = SvgOptions::new()
+            .padding(0)
+            .cell_size(10.0)
+            .show_coordinates(true)
+            .show_grid(true);
+        let svg = maze.to_svg(options.clone())?;
+        assert_eq!(svg.matches("<text").count(), 12);
+        assert!(svg.contains(">0,0</text>"));
+        assert!(svg.contains(">3,2</text>"));
+        assert!(svg.contains("<path d=\"M10,0V30M20,0V30M30,0V30M0,10H40M0,20H40\""));
+        // the grid lies below the walls
+        assert!(svg.find("<path") < svg.find("<line"));
+
+        let svg = maze.to_svg(options.cssclasses(true))?;
+        assert_eq!(svg.matches("class=\"maze-coordinates\"").count(), 12);
+        assert_eq!(svg.matches("class=\"maze-grid\"").count(), 1);
+
+        // a single field has no inner grid lines
+        let single = PrimsGenerator::new(Some([5; 32])).generate(1, 1)?;
+        assert!(!single
+            .to_svg(SvgOptions::new().show_grid(true))?
+            .contains("<path"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_tiles() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(10, 4)?;
+        let options = SvgOptions::new()
+            .padding(5)
+            .cell_size(10.0)
+            .tile_overlap(10.0);
+        // the image is 110 x 50 pixels large
+        let tiles = maze.to_svg_tiles(options.clone(), 40.0, 30.0)?;
+        assert_eq!(tiles.len(), 4 * 2);
+
+        let walls = maze.to_svg(options.clone())?.matches("<line").count();
+        for (i, tile) in tiles.iter().enumerate() {
+            assert!(tile.ends_with("</svg>\n"));
+            assert_eq!(svg_attribute(tile, "width"), Some(40.0));
+            assert_eq!(tile.matches("<line").count(), walls);
+            let (column, row) = (i % 4, i / 4);
+            let view_box = format!(
+                "viewBox=\"{} {} 40 30\"",
+                -5 + 30 * column as i32,
+                -5 + 20 * row as i32
+            );
+            assert!(tile.contains(&view_box), "{}", tile);
+        }
+        assert!(!tiles[0].contains("maze-crop-mark"));
+        assert_eq!(
+            maze.to_svg_tiles(options.clone().cssclasses(true), 40.0, 30.0)?[0]
+                .matches("class=\"maze-crop-mark\"")
+                .count(),
+            1
+        );
+
+        // a page which fits the whole image yields a single tile
+        assert_eq!(maze.to_svg_tiles(options.clone(), 200.0, 200.0)?.len(), 1);
+        let error = maze.to_svg_tiles(options, 10.0, 30.0).err();
+        assert_eq!(error.map(|e| e.kind()), Some(io::ErrorKind::InvalidInput));
+        Ok(())
+    }
+
+    #[test]
+    fn test_animated_svg() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([5; 32])).generate(3, 2)?;
+        let svg = maze.to_svg(SvgOptions::new())?;
+        assert!(!svg.contains("<set "));
+        let walls = svg.matches("<line").count();
+
+        let mut events: Vec<(Coordinates, Coordinates)> = Vec::new();
+        for coordinates in maze.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                if maze.has_passage(&coordinates, direction) {
+                    events.push((coordinates.next(direction), coordinates));
+                }
+            }
+        }
+        // events which are no passage of the maze are ignored
+        events.push((Coordinates::new(0, 0), Coordinates::new(2, 0)));
+        assert_eq!(events.len(), 6);
+
+        let options = SvgOptions::new().padding(0).cell_size(10.0);
+        let svg = maze.to_animated_svg(&events, options.animation_step(0.5))?;
+        assert_eq!(svg.matches("<line").count(), walls + 5);
+        assert_eq!(svg.matches("<set ").count(), 5);
+        assert!(svg.contains("begin=\"0.5s\""));
+        assert!(svg.contains("begin=\"2.5s\""));
+        assert!(!svg.contains("begin=\"3s\""));
+        // the wall of the first event, which lies west of (1, 0)
+        let first = events[0];
+        assert_eq!(first, (Coordinates::new(1, 0), Coordinates::new(0, 0)));
+        assert!(svg.contains("<line x1=\"10\" y1=\"0\" x2=\"10\" y2=\"10\"><set "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_custom_markers() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([6; 32])).generate(4, 4)?;
+        let key = MarkerStyle::new()
+            .shape(MarkerShape::Letter)
+            .letter("<K>")
+            .colour("gold")
+            .class("key");
+        let options = SvgOptions::new()
+            .marker(Coordinates::new(1, 2), key)
+            .marker(Coordinates::new(3, 3), MarkerStyle::new())
+            .marker(Coordinates::new(4, 0), MarkerStyle::new().colour("purple"));
+
+        let svg = maze.to_svg(options.clone())?;
+        assert!(svg.contains(">&lt;K&gt;</text>"));
+        assert!(svg.contains("fill=\"gold\""));
+        assert_eq!(svg.matches("fill=\"green\"").count(), 1);
+        assert!(!svg.contains("purple"));
+
+        let svg = maze.to_svg(options.cssclasses(true))?;
+        assert_eq!(svg.matches("class=\"maze-marker key\"").count(), 1);
+        assert_eq!(svg.matches("class=\"maze-marker\"").count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_svg_escapes_attributes() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([6; 32])).generate(4, 4)?;
+        let options = SvgOptions::new()
+            .background("white\" onload=\"alert(1)")
+            .cellfill("<cell>")
+            .bandfills(vec!["a&b"])
+            .startcol("\"start")
+            .goalcol("\"goal")
+            .marker(
+                Coordinates::new(1, 2),
+                MarkerStyle::new().colour("\"key").class("key\" id=\"x"),
+            );
+
+        let svg = maze.to_svg(options.clone())?;
+        assert!(!svg.contains("\" onload"));
+        assert!(svg.contains("fill=\"white&quot; onload=&quot;alert(1)\""));
+        assert!(svg.contains("fill=\"&lt;cell&gt;\""));
+        assert!(svg.contains("fill=\"a&amp;b\""));
+        assert!(svg.contains("fill=\"&quot;start\""));
+        assert!(svg.contains("fill=\"&quot;goal\""));
+        assert!(svg.contains("fill=\"&quot;key\""));
+
+        let svg = maze.to_svg(options.cssclasses(true))?;
+        assert!(svg.contains("class=\"maze-marker key&quot; id=&quot;x\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_mazes_render_every_row() -> anyhow::Result<()> {
+        // large enough to be rendered in parallel with the rayon feature
+        let maze = EllersGenerator::new(Some([22; 32])).generate(300, 240)?;
+        assert_eq!(Maze::from_ascii(&format!("{:?}", maze))?, maze);
+
+        let inner_walls: usize = maze
+            .coordinates()
+            .map(|c| {
+                [Direction::North, Direction::West]
+                    .iter()
+                    .filter(|d| !maze.has_passage(&c, d))
+                    .count()
+            })
+            .sum();
+        let svg = maze.to_svg(SvgOptions::new())?;
+        assert_eq!(svg.matches("<line").count(), inner_walls + 2);
+        Ok(())
+    }
+}
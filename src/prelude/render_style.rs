@@ -0,0 +1,209 @@
+use std::fmt;
+
+use crate::prelude::*;
+
+/// How a maze is rendered by its [`Display`](fmt::Display) implementation
+///
+/// Set it per maze via [`Maze::set_render_style()`].
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum RenderStyle {
+    /// The same plain ASCII format as the [`Debug`](fmt::Debug) output, which can be parsed
+    /// again via [`Maze::from_ascii()`]
+    #[default]
+    Ascii,
+    /// Connected lines made of Unicode box-drawing characters, joined by the junction glyph
+    /// which matches the walls meeting at every grid corner
+    ///
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let mut maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// ·-·-· ·
+    /// |G    |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// maze.set_render_style(RenderStyle::BoxDrawing);
+    ///
+    /// assert_eq!(maze.to_string(),
+    /// "┌─────┐
+    /// │S    │
+    /// ├──── │
+    /// │G    │
+    /// └─────┘
+    /// ");
+    /// ```
+    BoxDrawing,
+    /// Full blocks for walls, the same layout as [`Maze::to_block_grid()`]
+    Blocks,
+    /// A single hexadecimal digit per field encoding its passages, the same format as
+    /// [`Maze::to_hex()`]
+    Hex,
+}
+
+impl Maze {
+    /// The style in which the maze is displayed
+    #[must_use]
+    pub fn render_style(&self) -> RenderStyle {
+        self.render_style
+    }
+
+    /// Change the style in which the maze is displayed.
+    ///
+    /// The style only affects the [`Display`](fmt::Display) output and is ignored when
+    /// comparing mazes.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let mut maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// ·-·-· ·
+    /// |G    |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// maze.set_render_style(RenderStyle::Blocks);
+    ///
+    /// assert_eq!(maze.to_string(),
+    /// "███████
+    /// █S    █
+    /// █████ █
+    /// █G    █
+    /// ███████
+    /// ");
+    /// ```
+    pub fn set_render_style(&mut self, style: RenderStyle) {
+        self.render_style = style;
+    }
+
+    /// The box-drawing character for a grid corner which connects the walls in the given
+    /// directions
+    fn box_corner(north: bool, east: bool, south: bool, west: bool) -> char {
+        match (north, east, south, west) {
+            (false, false, false, false) => ' ',
+            (true, false, true, false)
+            | (true, false, false, false)
+            | (false, false, true, false) => '│',
+            (false, true, false, true)
+            | (false, true, false, false)
+            | (false, false, false, true) => '─',
+            (false, true, true, false) => '┌',
+            (false, false, true, true) => '┐',
+            (true, true, false, false) => '└',
+            (true, false, false, true) => '┘',
+            (true, true, true, false) => '├',
+            (true, false, true, true) => '┤',
+            (false, true, true, true) => '┬',
+            (true, true, false, true) => '┴',
+            (true, true, true, true) => '┼',
+        }
+    }
+}
+
+impl fmt::Display for Maze {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.render_style == RenderStyle::Ascii {
+            return fmt::Debug::fmt(self, f);
+        }
+        if self.render_style == RenderStyle::Hex {
+            return f.write_str(&self.to_hex());
+        }
+
+        let grid = self.to_block_grid();
+        let blocked = |x: usize, y: usize| grid.get(y).and_then(|row| row.get(x)) == Some(&true);
+        for (y, row) in grid.iter().enumerate() {
+            for (x, is_blocked) in row.iter().enumerate() {
+                let field = Coordinates::new(x as i32 / 2, y as i32 / 2);
+                let c = if x % 2 == 1 && y % 2 == 1 && field == self.start {
+                    'S'
+                } else if x % 2 == 1 && y % 2 == 1 && field == self.goal {
+                    'G'
+                } else if !is_blocked {
+                    ' '
+                } else if self.render_style == RenderStyle::Blocks {
+                    '█'
+                } else if x % 2 == 0 && y % 2 == 0 {
+                    Maze::box_corner(
+                        y > 0 && blocked(x, y - 1),
+                        blocked(x + 1, y),
+                        blocked(x, y + 1),
+                        x > 0 && blocked(x - 1, y),
+                    )
+                } else if x % 2 == 0 {
+                    '│'
+                } else {
+                    '─'
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Generator, Maze, RenderStyle};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_render_styles() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3)?;
+        assert_eq!(maze.to_string(), format!("{:?}", maze));
+
+        let ascii = maze.clone();
+        maze.set_render_style(RenderStyle::Blocks);
+        assert_eq!(maze, ascii);
+        assert_eq!(
+            maze.to_string(),
+            "███████\n█S█   █\n█ ███ █\n█     █\n█████ █\n█G    █\n███████\n"
+        );
+
+        maze.set_render_style(RenderStyle::BoxDrawing);
+        assert_eq!(
+            maze.to_string(),
+            "┌─┬───┐\n│S│   │\n│ └── │\n│     │\n├──── │\n│G    │\n└─────┘\n"
+        );
+        assert_eq!(maze.rotate90().render_style(), RenderStyle::BoxDrawing);
+        Ok(())
+    }
+
+    #[test]
+    fn test_box_corners_connect_their_walls() {
+        // the walls which every glyph connects to, in the order north, east, south, west
+        let arms = |c: char| match c {
+            ' ' => [false, false, false, false],
+            '│' => [true, false, true, false],
+            '─' => [false, true, false, true],
+            '┌' => [false, true, true, false],
+            '┐' => [false, false, true, true],
+            '└' => [true, true, false, false],
+            '┘' => [true, false, false, true],
+            '├' => [true, true, true, false],
+            '┤' => [true, false, true, true],
+            '┬' => [false, true, true, true],
+            '┴' => [true, true, false, true],
+            '┼' => [true, true, true, true],
+            _ => [false; 4],
+        };
+        for bits in 0..16 {
+            let walls = [bits & 1 != 0, bits & 2 != 0, bits & 4 != 0, bits & 8 != 0];
+            let glyph = Maze::box_corner(walls[0], walls[1], walls[2], walls[3]);
+            let expected = match walls.iter().filter(|wall| **wall).count() {
+                // a single wall ends in a straight line instead of a half one, which many
+                // fonts lack
+                1 if walls[0] || walls[2] => arms('│'),
+                1 => arms('─'),
+                _ => walls,
+            };
+            assert_eq!(arms(glyph), expected, "{:?}", walls);
+        }
+    }
+}
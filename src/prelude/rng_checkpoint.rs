@@ -0,0 +1,73 @@
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+use crate::prelude::*;
+
+/// Position of a seeded random generator from which it can be restored, e.g. in another process
+///
+/// A [`ChaChaRng`] can not be serialized itself, so its seed is stored together with the number
+/// of 32 bit words it produced so far.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct RngCheckpoint {
+    seed: Seed,
+    word_pos: u128,
+}
+
+impl RngCheckpoint {
+    /// Create a random generator from `seed` whose position can be remembered.
+    ///
+    /// `ChaChaRng::get_word_pos()` overflows for random generators which did not produce any
+    /// numbers yet, so the first blocks are generated right away, which does not change the
+    /// numbers it produces.
+    pub(crate) fn seeded(seed: Seed) -> ChaChaRng {
+        RngCheckpoint { seed, word_pos: 0 }.restore()
+    }
+
+    /// Remember the position of `rng`, which must have been created from `seed` via
+    /// [`RngCheckpoint::seeded()`]
+    pub(crate) fn new(seed: Seed, rng: &ChaChaRng) -> Self {
+        RngCheckpoint {
+            seed,
+            word_pos: rng.get_word_pos(),
+        }
+    }
+
+    /// The seed from which the random generator was created
+    pub(crate) fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    /// Create a random generator which continues exactly where the remembered one stopped
+    pub(crate) fn restore(&self) -> ChaChaRng {
+        let mut rng = ChaChaRng::from_seed(self.seed);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaChaRng;
+
+    use super::RngCheckpoint;
+
+    #[test]
+    fn test_restored_rng_continues() {
+        let mut rng = RngCheckpoint::seeded([3; 32]);
+        let mut fresh = RngCheckpoint::new([3; 32], &rng).restore();
+        assert_eq!(fresh.next_u64(), ChaChaRng::from_seed([3; 32]).next_u64());
+
+        // stop in the middle of a block and of a 64 bit number
+        for _ in 0..7 {
+            rng.next_u32();
+        }
+        let checkpoint = RngCheckpoint::new([3; 32], &rng);
+        let mut restored = checkpoint.restore();
+        for _ in 0..40 {
+            assert_eq!(restored.next_u64(), rng.next_u64());
+        }
+        assert_eq!(checkpoint.seed(), [3; 32]);
+    }
+}
@@ -0,0 +1,249 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while carving a room via [`Maze::carve_room()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RoomError {
+    /// The room does not contain a single field
+    #[error("The room {0:?} is empty")]
+    Empty(Room),
+    /// The room does not completely lie inside of the maze
+    #[error("The room {0:?} does not lie inside of the maze")]
+    OutsideMaze(Room),
+    /// The room overlaps a room which was carved before
+    #[error("The room {0:?} overlaps the existing room {1:?}")]
+    Overlaps(Room, Room),
+}
+
+/// A rectangular area of multiple fields without any walls between them
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let room = Room::new(Coordinates::new(2, 1), 3, 2);
+/// assert!(room.contains(&Coordinates::new(4, 2)));
+/// assert!(!room.contains(&Coordinates::new(5, 2)));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Room {
+    /// The north-western field of the room
+    pub origin: Coordinates,
+    /// Number of columns
+    pub width: i32,
+    /// Number of rows
+    pub height: i32,
+}
+
+impl Room {
+    /// Create a room whose north-western field lies at `origin`
+    #[must_use]
+    pub fn new(origin: Coordinates, width: i32, height: i32) -> Self {
+        Room {
+            origin,
+            width,
+            height,
+        }
+    }
+
+    /// Whether the field at `coordinates` is part of the room
+    #[must_use]
+    pub fn contains(&self, coordinates: &Coordinates) -> bool {
+        coordinates.x >= self.origin.x
+            && coordinates.x < self.origin.x + self.width
+            && coordinates.y >= self.origin.y
+            && coordinates.y < self.origin.y + self.height
+    }
+
+    /// Whether both rooms share at least one field
+    #[must_use]
+    pub fn overlaps(&self, other: &Room) -> bool {
+        self.origin.x < other.origin.x + other.width
+            && other.origin.x < self.origin.x + self.width
+            && self.origin.y < other.origin.y + other.height
+            && other.origin.y < self.origin.y + self.height
+    }
+
+    /// Iterate over all fields of the room in row-major order
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinates> {
+        let Room {
+            origin,
+            width,
+            height,
+        } = *self;
+        (origin.y..origin.y + height).flat_map(move |iy| {
+            (origin.x..origin.x + width).map(move |ix| Coordinates::new(ix, iy))
+        })
+    }
+}
+
+impl Maze {
+    /// All rooms which were carved into this maze
+    #[must_use]
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    /// The room which contains the field at `coordinates`, if any
+    #[must_use]
+    pub fn room_at(&self, coordinates: &Coordinates) -> Option<&Room> {
+        self.rooms.iter().find(|room| room.contains(coordinates))
+    }
+
+    /// Whether the grid corner at the north-western edge of field `(ix, iy)` lies inside of a room
+    pub(crate) fn is_inside_room(&self, ix: i32, iy: i32) -> bool {
+        self.room_at(&Coordinates::new(ix, iy))
+            .is_some_and(|room| room.contains(&Coordinates::new(ix - 1, iy - 1)))
+    }
+
+    /// Remove all walls inside of `room` and record it as a room of this maze.
+    ///
+    /// Corridors which lead into the room are kept as long as they are needed to reach some part
+    /// of the maze.
+    /// Entrances which only formed a loop through the room are walled off so that a perfect maze
+    /// stays perfect when treating the room as a single field.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// maze.carve_room(Room::new(Coordinates::new(1, 0), 2, 2)).unwrap();
+    ///
+    /// assert_eq!(format!("{:?}", maze),
+    /// "·-·-·-·
+    /// |S|   |
+    /// · ·   ·
+    /// |     |
+    /// ·-·-· ·
+    /// |G    |
+    /// ·-·-·-·
+    /// ");
+    /// ```
+    pub fn carve_room(&mut self, room: Room) -> Result<(), RoomError> {
+        if room.width <= 0 || room.height <= 0 {
+            return Err(RoomError::Empty(room));
+        }
+        let last = Coordinates::new(
+            room.origin.x + room.width - 1,
+            room.origin.y + room.height - 1,
+        );
+        if !self.are_coordinates_inside(&room.origin) || !self.are_coordinates_inside(&last) {
+            return Err(RoomError::OutsideMaze(room));
+        }
+        if let Some(other) = self.rooms.iter().find(|other| other.overlaps(&room)) {
+            return Err(RoomError::Overlaps(room, *other));
+        }
+
+        for coordinates in room.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if room.contains(&next) {
+                    self.add_passage(coordinates, next);
+                }
+            }
+        }
+
+        // join all fields which are connected without entering the room, then keep only those
+        // entrances which connect something new
+        let mut sets = DisjointSets::new(self.topology().cell_count());
+        let width = self.size.0 as usize;
+        let index = |c: Coordinates| c.y as usize * width + c.x as usize;
+        let mut entrances = Vec::new();
+        for coordinates in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if !self.has_passage_between(coordinates, next) {
+                    continue;
+                }
+                if room.contains(&coordinates) != room.contains(&next) {
+                    entrances.push((coordinates, next));
+                } else {
+                    sets.union(index(coordinates), index(next));
+                }
+            }
+        }
+        for (a, b) in entrances {
+            if !sets.union(index(a), index(b)) {
+                self.passages.remove(a, b);
+            }
+        }
+
+        self.rooms.push(room);
+        Ok(())
+    }
+}
+
+/// Generate a maze and carve the given rooms into it.
+///
+/// Corridors connect to every room through a single entrance per neighbouring part of the maze,
+/// see [`Maze::carve_room()`].
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::prims_algorithm::PrimsGenerator;
+///
+/// let rooms = [Room::new(Coordinates::new(2, 2), 3, 3)];
+/// let maze = generate_with_rooms(&mut PrimsGenerator::new(Some([42; 32])), 10, 10, &rooms).unwrap();
+/// assert_eq!(maze.rooms(), &rooms);
+/// ```
+pub fn generate_with_rooms<G: Generator + ?Sized>(
+    generator: &mut G,
+    width: i32,
+    height: i32,
+    rooms: &[Room],
+) -> Result<Maze, MazeError> {
+    let mut maze = generator.generate(width, height)?;
+    for room in rooms.iter() {
+        maze.carve_room(*room)?;
+    }
+    Ok(maze)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Generator, Maze, Room, RoomError};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_room_keeps_maze_perfect() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([4; 32])).generate(10, 8)?;
+        let room = Room::new(Coordinates::new(3, 2), 4, 3);
+        maze.carve_room(room)?;
+
+        // all fields are still reachable and the room behaves like a single field
+        let field_count = 10 * 8;
+        assert_eq!(maze.breadth_first_search(maze.start).len(), field_count);
+        let internal_passages = (4 - 1) * 3 + 4 * (3 - 1);
+        assert_eq!(
+            maze.passages.passage_count(),
+            field_count - 1 + internal_passages - (4 * 3 - 1)
+        );
+        assert_eq!(maze.room_at(&Coordinates::new(6, 4)), Some(&room));
+
+        // text output can be parsed again
+        let parsed = Maze::from_ascii(&format!("{:?}", maze))?;
+        assert_eq!(parsed.passages, maze.passages);
+
+        assert_eq!(
+            maze.carve_room(Room::new(Coordinates::new(6, 4), 2, 2)),
+            Err(RoomError::Overlaps(
+                Room::new(Coordinates::new(6, 4), 2, 2),
+                room
+            ))
+        );
+        assert_eq!(
+            maze.carve_room(Room::new(Coordinates::new(8, 0), 3, 1)),
+            Err(RoomError::OutsideMaze(Room::new(
+                Coordinates::new(8, 0),
+                3,
+                1
+            )))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while placing items via [`Maze::scatter_items()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ScatterError {
+    /// Not all items could be placed without violating the constraints
+    #[error("Only {placed} of {requested} items could be placed")]
+    NotEnoughSpace {
+        /// Number of items which were requested
+        requested: usize,
+        /// Number of items which could be placed
+        placed: usize,
+    },
+}
+
+/// Rules for where [`Maze::scatter_items()`] may place items
+///
+/// All distances are measured in steps along the passages of the maze.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScatterConstraints {
+    /// Minimum distance of every item from the start, default: `0`
+    pub min_distance_from_start: u32,
+    /// Minimum distance between any two items, default: `1`
+    ///
+    /// Items are never placed onto the same field, so values below `1` count as `1`.
+    pub min_distance_between: u32,
+    /// How much more likely items are placed at dead ends than at other fields, default: `1`
+    ///
+    /// A value of `1` places items uniformly, higher values prefer dead ends, which are the
+    /// classic places for treasure, and values below `1` avoid them.
+    pub dead_end_weight: f64,
+}
+
+impl Default for ScatterConstraints {
+    fn default() -> Self {
+        ScatterConstraints {
+            min_distance_from_start: 0,
+            min_distance_between: 1,
+            dead_end_weight: 1.0,
+        }
+    }
+}
+
+impl Maze {
+    /// Randomly choose `n` fields for placing items like loot or enemies which satisfy
+    /// `constraints`.
+    ///
+    /// Only fields which are reachable from the start are chosen.
+    /// The fields are picked one after another in random order, preferring fields with a
+    /// higher weight, and every field which lies too close to an already placed item is skipped.
+    /// Because the placement is greedy, it may fail even though the constraints could be
+    /// satisfied by another arrangement of the items.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(12, 12).unwrap();
+    /// let constraints = ScatterConstraints {
+    ///     min_distance_from_start: 5,
+    ///     min_distance_between: 4,
+    ///     dead_end_weight: 3.0,
+    /// };
+    /// let items = maze.scatter_items(6, &mut rand::thread_rng(), constraints).unwrap();
+    ///
+    /// assert_eq!(items.len(), 6);
+    /// ```
+    pub fn scatter_items(
+        &self,
+        n: usize,
+        rng: &mut impl Rng,
+        constraints: ScatterConstraints,
+    ) -> Result<Vec<Coordinates>, ScatterError> {
+        let dead_end_weight = constraints.dead_end_weight.max(0.0);
+        // weighted sampling without replacement by sorting along random keys, like
+        // `Direction::gen_weighted_order()`
+        let mut candidates: Vec<((f64, f64), Coordinates)> = self
+            .bfs_order(self.start)
+            .into_iter()
+            .filter(|(_, distance)| *distance >= constraints.min_distance_from_start)
+            .map(|(coordinates, _)| {
+                let weight = if self.passage_count(coordinates) == 1 {
+                    dead_end_weight
+                } else {
+                    1.0
+                };
+                let u: f64 = rng.gen();
+                ((u.ln() / weight, u), coordinates)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let width = self.size.0 as usize;
+        let index = |c: Coordinates| c.y as usize * width + c.x as usize;
+        let mut too_close = vec![false; self.topology().cell_count()];
+        let mut items = Vec::with_capacity(n);
+        for (_, coordinates) in candidates {
+            if items.len() == n {
+                break;
+            }
+            if too_close[index(coordinates)] {
+                continue;
+            }
+            items.push(coordinates);
+
+            // mark every field which is closer than the minimum distance to the new item
+            let mut queue = VecDeque::new();
+            queue.push_back((coordinates, 0));
+            too_close[index(coordinates)] = true;
+            while let Some((current, distance)) = queue.pop_front() {
+                if distance + 1 >= constraints.min_distance_between {
+                    continue;
+                }
+                for next in self.passage_neighbours(current) {
+                    if !too_close[index(next)] {
+                        too_close[index(next)] = true;
+                        queue.push_back((next, distance + 1));
+                    }
+                }
+            }
+        }
+
+        if items.len() < n {
+            return Err(ScatterError::NotEnoughSpace {
+                requested: n,
+                placed: items.len(),
+            });
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::{Generator, ScatterConstraints, ScatterError};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_items_respect_constraints() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([35; 32])).generate(15, 15)?;
+        let mut rng = ChaChaRng::from_seed([36; 32]);
+        let constraints = ScatterConstraints {
+            min_distance_from_start: 6,
+            min_distance_between: 5,
+            dead_end_weight: 1.0,
+        };
+        let items = maze.scatter_items(8, &mut rng, constraints)?;
+        assert_eq!(items.len(), 8);
+
+        let from_start = maze.breadth_first_search(maze.start);
+        for (i, item) in items.iter().enumerate() {
+            assert!(from_start[item].0 >= 6);
+            let from_item = maze.breadth_first_search(*item);
+            for other in items[..i].iter() {
+                assert!(from_item[other].0 >= 5);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_dead_ends_are_preferred() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([37; 32])).generate(15, 15)?;
+        let dead_ends = maze.dead_ends();
+        let count_dead_ends = |weight| -> anyhow::Result<usize> {
+            let constraints = ScatterConstraints {
+                dead_end_weight: weight,
+                ..ScatterConstraints::default()
+            };
+            let items = maze.scatter_items(20, &mut ChaChaRng::from_seed([38; 32]), constraints)?;
+            Ok(items.iter().filter(|item| dead_ends.contains(item)).count())
+        };
+        assert_eq!(count_dead_ends(0.0)?, 0);
+        assert!(count_dead_ends(1.0)? < count_dead_ends(100.0)?);
+        assert_eq!(count_dead_ends(f64::INFINITY)?, 20.min(dead_ends.len()));
+
+        assert_eq!(
+            maze.scatter_items(
+                1,
+                &mut ChaChaRng::from_seed([39; 32]),
+                ScatterConstraints {
+                    min_distance_from_start: 1000,
+                    ..ScatterConstraints::default()
+                }
+            ),
+            Err(ScatterError::NotEnoughSpace {
+                requested: 1,
+                placed: 0
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+#[cfg(feature = "svg")]
+use std::io;
+
+use crate::prelude::*;
+
+/// Path finding algorithms whose exploration can be traced via [`Maze::solve_with_trace()`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum SearchAlgorithm {
+    /// Expands the fields in the order of their distance from the start and always finds the
+    /// shortest route
+    BreadthFirst,
+    /// Follows every branch until its dead end before backtracking to the next one, which finds
+    /// some route but not necessarily the shortest one
+    DepthFirst,
+    /// Expands the fields in the order of their distance from the start plus their
+    /// [manhattan distance](Coordinates::manhattan_distance) to the goal and always finds the
+    /// shortest route
+    AStar,
+}
+
+/// Record of how a search explored a maze, see [`Maze::solve_with_trace()`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SolveTrace {
+    /// The algorithm which was traced
+    pub algorithm: SearchAlgorithm,
+    /// Every expanded field in the order of expansion, starting with the start and ending with
+    /// the goal if it was found
+    pub visited: Vec<Coordinates>,
+    /// Number of fields waiting to be expanded right after each expansion in `visited`
+    pub frontier_sizes: Vec<usize>,
+    /// Indices into `visited` at which the search abandoned the branch it was following, i.e.
+    /// the expanded field is not connected to the previously expanded one
+    pub backtracks: Vec<usize>,
+    /// The route which was found including start and goal, `None` if the goal is unreachable
+    pub path: Option<Vec<Coordinates>>,
+}
+
+/// A field waiting to be expanded together with the field through which it was reached and
+/// its distance from the start
+type Entry = (Coordinates, Option<Coordinates>, u32);
+
+/// The fields waiting to be expanded, whose order distinguishes the search algorithms
+enum Frontier {
+    Queue(VecDeque<Entry>),
+    Stack(Vec<Entry>),
+    /// Ordered by the estimated length of the whole route and then by the distance to the goal
+    Heap(BinaryHeap<Reverse<(u32, u32, Entry)>>, Coordinates),
+}
+
+impl Frontier {
+    fn push(&mut self, entry: Entry) {
+        match self {
+            Frontier::Queue(queue) => queue.push_back(entry),
+            Frontier::Stack(stack) => stack.push(entry),
+            Frontier::Heap(heap, to) => {
+                let remaining = entry.0.manhattan_distance(to);
+                heap.push(Reverse((entry.2 + remaining, remaining, entry)));
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<Entry> {
+        match self {
+            Frontier::Queue(queue) => queue.pop_front(),
+            Frontier::Stack(stack) => stack.pop(),
+            Frontier::Heap(heap, _) => heap.pop().map(|Reverse((_, _, entry))| entry),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Frontier::Queue(queue) => queue.len(),
+            Frontier::Stack(stack) => stack.len(),
+            Frontier::Heap(heap, _) => heap.len(),
+        }
+    }
+}
+
+impl Maze {
+    /// Search a route from `from` to `to` while recording every step of the search, e.g. for
+    /// teaching how path finding algorithms explore a maze.
+    ///
+    /// Neighbours are considered in the order of [`Direction::all()`], which makes the trace
+    /// deterministic.
+    /// Fields which are reachable through multiple routes can be waiting in the frontier
+    /// multiple times but are only expanded once.
+    /// The trace is empty if `from` lies outside of the maze.
+    ///
+    /// Draw the trace via [`Maze::format_trace()`] or, with the `svg` feature,
+    /// `Maze::trace_to_svg()` and `Maze::trace_to_animated_svg()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+    /// let bfs = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::BreadthFirst);
+    /// let a_star = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::AStar);
+    ///
+    /// assert_eq!(bfs.path, a_star.path);
+    /// assert_eq!(bfs.visited.last(), Some(&maze.goal));
+    /// ```
+    #[must_use]
+    pub fn solve_with_trace(
+        &self,
+        from: Coordinates,
+        to: Coordinates,
+        algorithm: SearchAlgorithm,
+    ) -> SolveTrace {
+        let mut trace = SolveTrace {
+            algorithm,
+            visited: Vec::new(),
+            frontier_sizes: Vec::new(),
+            backtracks: Vec::new(),
+            path: None,
+        };
+        if !self.are_coordinates_inside(&from) {
+            return trace;
+        }
+
+        let mut frontier = match algorithm {
+            SearchAlgorithm::BreadthFirst => Frontier::Queue(VecDeque::new()),
+            SearchAlgorithm::DepthFirst => Frontier::Stack(Vec::new()),
+            SearchAlgorithm::AStar => Frontier::Heap(BinaryHeap::new(), to),
+        };
+        let mut parents: HashMap<Coordinates, Option<Coordinates>> = HashMap::new();
+        let mut expanded = HashSet::new();
+        frontier.push((from, None, 0));
+
+        while let Some((coordinates, parent, distance)) = frontier.pop() {
+            if !expanded.insert(coordinates) {
+                continue;
+            }
+            parents.insert(coordinates, parent);
+            if let Some(previous) = trace.visited.last() {
+                if !self.has_passage_between(*previous, coordinates) {
+                    trace.backtracks.push(trace.visited.len());
+                }
+            }
+            trace.visited.push(coordinates);
+            if coordinates == to {
+                trace.frontier_sizes.push(frontier.len());
+                break;
+            }
+
+            // the stack returns the neighbours in reverse so they are pushed in reverse as well
+            let mut neighbours: Vec<Coordinates> = self
+                .passage_neighbours(coordinates)
+                .filter(|next| !expanded.contains(next))
+                .collect();
+            if algorithm == SearchAlgorithm::DepthFirst {
+                neighbours.reverse();
+            }
+            for next in neighbours {
+                frontier.push((next, Some(coordinates), distance + 1));
+            }
+            trace.frontier_sizes.push(frontier.len());
+        }
+
+        if expanded.contains(&to) {
+            let mut path = vec![to];
+            while let Some(Some(previous)) = path.last().and_then(|last| parents.get(last)) {
+                path.push(*previous);
+            }
+            path.reverse();
+            trace.path = Some(path);
+        }
+
+        trace
+    }
+
+    /// Render the maze into the same text format as its [`Debug`](std::fmt::Debug) output with
+    /// the fields expanded by a [traced search](Maze::solve_with_trace()) marked by `.` and the
+    /// route it found drawn as a trail of `*`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// · ·-·-·
+    /// |     |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// let trace = maze.solve_with_trace(maze.start, (2, 0).into(), SearchAlgorithm::BreadthFirst);
+    ///
+    /// assert_eq!(maze.format_trace(&trace),
+    /// "·-·-·-·
+    /// |S****|
+    /// · ·-·-·
+    /// |.    |
+    /// ·-·-·-·
+    /// ");
+    /// ```
+    #[must_use]
+    pub fn format_trace(&self, trace: &SolveTrace) -> String {
+        let path = trace.path.as_deref().unwrap_or_default();
+        let with_path = self.format_with_path(path, PathStyle::Trail('*'));
+        let with_visited = self.format_with_path(&trace.visited, PathStyle::Fields('.'));
+
+        // the route is drawn on top of the expanded fields
+        with_path
+            .chars()
+            .zip(with_visited.chars())
+            .map(|(path, visited)| if path == ' ' { visited } else { path })
+            .collect()
+    }
+}
+
+#[cfg(feature = "svg")]
+impl Maze {
+    /// Render a [traced search](Maze::solve_with_trace()) as an SVG image.
+    ///
+    /// Expanded fields are filled with a colour which changes from blue to red in the order
+    /// of expansion and the route which was found is drawn as a line through the centers of
+    /// its fields.
+    /// With [`SvgOptions::cssclasses`] the fills carry the classes `maze-cell maze-visited`
+    /// and the route the class `maze-path` instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// let trace = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::DepthFirst);
+    /// let svg = maze.trace_to_svg(&trace, SvgOptions::new()).unwrap();
+    ///
+    /// assert!(svg.contains("<polyline"));
+    /// ```
+    pub fn trace_to_svg(&self, trace: &SolveTrace, svgoptions: SvgOptions) -> io::Result<String> {
+        self.write_trace_svg(trace, svgoptions, false)
+    }
+
+    /// Render a [traced search](Maze::solve_with_trace()) as an animated SVG image, e.g. for
+    /// showing students how the algorithms explore a maze differently.
+    ///
+    /// The image looks like the one of [`trace_to_svg()`](Maze::trace_to_svg) but the expanded
+    /// fields appear one after another via SMIL animations, every
+    /// [`SvgOptions::animation_step`] seconds, and the route appears once the search is done.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// let trace = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::AStar);
+    /// let svg = maze
+    ///     .trace_to_animated_svg(&trace, SvgOptions::new().animation_step(0.25))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(svg.matches("<set ").count(), trace.visited.len() + 1);
+    /// ```
+    pub fn trace_to_animated_svg(
+        &self,
+        trace: &SolveTrace,
+        svgoptions: SvgOptions,
+    ) -> io::Result<String> {
+        self.write_trace_svg(trace, svgoptions, true)
+    }
+
+    /// Render a traced search with the fills appearing one after another if `animated` is set
+    fn write_trace_svg(
+        &self,
+        trace: &SolveTrace,
+        svgoptions: SvgOptions,
+        animated: bool,
+    ) -> io::Result<String> {
+        let last = trace.visited.len().saturating_sub(1).max(1) as f64;
+        let overlay = SvgOverlay {
+            fills: trace
+                .visited
+                .iter()
+                .enumerate()
+                .map(|(i, coordinates)| {
+                    let hue = 240.0 * (1.0 - i as f64 / last);
+                    (
+                        *coordinates,
+                        "maze-cell maze-visited",
+                        format!("hsl({:.0}, 80%, 80%)", hue),
+                    )
+                })
+                .collect(),
+            path: trace.path.as_deref().unwrap_or_default(),
+            animated,
+            ..SvgOverlay::default()
+        };
+
+        let mut svg = Vec::new();
+        self.write_svg_with_overlay(&mut svg, &svgoptions, &overlay)?;
+        String::from_utf8(svg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    use crate::prelude::{Coordinates, Generator, Maze, SearchAlgorithm};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_algorithms_explore_differently() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([8; 32])).generate(12, 12)?;
+        // loops make the route which depth-first search finds longer than the shortest one
+        maze.braid(1.0, &mut ChaChaRng::from_seed([8; 32]));
+        let (from, to) = (maze.start, maze.goal);
+        let shortest = maze.find_path(from, to);
+
+        for algorithm in [
+            SearchAlgorithm::BreadthFirst,
+            SearchAlgorithm::DepthFirst,
+            SearchAlgorithm::AStar,
+        ]
+        .iter()
+        {
+            let trace = maze.solve_with_trace(from, to, *algorithm);
+            assert_eq!(trace.visited.first(), Some(&from));
+            assert_eq!(trace.visited.last(), Some(&to));
+            assert_eq!(trace.frontier_sizes.len(), trace.visited.len());
+            assert!(trace.backtracks.iter().all(|i| *i > 0));
+
+            let path = trace.path.unwrap_or_default();
+            assert_eq!(path.first(), Some(&from));
+            assert!(path
+                .windows(2)
+                .all(|w| maze.has_passage_between(w[0], w[1])));
+            if *algorithm != SearchAlgorithm::DepthFirst {
+                assert_eq!(Some(path.len()), shortest.as_ref().map(Vec::len));
+            }
+        }
+
+        let bfs = maze.solve_with_trace(from, to, SearchAlgorithm::BreadthFirst);
+        let a_star = maze.solve_with_trace(from, to, SearchAlgorithm::AStar);
+        assert!(a_star.visited.len() <= bfs.visited.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreachable_goal() -> anyhow::Result<()> {
+        let maze = Maze::from_ascii(
+            "·-·-·-·
+|S  |G|
+·-·-·-·
+",
+        )?;
+        let trace = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::DepthFirst);
+        assert_eq!(trace.visited.len(), 2);
+        assert_eq!(trace.path, None);
+        assert_eq!(maze.format_trace(&trace), "·-·-·-·\n|S .|G|\n·-·-·-·\n");
+
+        let outside =
+            maze.solve_with_trace(Coordinates::new(3, 0), maze.goal, SearchAlgorithm::AStar);
+        assert!(outside.visited.is_empty());
+        Ok(())
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn test_trace_svg() -> anyhow::Result<()> {
+        use crate::prelude::SvgOptions;
+
+        let maze = PrimsGenerator::new(Some([9; 32])).generate(6, 4)?;
+        let trace = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::BreadthFirst);
+        let svg = maze.trace_to_svg(&trace, SvgOptions::new())?;
+        assert_eq!(svg.matches("hsl(").count(), trace.visited.len());
+        assert!(svg.contains("hsl(240, 80%, 80%)"));
+        assert!(svg.contains("hsl(0, 80%, 80%)"));
+
+        let svg = maze.trace_to_svg(&trace, SvgOptions::new().cssclasses(true))?;
+        assert_eq!(svg.matches("maze-visited").count(), trace.visited.len());
+        assert_eq!(svg.matches("class=\"maze-path\"").count(), 1);
+        Ok(())
+    }
+
+    #[cfg(feature = "svg")]
+    #[test]
+    fn test_trace_animated_svg() -> anyhow::Result<()> {
+        use crate::prelude::SvgOptions;
+
+        let maze = PrimsGenerator::new(Some([9; 32])).generate(6, 4)?;
+        let trace = maze.solve_with_trace(maze.start, maze.goal, SearchAlgorithm::DepthFirst);
+        let options = SvgOptions::new().animation_step(0.5);
+        let svg = maze.trace_to_animated_svg(&trace, options.clone())?;
+        let visited = trace.visited.len();
+        assert_eq!(svg.matches("hsl(").count(), visited);
+        assert_eq!(svg.matches("visibility=\"hidden\"").count(), visited + 1);
+        assert!(svg.contains("begin=\"0.5s\""));
+        // the path appears last
+        let end = format!("begin=\"{}s\"", (visited + 1) as f64 * 0.5);
+        assert_eq!(svg.matches(&end).count(), 1);
+        assert!(svg[svg.find("<polyline").unwrap_or_default()..].contains(&end));
+
+        let still = maze.trace_to_svg(&trace, options)?;
+        assert!(!still.contains("<set "));
+        Ok(())
+    }
+}
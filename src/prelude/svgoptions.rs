@@ -1,10 +1,108 @@
+use crate::prelude::*;
+
+/// Shape used to mark fields like the start and the goal in SVG output
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MarkerShape {
+    /// A filled circle with a radius of `markersize`
+    Circle,
+    /// A filled square with an edge length of twice the `markersize`
+    Square,
+    /// The letter *S* or *G*, or the [letter](MarkerStyle::letter) of a custom marker, sized
+    /// relative to the cell
+    Letter,
+}
+
+/// Appearance of a custom marker, see [`SvgOptions::markers`]
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let key = MarkerStyle::new().shape(MarkerShape::Letter).letter("K").colour("gold");
+/// assert_eq!(key.letter, "K");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct MarkerStyle {
+    /// Shape of the marker, default: [`MarkerShape::Circle`]
+    pub shape: MarkerShape,
+    /// Colour of the marker, default: "green"
+    pub colour: String,
+    /// Text which is drawn for [`MarkerShape::Letter`], default: "*"
+    pub letter: String,
+    /// CSS class which is emitted in addition to `maze-marker` if
+    /// [`SvgOptions::cssclasses`] is set, default: None
+    pub class: Option<String>,
+}
+
+impl MarkerStyle {
+    /// Create a default MarkerStyle object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the shape of the marker
+    #[must_use]
+    pub fn shape(mut self, shape: MarkerShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Set the colour of the marker
+    #[must_use]
+    pub fn colour(mut self, colour: impl Into<String>) -> Self {
+        self.colour = colour.into();
+        self
+    }
+
+    /// Set the text which is drawn for [`MarkerShape::Letter`]
+    #[must_use]
+    pub fn letter(mut self, letter: impl Into<String>) -> Self {
+        self.letter = letter.into();
+        self
+    }
+
+    /// Set the additional CSS class of the marker
+    #[must_use]
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.class = Some(class.into());
+        self
+    }
+}
+
+impl Default for MarkerStyle {
+    fn default() -> Self {
+        MarkerStyle {
+            shape: MarkerShape::Circle,
+            colour: String::from("green"),
+            letter: String::from("*"),
+            class: None,
+        }
+    }
+}
+
 /// Options for generating SVG output
-#[derive(Debug)]
+///
+/// Construct it via [`SvgOptions::new()`] (or [`Default`]) and adjust individual options with the
+/// provided builder methods:
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = SvgOptions::new().strokecol("green").padding(8).height(400);
+/// assert_eq!(options.height, Some(400));
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct SvgOptions {
     /// Padding, default: 10
     pub padding: i32,
-    /// Height in pixels - use None to derive a height based on the padding and number of cells in the maze
+    /// Height in pixels - use None to derive a height based on the padding and number of cells in the maze.
+    /// Ignored if `cell_size` is set.
     pub height: Option<i32>,
+    /// Edge length of a single square cell in pixels - use None to derive it from `height`.
+    /// The width of the image always follows from the cell size and the number of columns.
+    pub cell_size: Option<f64>,
     /// Marker size - start and end, default: 2
     pub markersize: i32,
     /// Start marker colour - either a named colour like 'red' or a hex string like '#FF0000', default: "red"
@@ -15,25 +113,267 @@ pub struct SvgOptions {
     pub strokewidth: i32,
     /// Stroke  colour, default: "#000000" (black)
     pub strokecol: String,
+    /// Fill colour of the whole image including padding - use None for a transparent background, default: None
+    pub background: Option<String>,
+    /// Fill colour of normal cells - use None to not fill them, default: None
+    pub cellfill: Option<String>,
+    /// Fill colour of the rooms of the maze (see [`Room`](crate::prelude::Room)) - use None to not fill them, default: None
+    pub roomfill: Option<String>,
+    /// Fill colour of the start cell - use None to not fill it, default: None
+    pub startfill: Option<String>,
+    /// Fill colour of the goal cell - use None to not fill it, default: None
+    pub goalfill: Option<String>,
+    /// Fill colours of the distance bands around the start (see
+    /// [`Maze::distance_bands()`](crate::prelude::Maze::distance_bands)) - the colours are
+    /// repeated if there are more bands than colours, leave empty to not fill them, default:
+    /// empty
+    pub bandfills: Vec<String>,
+    /// Number of steps covered by every distance band, default: 1
+    pub bandwidth: u32,
+    /// Shape of the start marker, default: [`MarkerShape::Circle`]
+    pub startmarker: MarkerShape,
+    /// Shape of the goal marker, default: [`MarkerShape::Circle`]
+    pub goalmarker: MarkerShape,
+    /// Additional markers for points of interest like keys or checkpoints, which are drawn on
+    /// top of the start and goal markers - markers outside of the maze are left out, default:
+    /// empty
+    pub markers: Vec<(Coordinates, MarkerStyle)>,
+    /// Emit CSS classes instead of colours and inline styles, default: false
+    ///
+    /// This is useful when embedding the SVG into a themed website.
+    /// All elements are then styled by the following classes:
+    /// `maze-background`, `maze-cell`, `maze-room`, `maze-start`, `maze-goal`, `maze-wall`,
+    /// `maze-marker`, `maze-grid`, `maze-coordinates`, `maze-crop-mark`
+    /// (which is combined with `maze-start` or `maze-goal`) and `maze-band-0`, `maze-band-1`, …
+    /// for the fill colours of the distance bands.
+    /// Traces rendered via [`Maze::trace_to_svg()`] additionally use `maze-visited` and
+    /// `maze-path`.
+    /// The fill options still decide whether backgrounds and cells are drawn but their colours
+    /// are not emitted.
+    pub cssclasses: bool,
+    /// Label every cell with its faint `x,y` coordinates, default: false
+    ///
+    /// This is meant for debugging generators and for referring to specific cells, e.g. in bug
+    /// reports.
+    pub show_coordinates: bool,
+    /// Draw a light grid along the borders of all cells below the walls, default: false
+    ///
+    /// Like [`show_coordinates`](SvgOptions::show_coordinates) this is meant for debugging, as
+    /// it makes the rows and columns easy to count.
+    pub show_grid: bool,
+    /// Number of pixels by which adjacent tiles of
+    /// [`Maze::to_svg_tiles()`](crate::prelude::Maze::to_svg_tiles) overlap, default: 20
+    pub tile_overlap: f64,
+    /// Number of seconds between two steps of
+    /// [`Maze::to_animated_svg()`](crate::prelude::Maze::to_animated_svg) and
+    /// [`Maze::trace_to_animated_svg()`](crate::prelude::Maze::trace_to_animated_svg), default:
+    /// 0.1
+    pub animation_step: f64,
 }
 
 impl SvgOptions {
     /// Create a default SvgOptions object
+    #[must_use]
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Set the padding around the maze
+    #[must_use]
+    pub fn padding(mut self, padding: i32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set a fixed height in pixels instead of deriving it from the padding and maze size
+    #[must_use]
+    pub fn height(mut self, height: i32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Set a fixed edge length of every cell in pixels, overriding the height
+    #[must_use]
+    pub fn cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = Some(cell_size);
+        self
+    }
+
+    /// Set the size of the start and goal markers
+    #[must_use]
+    pub fn markersize(mut self, markersize: i32) -> Self {
+        self.markersize = markersize;
+        self
+    }
+
+    /// Set the colour of the start marker
+    #[must_use]
+    pub fn startcol(mut self, startcol: impl Into<String>) -> Self {
+        self.startcol = startcol.into();
+        self
+    }
+
+    /// Set the colour of the goal marker
+    #[must_use]
+    pub fn goalcol(mut self, goalcol: impl Into<String>) -> Self {
+        self.goalcol = goalcol.into();
+        self
+    }
+
+    /// Set the width of wall strokes
+    #[must_use]
+    pub fn strokewidth(mut self, strokewidth: i32) -> Self {
+        self.strokewidth = strokewidth;
+        self
+    }
+
+    /// Set the colour of wall strokes
+    #[must_use]
+    pub fn strokecol(mut self, strokecol: impl Into<String>) -> Self {
+        self.strokecol = strokecol.into();
+        self
+    }
+
+    /// Set the background colour of the whole image
+    #[must_use]
+    pub fn background(mut self, background: impl Into<String>) -> Self {
+        self.background = Some(background.into());
+        self
+    }
+
+    /// Set the fill colour of normal cells
+    #[must_use]
+    pub fn cellfill(mut self, cellfill: impl Into<String>) -> Self {
+        self.cellfill = Some(cellfill.into());
+        self
+    }
+
+    /// Set the fill colour of rooms
+    #[must_use]
+    pub fn roomfill(mut self, roomfill: impl Into<String>) -> Self {
+        self.roomfill = Some(roomfill.into());
+        self
+    }
+
+    /// Set the fill colour of the start cell
+    #[must_use]
+    pub fn startfill(mut self, startfill: impl Into<String>) -> Self {
+        self.startfill = Some(startfill.into());
+        self
+    }
+
+    /// Set the fill colour of the goal cell
+    #[must_use]
+    pub fn goalfill(mut self, goalfill: impl Into<String>) -> Self {
+        self.goalfill = Some(goalfill.into());
+        self
+    }
+
+    /// Fill the distance bands around the start with the given colours, e.g. for a rainbow
+    /// maze
+    #[must_use]
+    pub fn bandfills<S: Into<String>>(mut self, bandfills: impl IntoIterator<Item = S>) -> Self {
+        self.bandfills = bandfills.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the number of steps covered by every distance band
+    #[must_use]
+    pub fn bandwidth(mut self, bandwidth: u32) -> Self {
+        self.bandwidth = bandwidth;
+        self
+    }
+
+    /// Set the shape of the start marker
+    #[must_use]
+    pub fn startmarker(mut self, startmarker: MarkerShape) -> Self {
+        self.startmarker = startmarker;
+        self
+    }
+
+    /// Set the shape of the goal marker
+    #[must_use]
+    pub fn goalmarker(mut self, goalmarker: MarkerShape) -> Self {
+        self.goalmarker = goalmarker;
+        self
+    }
+
+    /// Set all additional markers
+    #[must_use]
+    pub fn markers(mut self, markers: Vec<(Coordinates, MarkerStyle)>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    /// Add a marker at `coordinates`
+    #[must_use]
+    pub fn marker(mut self, coordinates: Coordinates, style: MarkerStyle) -> Self {
+        self.markers.push((coordinates, style));
+        self
+    }
+
+    /// Set whether CSS classes are emitted instead of colours and inline styles
+    #[must_use]
+    pub fn cssclasses(mut self, cssclasses: bool) -> Self {
+        self.cssclasses = cssclasses;
+        self
+    }
+
+    /// Set whether every cell is labelled with its coordinates
+    #[must_use]
+    pub fn show_coordinates(mut self, show_coordinates: bool) -> Self {
+        self.show_coordinates = show_coordinates;
+        self
+    }
+
+    /// Set whether a light grid is drawn along the borders of all cells
+    #[must_use]
+    pub fn show_grid(mut self, show_grid: bool) -> Self {
+        self.show_grid = show_grid;
+        self
+    }
+
+    /// Set the number of pixels by which adjacent tiles overlap
+    #[must_use]
+    pub fn tile_overlap(mut self, tile_overlap: f64) -> Self {
+        self.tile_overlap = tile_overlap;
+        self
+    }
+
+    /// Set the number of seconds between two steps of an animation
+    #[must_use]
+    pub fn animation_step(mut self, animation_step: f64) -> Self {
+        self.animation_step = animation_step;
+        self
+    }
 }
 
 impl Default for SvgOptions {
     fn default() -> Self {
         SvgOptions {
             height: None,
+            cell_size: None,
             padding: 10,
             markersize: 2,
             startcol: String::from("red"),
             goalcol: String::from("blue"),
             strokewidth: 4,
             strokecol: String::from("#000000"),
+            background: None,
+            cellfill: None,
+            roomfill: None,
+            startfill: None,
+            goalfill: None,
+            bandfills: Vec::new(),
+            bandwidth: 1,
+            startmarker: MarkerShape::Circle,
+            goalmarker: MarkerShape::Circle,
+            markers: Vec::new(),
+            cssclasses: false,
+            show_coordinates: false,
+            show_grid: false,
+            tile_overlap: 20.0,
+            animation_step: 0.1,
         }
     }
 }
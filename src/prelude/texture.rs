@@ -0,0 +1,247 @@
+use crate::prelude::*;
+
+/// Quantitative description of the texture of a maze, see [`Maze::corridor_histogram()`]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CorridorHistogram {
+    /// Number of straight runs of every length, `runs[n]` counting the runs of exactly `n`
+    /// passages.
+    ///
+    /// A straight run is a maximal sequence of passages which lead into the same direction
+    /// without interruption, so every passage belongs to exactly one run.
+    /// The vector ends with the longest run and `runs[0]` is always `0`.
+    pub runs: Vec<usize>,
+    /// Number of fields with every number of passages, `degrees[d]` counting the fields with
+    /// exactly `d` passages.
+    ///
+    /// `degrees[1]` are the dead ends while `degrees[3]` and `degrees[4]` are junctions.
+    pub degrees: [usize; 5],
+}
+
+impl CorridorHistogram {
+    /// Total number of straight runs
+    #[must_use]
+    pub fn run_count(&self) -> usize {
+        self.runs.iter().sum()
+    }
+
+    /// Number of passages in the longest straight run
+    #[must_use]
+    pub fn longest_run(&self) -> usize {
+        self.runs.len().saturating_sub(1)
+    }
+
+    /// Average number of passages per straight run, `0` if the maze has no passages.
+    ///
+    /// Higher values mean longer corridors, lower values a more twisty texture.
+    #[must_use]
+    pub fn mean_run_length(&self) -> f64 {
+        let passages: usize = self
+            .runs
+            .iter()
+            .enumerate()
+            .map(|(length, count)| length * count)
+            .sum();
+        match self.run_count() {
+            0 => 0.0,
+            runs => passages as f64 / runs as f64,
+        }
+    }
+
+    /// Share of fields with three or four passages, in range `0..=1`
+    #[must_use]
+    pub fn junction_ratio(&self) -> f64 {
+        self.ratio(self.degrees[3] + self.degrees[4])
+    }
+
+    /// Share of fields with exactly one passage, in range `0..=1`
+    #[must_use]
+    pub fn dead_end_ratio(&self) -> f64 {
+        self.ratio(self.degrees[1])
+    }
+
+    /// `count` relative to the number of fields
+    fn ratio(&self, count: usize) -> f64 {
+        match self.degrees.iter().sum::<usize>() {
+            0 => 0.0,
+            fields => count as f64 / fields as f64,
+        }
+    }
+}
+
+/// Desired texture of a maze which is understood by all algorithms, see
+/// [`Algorithm::generator_with_texture()`](crate::builder::Algorithm::generator_with_texture)
+/// and [`MazeBuilder::texture()`](crate::builder::MazeBuilder::texture)
+///
+/// Every parameter is in range `0..=1` and mapped to the knobs of the chosen algorithm, so that
+/// switching algorithms does not require learning their individual parameters.
+/// Parameters which are `None` keep the defaults of the algorithm and parameters which an
+/// algorithm can not influence are ignored by it.
+/// Use [`Maze::corridor_histogram()`] for measuring how a maze turned out.
+///
+/// # Examples
+/// ```
+/// use maze_generator::builder::{Algorithm, MazeBuilder};
+/// use maze_generator::prelude::*;
+///
+/// // long straight "rivers" without any dead ends
+/// let texture = Texture {
+///     windiness: Some(0.0),
+///     dead_ends: Some(0.0),
+///     ..Texture::default()
+/// };
+/// for algorithm in Algorithm::ALL.iter() {
+///     let maze = MazeBuilder::new(10, 10)
+///         .algorithm(*algorithm)
+///         .texture(texture)
+///         .build()
+///         .unwrap();
+///     assert!(maze.dead_ends().is_empty());
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Texture {
+    /// How often corridors turn, from long straight corridors at `0` to turns at almost every
+    /// field at `1`
+    ///
+    /// Used as [`RbGenerator::windiness`](crate::recursive_backtracking::RbGenerator::windiness)
+    /// and by [`EllersGenerator`](crate::ellers_algorithm::EllersGenerator), which joins the
+    /// fields of a row into horizontal corridors with a chance of `1 - windiness / 2` and adds
+    /// extra connections to the next row with a chance of `windiness / 2`.
+    pub windiness: Option<f64>,
+    /// How often corridors branch off, from few long branches at `0` to many short ones at `1`
+    ///
+    /// Used by [`GrowingTreeGenerator`](crate::growing_tree::GrowingTreeGenerator) as chance of
+    /// [`GrowingTreeSelectionMethod::Mixed`](crate::growing_tree::GrowingTreeSelectionMethod::Mixed).
+    pub branchiness: Option<f64>,
+    /// Approximate share of the dead ends which are kept, from none at `0` to all at `1`
+    ///
+    /// All algorithms generate perfect mazes whose dead ends are then removed by
+    /// [braiding](Maze::braid) with a chance of `1 - dead_ends`, which is only done by
+    /// [`MazeBuilder`](crate::builder::MazeBuilder).
+    pub dead_ends: Option<f64>,
+}
+
+impl Maze {
+    /// Measure the texture of the maze by the lengths of its straight corridors and the number
+    /// of passages of its fields, e.g. for tuning the parameters of a generator.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// · ·-· ·
+    /// |  G  |
+    /// ·-·-·-·
+    /// ").unwrap();
+    /// let histogram = maze.corridor_histogram();
+    ///
+    /// // the two rows of three fields each and the two columns at the sides
+    /// assert_eq!(histogram.runs, vec![0, 2, 2]);
+    /// assert_eq!(histogram.degrees, [0, 0, 6, 0, 0]);
+    /// assert_eq!(histogram.mean_run_length(), 1.5);
+    /// ```
+    #[must_use]
+    pub fn corridor_histogram(&self) -> CorridorHistogram {
+        let (width, height) = self.size;
+        let mut runs = vec![0];
+        let mut record = |length: usize| {
+            if length > 0 {
+                if runs.len() <= length {
+                    runs.resize(length + 1, 0);
+                }
+                runs[length] += 1;
+            }
+        };
+
+        // follow every row towards the east and every column towards the south
+        for (lines, line_length, direction) in [
+            (height, width, Direction::East),
+            (width, height, Direction::South),
+        ]
+        .iter()
+        {
+            for i_line in 0..*lines {
+                let mut length = 0;
+                for i_field in 0..*line_length {
+                    let coordinates = match direction {
+                        Direction::East => Coordinates::new(i_field, i_line),
+                        _ => Coordinates::new(i_line, i_field),
+                    };
+                    if self.has_passage(&coordinates, direction) {
+                        length += 1;
+                    } else {
+                        record(length);
+                        length = 0;
+                    }
+                }
+                record(length);
+            }
+        }
+
+        let mut degrees = [0; 5];
+        for coordinates in self.coordinates() {
+            degrees[self.passage_count(coordinates)] += 1;
+        }
+
+        CorridorHistogram { runs, degrees }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::growing_tree::GrowingTreeGenerator;
+    use crate::prelude::{Generator, Maze};
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_histogram_counts_every_passage() -> anyhow::Result<()> {
+        let maze = GrowingTreeGenerator::new(Some([13; 32])).generate(15, 11)?;
+        let histogram = maze.corridor_histogram();
+
+        let passages: usize = histogram
+            .runs
+            .iter()
+            .enumerate()
+            .map(|(length, count)| length * count)
+            .sum();
+        assert_eq!(passages, 15 * 11 - 1);
+        assert_eq!(histogram.degrees.iter().sum::<usize>(), 15 * 11);
+        assert_eq!(histogram.degrees[0], 0);
+        assert_eq!(histogram.runs.last().map(|count| *count > 0), Some(true));
+        assert_eq!(histogram.degrees[1], maze.dead_ends().len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_texture_reflects_generator_parameters() -> anyhow::Result<()> {
+        let texture = |windiness| -> anyhow::Result<_> {
+            let mut generator = RbGenerator::new(Some([14; 32]));
+            generator.windiness = Some(windiness);
+            Ok(generator.generate(30, 30)?.corridor_histogram())
+        };
+        let straight = texture(0.0)?;
+        let winding = texture(1.0)?;
+        assert!(straight.mean_run_length() > winding.mean_run_length());
+        assert!(straight.longest_run() > winding.longest_run());
+
+        // recursive backtracking carves long corridors with few branches while Prim's algorithm
+        // results in a branchy texture
+        let prims = PrimsGenerator::new(Some([14; 32]))
+            .generate(30, 30)?
+            .corridor_histogram();
+        assert!(winding.junction_ratio() < prims.junction_ratio());
+        assert!(winding.dead_end_ratio() < prims.dead_end_ratio());
+
+        let closed = Maze::from_ascii("·-·\n|S|\n·-·\n")?.corridor_histogram();
+        assert_eq!(closed.runs, vec![0]);
+        assert_eq!(closed.longest_run(), 0);
+        assert_eq!(closed.mean_run_length(), 0.0);
+        assert_eq!(closed.degrees, [1, 0, 0, 0, 0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+use crate::prelude::*;
+
+/// A single tile of a tilemap created by [`Maze::to_tilemap()`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Tile {
+    /// Impassable wall tile
+    Wall,
+    /// Walkable floor tile
+    Floor,
+    /// Floor tile in the center of the start field
+    Start,
+    /// Floor tile in the center of the goal field
+    Goal,
+}
+
+/// How many tiles a single field is expanded into
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TilemapScale {
+    /// Each field becomes a 2x2 block whose right column and bottom row are walls or floor,
+    /// depending on whether passages exist in that direction.
+    ///
+    /// Neighbouring fields share the wall tiles between them and the tilemap is closed by an
+    /// additional row and column at the top and left, resulting in `2 * width + 1` by
+    /// `2 * height + 1` tiles.
+    Double,
+    /// Each field becomes a 3x3 block surrounded by its own walls, resulting in `3 * width` by
+    /// `3 * height` tiles.
+    Triple,
+}
+
+impl Maze {
+    /// Expand every field into a block of wall and floor tiles.
+    ///
+    /// The tilemap is indexed as `tiles[y][x]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let tiles = maze.to_tilemap(TilemapScale::Double);
+    ///
+    /// assert_eq!(tiles.len(), 7);
+    /// assert_eq!(tiles[1][1], Tile::Start);
+    /// assert_eq!(tiles[2][1], Tile::Floor);
+    /// ```
+    #[must_use]
+    pub fn to_tilemap(&self, scale: TilemapScale) -> Vec<Vec<Tile>> {
+        let (block_size, extra) = match scale {
+            TilemapScale::Double => (2, 1),
+            TilemapScale::Triple => (3, 0),
+        };
+        let mut tiles = vec![
+            vec![Tile::Wall; (self.size.0 * block_size + extra) as usize];
+            (self.size.1 * block_size + extra) as usize
+        ];
+
+        for coordinates in self.coordinates() {
+            // the center of each block is the tile at offset (1, 1)
+            let center = Coordinates::new(
+                coordinates.x * block_size + 1,
+                coordinates.y * block_size + 1,
+            );
+            tiles[center.y as usize][center.x as usize] = if coordinates == self.start {
+                Tile::Start
+            } else if coordinates == self.goal {
+                Tile::Goal
+            } else {
+                Tile::Floor
+            };
+
+            for direction in Direction::all().iter() {
+                if self.has_passage(&coordinates, direction) {
+                    let tile = center.next(direction);
+                    tiles[tile.y as usize][tile.x as usize] = Tile::Floor;
+                }
+            }
+        }
+
+        tiles
+    }
+
+    /// Export the maze as an occupancy grid in which walls occupy their own cells, as used by
+    /// physics engines and pathfinding libraries.
+    ///
+    /// The grid has `2 * width + 1` columns and `2 * height + 1` rows and is indexed as
+    /// `grid[y][x]`.
+    /// `true` marks a blocked cell.
+    /// The field at `coordinates` lies at `grid[2 * y + 1][2 * x + 1]` and the cells between
+    /// two fields are open if a passage connects them.
+    /// Unlike [`TilemapScale::Double`] the pillars in the interior of rooms are open as well, so
+    /// rooms become completely open areas.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let grid = maze.to_block_grid();
+    ///
+    /// assert_eq!((grid[0].len(), grid.len()), (7, 7));
+    /// assert!(!grid[1][1]);
+    /// assert!(grid[0].iter().all(|blocked| *blocked));
+    /// ```
+    #[must_use]
+    pub fn to_block_grid(&self) -> Vec<Vec<bool>> {
+        let mut grid: Vec<Vec<bool>> = self
+            .to_tilemap(TilemapScale::Double)
+            .into_iter()
+            .map(|row| row.into_iter().map(|tile| tile == Tile::Wall).collect())
+            .collect();
+
+        for room in self.rooms.iter() {
+            for coordinates in room.coordinates() {
+                if self.is_inside_room(coordinates.x, coordinates.y) {
+                    grid[2 * coordinates.y as usize][2 * coordinates.x as usize] = false;
+                }
+            }
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Generator, Room, Tile, TilemapScale};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_triple_tilemap() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([42; 32])).generate(3, 3)?;
+        let tiles = maze.to_tilemap(TilemapScale::Triple);
+        let rendered: Vec<String> = tiles
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| match tile {
+                        Tile::Wall => '#',
+                        Tile::Floor => '.',
+                        Tile::Start => 'S',
+                        Tile::Goal => 'G',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "#########",
+                "#S##....#",
+                "#.#####.#",
+                "#.#####.#",
+                "#.......#",
+                "#######.#",
+                "#######.#",
+                "#G......#",
+                "#########",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_block_grid_opens_rooms() -> anyhow::Result<()> {
+        let mut maze = RbGenerator::new(Some([42; 32])).generate(3, 3)?;
+        let tiles = maze.to_tilemap(TilemapScale::Double);
+        let grid = maze.to_block_grid();
+        for (tile_row, grid_row) in tiles.iter().zip(grid.iter()) {
+            for (tile, blocked) in tile_row.iter().zip(grid_row.iter()) {
+                assert_eq!(*tile == Tile::Wall, *blocked);
+            }
+        }
+
+        maze.carve_room(Room::new(Coordinates::new(1, 0), 2, 2))?;
+        let grid = maze.to_block_grid();
+        let rendered: Vec<String> = grid
+            .iter()
+            .map(|row| row.iter().map(|b| if *b { '#' } else { '.' }).collect())
+            .collect();
+        assert_eq!(
+            rendered,
+            vec!["#######", "#.#...#", "#.#...#", "#.....#", "#####.#", "#.....#", "#######"]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::Write;
+
+use crate::prelude::*;
+
+/// Options for exporting a maze to the TMX map format of the [Tiled](https://www.mapeditor.org)
+/// editor, which Godot and many other engines can import as well
+///
+/// The map references an external tileset whose tiles are selected by their local ids.
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = TmxOptions::new().tileset("dungeon.tsx").tile_size(32, 32).wall(7);
+/// assert_eq!(options.tile_width, 32);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct TmxOptions {
+    /// How many tiles a single field is expanded into, default: [`TilemapScale::Double`]
+    pub scale: TilemapScale,
+    /// Width of a tile in pixels, default: 16
+    pub tile_width: u32,
+    /// Height of a tile in pixels, default: 16
+    pub tile_height: u32,
+    /// Path of the external tileset relative to the map, default: `maze.tsx`
+    pub tileset: String,
+    /// Id of the wall tile inside of the tileset, default: 0
+    pub wall: u32,
+    /// Id of the floor tile inside of the tileset, default: 1
+    pub floor: u32,
+    /// Id of the tile marking the start inside of the tileset, default: 2
+    pub start: u32,
+    /// Id of the tile marking the goal inside of the tileset, default: 3
+    pub goal: u32,
+}
+
+impl TmxOptions {
+    /// Create a default TmxOptions object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set how many tiles a single field is expanded into
+    #[must_use]
+    pub fn scale(mut self, scale: TilemapScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the size of a tile in pixels
+    #[must_use]
+    pub fn tile_size(mut self, width: u32, height: u32) -> Self {
+        self.tile_width = width;
+        self.tile_height = height;
+        self
+    }
+
+    /// Set the path of the external tileset
+    #[must_use]
+    pub fn tileset(mut self, tileset: &str) -> Self {
+        self.tileset = tileset.to_string();
+        self
+    }
+
+    /// Set the id of the wall tile
+    #[must_use]
+    pub fn wall(mut self, wall: u32) -> Self {
+        self.wall = wall;
+        self
+    }
+
+    /// Set the id of the floor tile
+    #[must_use]
+    pub fn floor(mut self, floor: u32) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Set the id of the tile marking the start
+    #[must_use]
+    pub fn start(mut self, start: u32) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Set the id of the tile marking the goal
+    #[must_use]
+    pub fn goal(mut self, goal: u32) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// Global id of `tile` in a map in which the tileset starts at global id 1
+    fn gid(&self, tile: Tile) -> u32 {
+        1 + match tile {
+            Tile::Wall => self.wall,
+            Tile::Floor => self.floor,
+            Tile::Start => self.start,
+            Tile::Goal => self.goal,
+        }
+    }
+}
+
+impl Default for TmxOptions {
+    fn default() -> Self {
+        TmxOptions {
+            scale: TilemapScale::Double,
+            tile_width: 16,
+            tile_height: 16,
+            tileset: String::from("maze.tsx"),
+            wall: 0,
+            floor: 1,
+            start: 2,
+            goal: 3,
+        }
+    }
+}
+
+/// Escape the characters which may not appear inside of an XML attribute
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Maze {
+    /// Export the tiles of [`Maze::to_tilemap()`] as the CSV encoded data of a Tiled layer.
+    ///
+    /// Every row of tiles is written on its own line and every tile is given by its global id,
+    /// i.e. the id in the tileset plus 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(1, 1).unwrap();
+    /// assert_eq!(maze.to_tiled_csv(&TmxOptions::new()), "1,1,1,\n1,3,1,\n1,1,1");
+    /// ```
+    #[must_use]
+    pub fn to_tiled_csv(&self, options: &TmxOptions) -> String {
+        self.to_tilemap(options.scale)
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| options.gid(*tile).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join(",\n")
+    }
+
+    /// Export the maze as a Tiled map with a single tile layer.
+    ///
+    /// The map can be opened in Tiled or imported into Godot once the tileset referenced by
+    /// [`TmxOptions::tileset`] exists next to it.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let tmx = maze.to_tmx(&TmxOptions::new());
+    ///
+    /// assert!(tmx.contains(r#"<map version="1.10" orientation="orthogonal""#));
+    /// assert!(tmx.contains(r#"<layer id="1" name="maze" width="7" height="7">"#));
+    /// ```
+    #[must_use]
+    pub fn to_tmx(&self, options: &TmxOptions) -> String {
+        let tiles = self.to_tilemap(options.scale);
+        let height = tiles.len();
+        let width = tiles.first().map_or(0, |row| row.len());
+
+        let mut tmx = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        // writing into a string never fails
+        let _ = writeln!(
+            tmx,
+            "<map version=\"1.10\" orientation=\"orthogonal\" renderorder=\"right-down\" \
+             width=\"{}\" height=\"{}\" tilewidth=\"{}\" tileheight=\"{}\" infinite=\"0\" \
+             nextlayerid=\"2\" nextobjectid=\"1\">",
+            width, height, options.tile_width, options.tile_height
+        );
+        let _ = writeln!(
+            tmx,
+            " <tileset firstgid=\"1\" source=\"{}\"/>",
+            escape_attribute(&options.tileset)
+        );
+        let _ = writeln!(
+            tmx,
+            " <layer id=\"1\" name=\"maze\" width=\"{}\" height=\"{}\">",
+            width, height
+        );
+        let _ = writeln!(tmx, "  <data encoding=\"csv\">");
+        let _ = writeln!(tmx, "{}", self.to_tiled_csv(options));
+        let _ = writeln!(tmx, "</data>");
+        let _ = writeln!(tmx, " </layer>");
+        let _ = writeln!(tmx, "</map>");
+
+        tmx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Generator, TilemapScale, TmxOptions};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_csv_matches_tilemap() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([42; 32])).generate(3, 2)?;
+        let options = TmxOptions::new()
+            .scale(TilemapScale::Triple)
+            .tileset("a&b.tsx")
+            .floor(9);
+
+        let csv = maze.to_tiled_csv(&options);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), 6);
+        assert!(rows[..5].iter().all(|row| row.ends_with(',')));
+        assert_eq!(rows[5].split(',').count(), 9);
+        assert!(csv.contains("10"));
+
+        let tmx = maze.to_tmx(&options);
+        assert!(tmx.contains(r#"source="a&amp;b.tsx""#));
+        assert!(tmx.contains(r#"width="9" height="6" tilewidth="16""#));
+        assert!(tmx.contains(&csv));
+        Ok(())
+    }
+}
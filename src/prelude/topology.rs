@@ -0,0 +1,144 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::prelude::*;
+
+/// How the fields of a [`Topology`] are laid out when drawing them
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum RenderingHint {
+    /// Square fields arranged in rows and columns
+    Grid {
+        /// Number of columns
+        width: i32,
+        /// Number of rows
+        height: i32,
+    },
+    /// Concentric rings around a single center field which are subdivided radially
+    Polar {
+        /// Number of rings including the center field
+        rings: usize,
+    },
+}
+
+/// The shape of a maze, i.e. which fields exist and which of them are adjacent
+///
+/// Algorithms which only need to know about fields and their neighbours can be written against
+/// this trait and then work on every shape of maze, e.g. the generic
+/// [`RbGenerator::carve()`](crate::recursive_backtracking::RbGenerator::carve).
+/// The rectangular [`Maze`] uses [`RectangularTopology`] while
+/// [`ThetaMaze`](crate::theta::ThetaMaze) uses [`ThetaTopology`](crate::theta::ThetaTopology).
+///
+/// [`Maze`] itself is not generic over its topology.
+/// Its passage storage, rendering and file formats all assume rows and columns, so other shapes
+/// come with their own maze type instead.
+/// Prim's algorithm and growing tree find the neighbours of a field through
+/// [`Maze::topology()`], while recursive backtracking and Eller's algorithm work with
+/// directions and rows of the grid.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let topology = RectangularTopology::new(3, 2);
+/// assert_eq!(topology.cell_count(), 6);
+/// assert_eq!(
+///     topology.neighbours(&Coordinates::new(0, 0)),
+///     vec![Coordinates::new(1, 0), Coordinates::new(0, 1)]
+/// );
+///
+/// let mut buffer = vec![Coordinates::new(0, 0)];
+/// topology.neighbours_into(&Coordinates::new(5, 5), &mut buffer);
+/// assert!(buffer.is_empty());
+/// ```
+pub trait Topology {
+    /// Type which addresses a single field
+    type Cell: Copy + Eq + Hash + Debug;
+
+    /// All fields in a stable order
+    fn cells(&self) -> Vec<Self::Cell>;
+
+    /// Whether `cell` addresses a field of this topology
+    fn contains(&self, cell: &Self::Cell) -> bool;
+
+    /// All fields which are adjacent to `cell`, regardless of walls between them
+    ///
+    /// Fields outside of the topology have no neighbours.
+    fn neighbours(&self, cell: &Self::Cell) -> Vec<Self::Cell>;
+
+    /// Replace the contents of `buffer` with the fields which are adjacent to `cell`, in the same
+    /// order as [`Topology::neighbours()`]
+    ///
+    /// Unlike `neighbours`, this can reuse the allocation of `buffer` in hot loops.
+    fn neighbours_into(&self, cell: &Self::Cell, buffer: &mut Vec<Self::Cell>) {
+        buffer.clear();
+        buffer.extend(self.neighbours(cell));
+    }
+
+    /// Number of fields
+    fn cell_count(&self) -> usize {
+        self.cells().len()
+    }
+
+    /// How the fields should be laid out when drawing them
+    fn rendering_hint(&self) -> RenderingHint;
+}
+
+/// A rectangular grid of square fields, the shape of every [`Maze`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct RectangularTopology {
+    /// Number of columns
+    pub width: i32,
+    /// Number of rows
+    pub height: i32,
+}
+
+impl RectangularTopology {
+    /// Create a grid with the given number of columns and rows
+    #[must_use]
+    pub fn new(width: i32, height: i32) -> Self {
+        RectangularTopology { width, height }
+    }
+}
+
+impl Topology for RectangularTopology {
+    type Cell = Coordinates;
+
+    fn cells(&self) -> Vec<Coordinates> {
+        (0..self.height)
+            .flat_map(|iy| (0..self.width).map(move |ix| Coordinates::new(ix, iy)))
+            .collect()
+    }
+
+    fn contains(&self, cell: &Coordinates) -> bool {
+        cell.x >= 0 && cell.x < self.width && cell.y >= 0 && cell.y < self.height
+    }
+
+    fn neighbours(&self, cell: &Coordinates) -> Vec<Coordinates> {
+        let mut neighbours = Vec::with_capacity(4);
+        self.neighbours_into(cell, &mut neighbours);
+        neighbours
+    }
+
+    fn neighbours_into(&self, cell: &Coordinates, buffer: &mut Vec<Coordinates>) {
+        buffer.clear();
+        if self.contains(cell) {
+            buffer.extend(
+                IntoIterator::into_iter(Direction::all())
+                    .map(|direction| cell.next(&direction))
+                    .filter(|next| self.contains(next)),
+            );
+        }
+    }
+
+    fn cell_count(&self) -> usize {
+        self.width.max(0) as usize * self.height.max(0) as usize
+    }
+
+    fn rendering_hint(&self) -> RenderingHint {
+        RenderingHint::Grid {
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
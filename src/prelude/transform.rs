@@ -0,0 +1,117 @@
+use crate::prelude::*;
+
+impl Maze {
+    /// Build a maze of size `size` in which every field of this maze is moved to
+    /// `map(coordinates)`, together with its passages, rooms, start and goal
+    fn transformed(&self, size: (i32, i32), map: impl Fn(Coordinates) -> Coordinates) -> Maze {
+        let mut maze = Maze::new(size.0, size.1, map(self.start), map(self.goal));
+        for coordinates in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if self.has_passage_between(coordinates, next) {
+                    maze.add_passage(map(coordinates), map(next));
+                }
+            }
+        }
+        maze.rooms = self
+            .rooms
+            .iter()
+            .map(|room| {
+                let a = map(room.origin);
+                let b = map(Coordinates::new(
+                    room.origin.x + room.width - 1,
+                    room.origin.y + room.height - 1,
+                ));
+                let origin = Coordinates::new(a.x.min(b.x), a.y.min(b.y));
+                Room::new(origin, (a.x - b.x).abs() + 1, (a.y - b.y).abs() + 1)
+            })
+            .collect();
+        maze.render_style = self.render_style;
+        maze
+    }
+
+    /// Rotate the maze by 90° clockwise, swapping its width and height
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    ///
+    /// let maze = Maze::from_ascii(
+    /// "·-·-·-·
+    /// |S    |
+    /// ·-·-· ·
+    /// |G    |
+    /// ·-·-·-·
+    /// ").unwrap();
+    ///
+    /// assert_eq!(format!("{:?}", maze.rotate90()),
+    /// "·-·-·
+    /// |G|S|
+    /// · · ·
+    /// | | |
+    /// · · ·
+    /// |   |
+    /// ·-·-·
+    /// ");
+    /// ```
+    #[must_use]
+    pub fn rotate90(&self) -> Maze {
+        let height = self.size.1;
+        self.transformed((self.size.1, self.size.0), |c| {
+            Coordinates::new(height - 1 - c.y, c.x)
+        })
+    }
+
+    /// Mirror the maze horizontally, i.e. swap its left and right side
+    #[must_use]
+    pub fn mirror_horizontal(&self) -> Maze {
+        let width = self.size.0;
+        self.transformed(self.size, |c| Coordinates::new(width - 1 - c.x, c.y))
+    }
+
+    /// Mirror the maze vertically, i.e. swap its top and bottom side
+    #[must_use]
+    pub fn mirror_vertical(&self) -> Maze {
+        let height = self.size.1;
+        self.transformed(self.size, |c| Coordinates::new(c.x, height - 1 - c.y))
+    }
+
+    /// Mirror the maze along its diagonal from the north-western corner, swapping its width and
+    /// height
+    #[must_use]
+    pub fn transpose(&self) -> Maze {
+        self.transformed((self.size.1, self.size.0), |c| Coordinates::new(c.y, c.x))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Generator, Room};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_transformations_compose() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([21; 32])).generate(7, 4)?;
+        maze.carve_room(Room::new(Coordinates::new(1, 1), 3, 2))?;
+
+        let rotated = maze.rotate90();
+        assert_eq!(rotated.size, (4, 7));
+        assert_eq!(rotated.rooms(), &[Room::new(Coordinates::new(1, 1), 2, 3)]);
+        assert_eq!(rotated.rotate90().rotate90().rotate90(), maze);
+        assert_eq!(
+            maze.rotate90().rotate90(),
+            maze.mirror_horizontal().mirror_vertical()
+        );
+        assert_eq!(maze.transpose(), maze.rotate90().mirror_horizontal());
+        assert_eq!(maze.mirror_horizontal().mirror_horizontal(), maze);
+        assert_eq!(maze.transpose().transpose(), maze);
+
+        let mirrored = maze.mirror_vertical();
+        assert_eq!(
+            mirrored.start,
+            Coordinates::new(maze.start.x, 3 - maze.start.y)
+        );
+        assert_eq!(mirrored.difficulty(), maze.difficulty());
+        Ok(())
+    }
+}
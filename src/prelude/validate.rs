@@ -0,0 +1,247 @@
+use rand::prelude::*;
+use thiserror::Error;
+
+use crate::prelude::disjoint_sets::DisjointSets;
+use crate::prelude::*;
+
+/// Inconsistencies of a maze which are found by [`Maze::validate()`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// Width or height of the maze are not positive
+    #[error("The maze has an invalid size of {0}x{1}")]
+    InvalidSize(i32, i32),
+    /// The start field lies outside of the maze
+    #[error("The start {0} lies outside of the maze")]
+    StartOutsideMaze(Coordinates),
+    /// The goal field lies outside of the maze
+    #[error("The goal {0} lies outside of the maze")]
+    GoalOutsideMaze(Coordinates),
+    /// A passage leads from the field into the direction but not back from the neighbouring
+    /// field
+    #[error("The passage from {0} to the {1} does not lead back")]
+    AsymmetricPassage(Coordinates, Direction),
+    /// A passage leads from the field into the direction out of the maze, e.g. because its
+    /// [`size`](Maze::size) was reduced
+    #[error("The passage from {0} to the {1} leaves the maze")]
+    PassageLeavesMaze(Coordinates, Direction),
+    /// A room does not completely lie inside of the maze
+    #[error("The room {0:?} does not lie inside of the maze")]
+    RoomOutsideMaze(Room),
+    /// Two rooms overlap each other
+    #[error("The room {0:?} overlaps the room {1:?}")]
+    RoomsOverlap(Room, Room),
+}
+
+impl Maze {
+    /// Check the maze for inconsistencies, e.g. after constructing or editing it by hand.
+    ///
+    /// Every passage needs to lead back from the neighbouring field and may not leave the maze,
+    /// start, goal and rooms need to lie inside of the maze and rooms may not overlap.
+    /// The first inconsistency that is found is returned.
+    ///
+    /// Whether all fields are connected is not checked, see [`Maze::unreachable_cells()`] and
+    /// [`Maze::is_perfect()`] for that.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// assert_eq!(maze.validate(), Ok(()));
+    ///
+    /// maze.goal = Coordinates::new(5, 0);
+    /// assert_eq!(maze.validate(), Err(ValidationError::GoalOutsideMaze(maze.goal)));
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let (width, height) = self.size;
+        if width <= 0 || height <= 0 {
+            return Err(ValidationError::InvalidSize(width, height));
+        }
+        if !self.are_coordinates_inside(&self.start) {
+            return Err(ValidationError::StartOutsideMaze(self.start));
+        }
+        if !self.are_coordinates_inside(&self.goal) {
+            return Err(ValidationError::GoalOutsideMaze(self.goal));
+        }
+
+        // passages may be stored outside of the maze if its size was changed without resetting
+        let (grid_width, grid_height) = self.passages.size();
+        for y in 0..height.max(grid_height) {
+            for x in 0..width.max(grid_width) {
+                let coordinates = Coordinates::new(x, y);
+                for direction in Direction::iter() {
+                    let next = coordinates.next(&direction);
+                    if !self.has_passage(&coordinates, &direction) {
+                        continue;
+                    }
+                    if !self.are_coordinates_inside(&coordinates)
+                        || !self.are_coordinates_inside(&next)
+                    {
+                        return Err(ValidationError::PassageLeavesMaze(coordinates, direction));
+                    }
+                    if !self.has_passage(&next, &direction.opposite()) {
+                        return Err(ValidationError::AsymmetricPassage(coordinates, direction));
+                    }
+                }
+            }
+        }
+
+        for (i, room) in self.rooms.iter().enumerate() {
+            let last = Coordinates::new(
+                room.origin.x + room.width - 1,
+                room.origin.y + room.height - 1,
+            );
+            if room.width <= 0
+                || room.height <= 0
+                || !self.are_coordinates_inside(&room.origin)
+                || !self.are_coordinates_inside(&last)
+            {
+                return Err(ValidationError::RoomOutsideMaze(*room));
+            }
+            if let Some(other) = self.rooms[..i].iter().find(|other| other.overlaps(room)) {
+                return Err(ValidationError::RoomsOverlap(*other, *room));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Connect all fields of the maze again, e.g. after walls were added by hand, by carving as
+    /// few passages as possible between its disconnected parts.
+    ///
+    /// Which walls are removed between two parts is chosen randomly via `rng`.
+    /// Fields which were deliberately left without passages, e.g. the obstacles of
+    /// [`RbGenerator::generate_with_obstacles()`](crate::recursive_backtracking::RbGenerator::generate_with_obstacles),
+    /// are connected as well.
+    ///
+    /// Returns the carved passages as the field and the direction in which it was carved, which
+    /// is empty if all fields already were connected.
+    /// Mazes which do not pass [`Maze::validate()`] are left unchanged and its error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let mut maze = RbGenerator::new(Some([42; 32])).generate(5, 5).unwrap();
+    /// let start = maze.start;
+    /// maze.add_wall_unchecked(start, Direction::South).unwrap();
+    /// assert!(!maze.unreachable_cells().is_empty());
+    ///
+    /// let carved = maze.repair(&mut rand::thread_rng()).unwrap();
+    /// assert_eq!(carved.len(), 1);
+    /// assert!(maze.is_perfect());
+    /// ```
+    pub fn repair(
+        &mut self,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<(Coordinates, Direction)>, ValidationError> {
+        self.validate()?;
+
+        let mut sets = DisjointSets::new(self.topology().cell_count());
+        let width = self.size.0 as usize;
+        let index = |c: Coordinates| c.y as usize * width + c.x as usize;
+        let mut walls = Vec::new();
+        for coordinates in self.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if !self.are_coordinates_inside(&next) {
+                    continue;
+                }
+                if self.has_passage(&coordinates, direction) {
+                    sets.union(index(coordinates), index(next));
+                } else {
+                    walls.push((coordinates, *direction));
+                }
+            }
+        }
+
+        // randomized Kruskal's algorithm over the walls, which only carves between parts that
+        // are not connected yet
+        walls.shuffle(rng);
+        let mut carved = Vec::new();
+        for (coordinates, direction) in walls {
+            let next = coordinates.next(&direction);
+            if sets.union(index(coordinates), index(next)) {
+                self.add_passage(coordinates, next);
+                carved.push((coordinates, direction));
+            }
+        }
+
+        Ok(carved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, Direction, Generator, Room, ValidationError};
+    use crate::prims_algorithm::PrimsGenerator;
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_shrunk_maze_is_invalid() -> anyhow::Result<()> {
+        let mut maze = PrimsGenerator::new(Some([12; 32])).generate(4, 4)?;
+        maze.carve_room(Room::new(Coordinates::new(2, 2), 2, 2))?;
+        assert_eq!(maze.validate(), Ok(()));
+
+        let mut overlapping = maze.clone();
+        let room = Room::new(Coordinates::new(1, 1), 2, 2);
+        overlapping.rooms.push(room);
+        assert_eq!(
+            overlapping.validate(),
+            Err(ValidationError::RoomsOverlap(overlapping.rooms[0], room))
+        );
+
+        maze.start = Coordinates::new(0, 0);
+        maze.goal = Coordinates::new(0, 0);
+        maze.size = (4, 3);
+        assert!(matches!(
+            maze.validate(),
+            Err(ValidationError::PassageLeavesMaze(c, Direction::South)) if c.y == 2
+        ));
+        maze.passages.reset(4, 3);
+        assert_eq!(
+            maze.validate(),
+            Err(ValidationError::RoomOutsideMaze(maze.rooms[0]))
+        );
+
+        maze.size = (0, 3);
+        assert_eq!(maze.validate(), Err(ValidationError::InvalidSize(0, 3)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_reconnects_parts() -> anyhow::Result<()> {
+        let original = RbGenerator::new(Some([31; 32])).generate(9, 7)?;
+        let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+        let mut maze = original.clone();
+        assert_eq!(maze.repair(&mut rng)?, vec![]);
+        assert_eq!(maze, original);
+
+        // every removed passage of a perfect maze splits off another part
+        let removed = [(1, 1), (4, 2), (7, 5)];
+        for (x, y) in removed.iter() {
+            let coordinates = Coordinates::new(*x, *y);
+            let direction = Direction::iter()
+                .find(|direction| maze.has_passage(&coordinates, direction))
+                .ok_or_else(|| anyhow::anyhow!("{} has no passage", coordinates))?;
+            maze.add_wall_unchecked(coordinates, direction)?;
+        }
+        assert!(!maze.unreachable_cells().is_empty());
+
+        let carved = maze.repair(&mut rng)?;
+        assert_eq!(carved.len(), removed.len());
+        assert!(maze.is_perfect());
+        for (coordinates, direction) in carved {
+            assert!(maze.has_passage(&coordinates, &direction));
+        }
+
+        maze.size = (9, 6);
+        let unchanged = maze.clone();
+        assert!(maze.repair(&mut rng).is_err());
+        assert_eq!(maze, unchanged);
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+
+use crate::prelude::*;
+
+/// Identifier of a block type as used by voxel games, e.g. `minecraft:stone`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BlockId(pub String);
+
+impl From<&str> for BlockId {
+    fn from(id: &str) -> Self {
+        BlockId(id.to_string())
+    }
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Options for extruding a maze into voxels via [`Maze::to_voxels()`]
+///
+/// ```
+/// use maze_generator::prelude::*;
+///
+/// let options = VoxelOptions::new().wall_height(5).wall("minecraft:hedge".into());
+/// assert_eq!(options.wall_height, 5);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct VoxelOptions {
+    /// How many blocks a single field is expanded into horizontally, default:
+    /// [`TilemapScale::Double`]
+    pub scale: TilemapScale,
+    /// How many blocks the walls are stacked on top of the floor, default: 3
+    pub wall_height: u32,
+    /// Block of which walls are built, default: `minecraft:stone_bricks`
+    pub wall: BlockId,
+    /// Block of which the floor is built, default: `minecraft:smooth_stone`
+    pub floor: BlockId,
+    /// Block in the floor marking the start, default: `minecraft:emerald_block`
+    pub start: BlockId,
+    /// Block in the floor marking the goal, default: `minecraft:gold_block`
+    pub goal: BlockId,
+}
+
+impl VoxelOptions {
+    /// Create a default VoxelOptions object
+    #[must_use]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set how many blocks a single field is expanded into horizontally
+    #[must_use]
+    pub fn scale(mut self, scale: TilemapScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set how many blocks the walls are stacked on top of the floor
+    #[must_use]
+    pub fn wall_height(mut self, wall_height: u32) -> Self {
+        self.wall_height = wall_height;
+        self
+    }
+
+    /// Set the block of which walls are built
+    #[must_use]
+    pub fn wall(mut self, wall: BlockId) -> Self {
+        self.wall = wall;
+        self
+    }
+
+    /// Set the block of which the floor is built
+    #[must_use]
+    pub fn floor(mut self, floor: BlockId) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Set the block in the floor marking the start
+    #[must_use]
+    pub fn start(mut self, start: BlockId) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Set the block in the floor marking the goal
+    #[must_use]
+    pub fn goal(mut self, goal: BlockId) -> Self {
+        self.goal = goal;
+        self
+    }
+}
+
+impl Default for VoxelOptions {
+    fn default() -> Self {
+        VoxelOptions {
+            scale: TilemapScale::Double,
+            wall_height: 3,
+            wall: "minecraft:stone_bricks".into(),
+            floor: "minecraft:smooth_stone".into(),
+            start: "minecraft:emerald_block".into(),
+            goal: "minecraft:gold_block".into(),
+        }
+    }
+}
+
+impl Maze {
+    /// Extrude the tiles of [`Maze::to_tilemap()`] into blocks for voxel games like Minecraft.
+    ///
+    /// Every block is given as `(x, y, z, block)` with `y` pointing upwards, so the tile at
+    /// `tiles[z][x]` becomes a column of blocks.
+    /// The floor is laid at `y = 0` below every tile, including walls, and wall tiles are
+    /// stacked on top of it up to [`VoxelOptions::wall_height`].
+    /// Air is omitted.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let maze = RbGenerator::new(Some([42; 32])).generate(3, 3).unwrap();
+    /// let voxels = maze.to_voxels(&VoxelOptions::new().wall_height(2));
+    ///
+    /// assert!(voxels.contains(&(0, 2, 0, "minecraft:stone_bricks".into())));
+    /// assert!(voxels.contains(&(1, 0, 1, "minecraft:emerald_block".into())));
+    /// assert!(voxels.iter().all(|(_, y, _, _)| (0..=2).contains(y)));
+    /// ```
+    #[must_use]
+    pub fn to_voxels(&self, options: &VoxelOptions) -> Vec<(i32, i32, i32, BlockId)> {
+        let mut voxels = Vec::new();
+        for (z, row) in self.to_tilemap(options.scale).iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let (x, z) = (x as i32, z as i32);
+                let floor = match tile {
+                    Tile::Start => &options.start,
+                    Tile::Goal => &options.goal,
+                    Tile::Wall | Tile::Floor => &options.floor,
+                };
+                voxels.push((x, 0, z, floor.clone()));
+
+                if *tile == Tile::Wall {
+                    for y in 1..=options.wall_height as i32 {
+                        voxels.push((x, y, z, options.wall.clone()));
+                    }
+                }
+            }
+        }
+
+        voxels
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{BlockId, Generator, Tile, TilemapScale, VoxelOptions};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_walls_are_extruded() -> anyhow::Result<()> {
+        let maze = RbGenerator::new(Some([16; 32])).generate(4, 3)?;
+        let options = VoxelOptions::new()
+            .scale(TilemapScale::Triple)
+            .wall_height(4)
+            .wall(BlockId::from("wall"));
+        let tiles = maze.to_tilemap(TilemapScale::Triple);
+        let wall_tiles = tiles.iter().flatten().filter(|t| **t == Tile::Wall).count();
+
+        let voxels = maze.to_voxels(&options);
+        assert_eq!(voxels.len(), 12 * 9 + 4 * wall_tiles);
+        assert_eq!(voxels.iter().filter(|(_, y, _, _)| *y == 0).count(), 12 * 9);
+        for (x, y, z, block) in &voxels {
+            let tile = tiles[*z as usize][*x as usize];
+            assert_eq!(*y > 0, block.to_string() == "wall");
+            assert!(*y == 0 || tile == Tile::Wall);
+        }
+
+        let flat = maze.to_voxels(&VoxelOptions::new().wall_height(0));
+        assert_eq!(flat.len(), 9 * 7);
+        Ok(())
+    }
+}
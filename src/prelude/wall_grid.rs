@@ -0,0 +1,174 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Walls surrounding a single field, `true` meaning that a wall exists in that direction.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct CellWalls {
+    /// Whether a wall exists to the north
+    pub north: bool,
+    /// Whether a wall exists to the east
+    pub east: bool,
+    /// Whether a wall exists to the south
+    pub south: bool,
+    /// Whether a wall exists to the west
+    pub west: bool,
+}
+
+impl CellWalls {
+    /// A cell which is enclosed by walls on all sides
+    #[must_use]
+    pub fn closed() -> Self {
+        CellWalls {
+            north: true,
+            east: true,
+            south: true,
+            west: true,
+        }
+    }
+
+    /// Whether a wall exists in the given direction
+    #[must_use]
+    pub fn has_wall(&self, direction: &Direction) -> bool {
+        match direction {
+            Direction::North => self.north,
+            Direction::East => self.east,
+            Direction::South => self.south,
+            Direction::West => self.west,
+        }
+    }
+}
+
+/// Errors which can occur while constructing a maze from a wall grid
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WallGridError {
+    /// The grid does not contain any cells
+    #[error("The wall grid is empty")]
+    Empty,
+    /// Not all rows of the grid have the same length
+    #[error("Row {0} does not have the same length as the first row")]
+    RaggedRow(usize),
+    /// A cell has no wall towards the outside of the maze
+    #[error("Field {0} has no wall towards the {1} border")]
+    OpenBorder(Coordinates, Direction),
+    /// Two neighbouring cells disagree on whether a wall exists between them
+    #[error("Field {0} and its {1} neighbour disagree about the wall between them")]
+    InconsistentWall(Coordinates, Direction),
+    /// Start or goal do not lie inside of the grid
+    #[error("Coordinates {0} lie outside of the wall grid")]
+    OutOfBounds(Coordinates),
+}
+
+impl Maze {
+    /// Convert the maze into a grid of per-cell wall information.
+    ///
+    /// The grid is indexed as `grid[y][x]`.
+    #[must_use]
+    pub fn to_wall_grid(&self) -> Vec<Vec<CellWalls>> {
+        (0..self.size.1)
+            .map(|iy| {
+                (0..self.size.0)
+                    .map(|ix| {
+                        let coordinates = Coordinates::new(ix, iy);
+                        let has_wall = |direction| !self.has_passage(&coordinates, &direction);
+                        CellWalls {
+                            north: has_wall(Direction::North),
+                            east: has_wall(Direction::East),
+                            south: has_wall(Direction::South),
+                            west: has_wall(Direction::West),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Construct a maze from a grid of per-cell wall information as produced by
+    /// [`to_wall_grid()`](Maze::to_wall_grid).
+    ///
+    /// The grid is validated to be rectangular, to be enclosed by walls and to describe every
+    /// wall consistently from both of its sides.
+    pub fn from_wall_grid(
+        grid: &[Vec<CellWalls>],
+        start: Coordinates,
+        goal: Coordinates,
+    ) -> Result<Maze, WallGridError> {
+        let width = grid.first().map(|row| row.len()).unwrap_or(0);
+        if width == 0 {
+            return Err(WallGridError::Empty);
+        }
+        if let Some(i_row) = grid.iter().position(|row| row.len() != width) {
+            return Err(WallGridError::RaggedRow(i_row));
+        }
+
+        let mut maze = Maze::new(width as i32, grid.len() as i32, start, goal);
+        for coordinates in [start, goal].iter() {
+            if !maze.are_coordinates_inside(coordinates) {
+                return Err(WallGridError::OutOfBounds(*coordinates));
+            }
+        }
+
+        for coordinates in maze.coordinates().collect::<Vec<_>>() {
+            let walls = &grid[coordinates.y as usize][coordinates.x as usize];
+            for direction in Direction::all().iter() {
+                let next = coordinates.next(direction);
+                if !maze.are_coordinates_inside(&next) {
+                    if !walls.has_wall(direction) {
+                        return Err(WallGridError::OpenBorder(coordinates, *direction));
+                    }
+                    continue;
+                }
+
+                let next_walls = &grid[next.y as usize][next.x as usize];
+                if walls.has_wall(direction) != next_walls.has_wall(&direction.opposite()) {
+                    return Err(WallGridError::InconsistentWall(coordinates, *direction));
+                }
+                if !walls.has_wall(direction) {
+                    maze.add_passage(coordinates, next);
+                }
+            }
+        }
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::growing_tree::GrowingTreeGenerator;
+    use crate::prelude::{CellWalls, Coordinates, Direction, Generator, Maze, WallGridError};
+
+    #[test]
+    fn test_wall_grid_round_trip() -> anyhow::Result<()> {
+        let maze = GrowingTreeGenerator::new(Some([7; 32])).generate(6, 4)?;
+        let grid = maze.to_wall_grid();
+
+        assert_eq!(grid.len(), 4);
+        assert_eq!(grid[0].len(), 6);
+        let restored = Maze::from_wall_grid(&grid, maze.start, maze.goal)?;
+        assert_eq!(format!("{:?}", restored), format!("{:?}", maze));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_wall_grids() {
+        let origin = Coordinates::new(0, 0);
+        let open = CellWalls {
+            east: false,
+            ..CellWalls::closed()
+        };
+
+        assert_eq!(
+            Maze::from_wall_grid(&[], origin, origin).err(),
+            Some(WallGridError::Empty)
+        );
+        assert_eq!(
+            Maze::from_wall_grid(&[vec![open]], origin, origin).err(),
+            Some(WallGridError::OpenBorder(origin, Direction::East))
+        );
+        assert_eq!(
+            Maze::from_wall_grid(&[vec![open, CellWalls::closed()]], origin, origin).err(),
+            Some(WallGridError::InconsistentWall(origin, Direction::East))
+        );
+    }
+}
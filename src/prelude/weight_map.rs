@@ -0,0 +1,133 @@
+use crate::prelude::fingerprint::stable_hash;
+use crate::prelude::*;
+
+/// How strongly generators prefer carving into each field of a maze
+///
+/// Designers can use this to shape areas of a maze, e.g. to let the passages flow towards a
+/// region or to keep a generator from rushing through another one.
+/// Whenever a generator chooses between fields to carve into next, a field with twice the weight
+/// is twice as likely to be chosen.
+/// Fields which are not covered by the map have a weight of `1`, negative weights count as `0`
+/// and if all candidates have a weight of `0` they are chosen uniformly.
+///
+/// It is supported by [`RbGenerator`](crate::recursive_backtracking::RbGenerator),
+/// [`GrowingTreeGenerator`](crate::growing_tree::GrowingTreeGenerator),
+/// [`PrimsGenerator`](crate::prims_algorithm::PrimsGenerator) and
+/// [`EllersGenerator`](crate::ellers_algorithm::EllersGenerator), which scales its chances of
+/// carving into a field instead.
+///
+/// # Examples
+/// ```
+/// use maze_generator::prelude::*;
+/// use maze_generator::recursive_backtracking::RbGenerator;
+///
+/// // let the corridors run towards the eastern half first
+/// let map = WeightMap::from_fn(20, 10, |c| if c.x >= 10 { 5.0 } else { 1.0 });
+/// let maze = RbGenerator::new(Some([42; 32]))
+///     .with_weight_map(map)
+///     .generate(20, 10)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightMap {
+    width: i32,
+    height: i32,
+    /// Row-major weights of all fields
+    weights: Vec<f64>,
+}
+
+impl WeightMap {
+    /// Create a map of `width` x `height` fields which all have a weight of `1`
+    #[must_use]
+    pub fn new(width: i32, height: i32) -> Self {
+        WeightMap::from_fn(width, height, |_| 1.0)
+    }
+
+    /// Create a map of `width` x `height` fields whose weights are given by `weight`
+    #[must_use]
+    pub fn from_fn(width: i32, height: i32, weight: impl Fn(Coordinates) -> f64) -> Self {
+        let weights = RectangularTopology::new(width, height)
+            .cells()
+            .into_iter()
+            .map(|coordinates| weight(coordinates).max(0.0))
+            .collect();
+        WeightMap {
+            width: width.max(0),
+            height: height.max(0),
+            weights,
+        }
+    }
+
+    /// The weight of the field at `coordinates`, `1` for fields outside of the map
+    #[must_use]
+    pub fn weight(&self, coordinates: &Coordinates) -> f64 {
+        self.index(coordinates)
+            .map_or(1.0, |i_field| self.weights[i_field])
+    }
+
+    /// Change the weight of the field at `coordinates`, which is ignored outside of the map
+    pub fn set(&mut self, coordinates: &Coordinates, weight: f64) {
+        if let Some(i_field) = self.index(coordinates) {
+            self.weights[i_field] = weight.max(0.0);
+        }
+    }
+
+    /// Change the weight of all fields of the map inside the rectangle spanned by the corners
+    /// `a` and `b`, including both
+    pub fn set_region(&mut self, a: &Coordinates, b: &Coordinates, weight: f64) {
+        for y in a.y.min(b.y)..=a.y.max(b.y) {
+            for x in a.x.min(b.x)..=a.x.max(b.x) {
+                self.set(&Coordinates::new(x, y), weight);
+            }
+        }
+    }
+
+    /// Stable hash of the map for recording it in the [`Provenance`] of a maze
+    pub(crate) fn fingerprint(&self) -> u64 {
+        let mut bytes = Vec::with_capacity(8 + 8 * self.weights.len());
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        for weight in self.weights.iter() {
+            bytes.extend_from_slice(&weight.to_bits().to_le_bytes());
+        }
+        stable_hash(&bytes)
+    }
+
+    fn index(&self, coordinates: &Coordinates) -> Option<usize> {
+        if RectangularTopology::new(self.width, self.height).contains(coordinates) {
+            Some((coordinates.y * self.width + coordinates.x) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Coordinates, WeightMap};
+
+    #[test]
+    fn test_weights() {
+        let mut map = WeightMap::new(4, 3);
+        map.set_region(&Coordinates::new(3, 2), &Coordinates::new(2, 1), 4.0);
+        map.set(&Coordinates::new(0, 0), -1.0);
+        map.set(&Coordinates::new(9, 9), 2.0);
+
+        assert_eq!(map.weight(&Coordinates::new(0, 0)), 0.0);
+        assert_eq!(map.weight(&Coordinates::new(1, 1)), 1.0);
+        assert_eq!(map.weight(&Coordinates::new(2, 1)), 4.0);
+        assert_eq!(map.weight(&Coordinates::new(3, 2)), 4.0);
+        assert_eq!(map.weight(&Coordinates::new(9, 9)), 1.0);
+        assert_eq!(map.weight(&Coordinates::new(-1, 0)), 1.0);
+
+        let same = WeightMap::from_fn(4, 3, |c| match (c.x, c.y) {
+            (0, 0) => 0.0,
+            (x, y) if x >= 2 && y >= 1 => 4.0,
+            _ => 1.0,
+        });
+        assert_eq!(same, map);
+        assert_eq!(same.fingerprint(), map.fingerprint());
+        assert_ne!(WeightMap::new(4, 3).fingerprint(), map.fingerprint());
+    }
+}
@@ -20,21 +20,98 @@
 //! *Explanation and credits to
 //! [Jamis Buck's Buckblog](http://weblog.jamisbuck.org/2011/1/10/maze-generation-prim-s-algorithm.html)*
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::HashMap;
 
-use anyhow::{Context, Result};
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 
+use crate::builder::Algorithm;
 use crate::prelude::*;
 
 /// [`Generator`] implementation which uses the recursive-backtracking algorithm.
 #[derive(Debug, Clone)]
 pub struct PrimsGenerator {
+    seed: Seed,
     rng: ChaChaRng,
+    /// Where start and goal are placed, default: [`GoalPlacement::Anywhere`]
+    pub goal_placement: GoalPlacement,
+    /// Start carving at a random field instead of the start field, default: false
+    ///
+    /// This removes the bias of the maze texture towards the start field which otherwise lies in
+    /// the north-western corner.
+    /// The position of the start field itself is not affected.
+    pub random_carving_start: bool,
     frontier: Vec<Coordinates>,
-    visited: Vec<Coordinates>,
+    frontier_positions: HashMap<Coordinates, usize>,
     neighbours: Vec<Coordinates>,
+    weight_map: Option<WeightMap>,
+}
+
+/// Progress of carving the passages of a maze which can be paused after every passage
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Carving {
+    rng: RngCheckpoint,
+    /// The field at which carving started, which is the only visited field without passages
+    origin: Coordinates,
+    frontier: Vec<Coordinates>,
+    /// Position of every frontier cell inside of `frontier`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frontier_positions: HashMap<Coordinates, usize>,
+    /// Whether the carving is known to belong to its maze, which is only unknown after it was
+    /// deserialized
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checked: bool,
+}
+
+impl Carving {
+    /// Whether the carving can be continued on `maze` without panicking
+    fn is_valid_for(&mut self, maze: &Maze) -> bool {
+        if !self.checked {
+            self.frontier_positions = self
+                .frontier
+                .iter()
+                .enumerate()
+                .map(|(idx, coordinates)| (*coordinates, idx))
+                .collect();
+            self.checked = maze.passages.size() == maze.size
+                && maze.are_coordinates_inside(&self.origin)
+                && self.frontier_positions.len() == self.frontier.len()
+                && self
+                    .frontier
+                    .iter()
+                    .all(|coordinates| maze.are_coordinates_inside(coordinates));
+        }
+        self.checked
+    }
+}
+
+/// A maze which a [`PrimsGenerator`] has only partially carved, see [`PrimsGenerator::start()`]
+///
+/// Pausing makes it possible to spread the generation of huge mazes over multiple sessions.
+/// With the `serde` feature the state can be serialized to continue the generation in another
+/// process or on another machine.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrimsState {
+    maze: Maze,
+    carving: Carving,
+}
+
+impl PrimsState {
+    /// The maze with all passages carved so far.
+    ///
+    /// Start and goal are only placed once the generation is finished.
+    #[must_use]
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Whether all passages have been carved
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.carving.frontier.is_empty()
+    }
 }
 
 impl PrimsGenerator {
@@ -43,135 +120,301 @@ impl PrimsGenerator {
     /// Optionally a 32 bit seed can be provided to seed the internal random generator.
     /// Giving a seed results in identical mazes being generated which omitting it sources the
     /// random generator from entropy.
-    pub fn new(seed: Option<[u8; 32]>) -> PrimsGenerator {
+    #[must_use]
+    pub fn new(seed: Option<Seed>) -> PrimsGenerator {
+        // the seed is remembered so that paused generations can restore the random generator
+        let seed = seed.unwrap_or_else(random);
         PrimsGenerator {
-            rng: match seed {
-                None => ChaChaRng::from_entropy(),
-                Some(seed) => ChaChaRng::from_seed(seed),
-            },
+            seed,
+            rng: RngCheckpoint::seeded(seed),
+            goal_placement: GoalPlacement::default(),
+            random_carving_start: false,
             frontier: Vec::new(),
-            visited: Vec::new(),
+            frontier_positions: HashMap::new(),
             neighbours: Vec::new(),
+            weight_map: None,
         }
     }
 
-    /// Core algorithm implementation
+    /// Prefer connecting frontier cells with a higher weight in `map`, so that the maze grows
+    /// into those areas first.
+    ///
+    /// Choosing among all frontier cells by weight takes time proportional to the size of the
+    /// frontier for every passage, which makes generation noticeably slower for large mazes.
+    #[must_use]
+    pub fn with_weight_map(mut self, map: WeightMap) -> Self {
+        self.weight_map = Some(map);
+        self
+    }
+
+    /// Start generating a maze which is carved a limited number of passages at a time via
+    /// [`resume()`](PrimsGenerator::resume).
+    ///
+    /// Once [`finish()`](PrimsGenerator::finish)ed, the maze is the same one which
+    /// [`generate()`](Generator::generate) would have returned, regardless of how often the
+    /// generation was paused.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::prims_algorithm::PrimsGenerator;
     ///
-    /// Carves passages in all directions in random order from the current coordinates but only
-    /// if the field in that direction has not yet been processed.
+    /// let mut generator = PrimsGenerator::new(Some([42; 32]));
+    /// let mut state = generator.start(20, 20).unwrap();
+    /// generator.resume(&mut state, 150).unwrap();
+    /// assert!(!state.is_complete());
+    ///
+    /// let maze = generator.finish(state).unwrap();
+    /// assert_eq!(maze, PrimsGenerator::new(Some([42; 32])).generate(20, 20).unwrap());
+    /// ```
+    pub fn start(&mut self, width: i32, height: i32) -> Result<PrimsState, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        maze.provenance = Some(self.provenance(None));
+        let carving_start = self.carving_start(&maze);
+        Ok(PrimsState {
+            carving: self.begin_carving(&maze, carving_start),
+            maze,
+        })
+    }
+
+    /// Carve at most `passages` further passages of a paused generation.
     ///
-    /// Returns coordinates of the goal field
-    fn carve_passages_from(
+    /// Returns whether all passages have been carved.
+    /// The generator must be configured like the one which started the generation, while its
+    /// random generator is replaced so that it continues where the state left off.
+    pub fn resume(&mut self, state: &mut PrimsState, passages: usize) -> Result<bool, MazeError> {
+        if !state.carving.is_valid_for(&state.maze) {
+            return Err(MazeError::InvalidState);
+        }
+        self.carve(&mut state.maze, &mut state.carving, passages)?;
+        Ok(state.is_complete())
+    }
+
+    /// Carve all remaining passages of a paused generation and place start and goal
+    pub fn finish(&mut self, mut state: PrimsState) -> Result<Maze, MazeError> {
+        self.resume(&mut state, usize::MAX)?;
+        self.place_goal(&mut state.maze, state.carving);
+        Ok(state.maze)
+    }
+
+    /// Choose the field at which carving starts
+    fn carving_start(&mut self, maze: &Maze) -> Coordinates {
+        if self.random_carving_start {
+            maze.random_coordinates(&mut self.rng)
+        } else {
+            maze.start
+        }
+    }
+
+    /// Provenance of a maze which is about to be generated, optionally from a given start
+    fn provenance(&self, start: Option<Coordinates>) -> Provenance {
+        let options = (
+            self.goal_placement,
+            self.random_carving_start,
+            self.weight_map.as_ref().map(WeightMap::fingerprint),
+            start,
+        );
+        Provenance::new(Algorithm::Prims, self.seed, &self.rng, &options)
+    }
+
+    /// Prepare carving passages starting at `start_coordinates`
+    fn begin_carving(&mut self, maze: &Maze, start_coordinates: Coordinates) -> Carving {
+        let mut carving = Carving {
+            rng: RngCheckpoint::new(self.seed, &self.rng),
+            origin: start_coordinates,
+            frontier: std::mem::take(&mut self.frontier),
+            frontier_positions: std::mem::take(&mut self.frontier_positions),
+            checked: true,
+        };
+        carving.frontier.clear();
+        carving.frontier_positions.clear();
+
+        // Mark our starting cell as 'in' and find its frontier
+        Self::mark_cell(maze, &mut carving, &mut self.neighbours, start_coordinates);
+        carving
+    }
+
+    /// Carve at most `limit` passages, continuing with the random generator of `carving`
+    fn carve(
         &mut self,
         maze: &mut Maze,
-        current_coordinates: Coordinates,
-    ) -> Result<()> {
-        // Mark our starting cell as 'in' and find its frontier
-        self.mark_cell(maze, current_coordinates)
-            .with_context(|| String::from("Could not parse passages"))?;
+        carving: &mut Carving,
+        limit: usize,
+    ) -> Result<(), MazeError> {
+        self.seed = carving.rng.seed();
+        self.rng = carving.rng.restore();
+        let result = self.carve_passages(maze, carving, limit);
+        carving.rng = RngCheckpoint::new(self.seed, &self.rng);
+        result
+    }
+
+    /// Core algorithm implementation
+    ///
+    /// Connects random frontier cells to the visited cells until there are no more frontier
+    /// cells or `limit` passages were carved.
+    fn carve_passages(
+        &mut self,
+        maze: &mut Maze,
+        carving: &mut Carving,
+        limit: usize,
+    ) -> Result<(), MazeError> {
+        let mut carved = 0;
 
-        while !self.frontier.is_empty() {
+        while !carving.frontier.is_empty() && carved < limit {
             // Choose a random frontier cell
-            let next_coords = self.frontier[self.rng.gen_range(0, self.frontier.len())];
+            let next_coords = self.choose_frontier_cell(&carving.frontier);
 
             // Choose a random 'in' neighbour of that cell
-            self.find_visited_neighbours(maze, next_coords);
+            self.find_visited_neighbours(maze, carving.origin, next_coords);
             if !self.neighbours.is_empty() {
-                let ncell = self.neighbours[self.rng.gen_range(0, self.neighbours.len())]; // neighbours is  aways non-zero length
-                maze.graph.add_edge(next_coords, ncell, ()); // Knock down the wall between them
-                self.mark_cell(maze, next_coords)
-                    .with_context(|| "Could not parse passages")?; // frontier cell is now 'in'
+                let ncell = self.neighbours[gen_index(&mut self.rng, self.neighbours.len())]; // neighbours is  aways non-zero length
+                maze.add_passage(next_coords, ncell); // Knock down the wall between them
+                Self::mark_cell(maze, carving, &mut self.neighbours, next_coords); // frontier cell is now 'in'
+                carved += 1;
             } else {
-                // No neighbours - panic
-                self.frontier.clear(); // Will cause a non-panic return but the maze will be incomplete
-                eprintln!("No neighbours! {:?}", next_coords);
+                // every frontier cell is adjacent to a visited one so this is a bug
+                let missing = maze
+                    .coordinates()
+                    .filter(|c| *c != carving.origin && maze.passage_count(*c) == 0)
+                    .collect();
+                return Err(MazeError::IncompleteMaze { missing });
             }
         }
 
         Ok(())
     }
 
-    /// Mark a cell as visited and it's unvisited neighbours as frontier cells
-    fn mark_cell(&mut self, maze: &mut Maze, current_coordinates: Coordinates) -> Result<()> {
-        // Mark the current cell as visited
-        if !self.visited.contains(&current_coordinates) {
-            self.visited.push(current_coordinates);
-        }
+    /// Choose the next frontier cell to connect, weighted by the weight map
+    fn choose_frontier_cell(&mut self, frontier: &[Coordinates]) -> Coordinates {
+        let rng = &mut self.rng;
+        let weighted = self.weight_map.as_ref().and_then(|map| {
+            frontier
+                .choose_weighted(rng, |cell| map.weight(cell))
+                .ok()
+                .copied()
+        });
+        // all weights being zero is treated like no weight map at all
+        weighted.unwrap_or_else(|| frontier[gen_index(&mut self.rng, frontier.len())])
+    }
+
+    /// Place start and goal of a completely carved maze
+    fn place_goal(&mut self, maze: &mut Maze, carving: Carving) {
+        // place start and goal as far apart as possible
+        let (start, goal, _) = maze.longest_path();
+        maze.start = start;
+        maze.goal = goal;
+        self.goal_placement.apply(maze);
+        self.keep_buffers(carving);
+    }
+
+    /// Keep the allocations of a finished carving for the next maze
+    fn keep_buffers(&mut self, carving: Carving) {
+        self.frontier = carving.frontier;
+        self.frontier_positions = carving.frontier_positions;
+    }
 
+    /// Mark a cell as visited and it's unvisited neighbours as frontier cells
+    ///
+    /// Every visited field except the one at which carving started has a passage, so the
+    /// passages are all that needs to be remembered about visited fields.
+    /// `neighbours` is only used as a buffer.
+    fn mark_cell(
+        maze: &Maze,
+        carving: &mut Carving,
+        neighbours: &mut Vec<Coordinates>,
+        current_coordinates: Coordinates,
+    ) {
         // Mark the current cell as not part of the frontier
-        if self.frontier.contains(&current_coordinates) {
-            let idx = self
-                .frontier
-                .iter()
-                .position(|&r| r == current_coordinates)
-                .ok_or_else(|| {
-                    GenericGeneratorError::InternalError(String::from(
-                        "Could not find coordinates in frontier list",
-                    ))
-                })
-                .with_context(|| "Could not mark cell")?;
-            self.frontier.remove(idx);
+        if let Some(idx) = carving.frontier_positions.remove(&current_coordinates) {
+            carving.frontier.swap_remove(idx);
+            // the previously last cell now takes the place of the removed one
+            if let Some(moved) = carving.frontier.get(idx) {
+                carving.frontier_positions.insert(*moved, idx);
+            }
         }
 
         // Add any unvisited neighbours to the frontier
-        for i_dir in Direction::all().iter() {
-            let next_coords = current_coordinates.next(i_dir);
-            if maze.are_coordinates_inside(&next_coords)
-                && !self.frontier.contains(&next_coords)
-                && !self.visited.contains(&next_coords)
+        maze.topology()
+            .neighbours_into(&current_coordinates, neighbours);
+        for next_coords in neighbours.iter() {
+            if !carving.frontier_positions.contains_key(next_coords)
+                && !is_visited(maze, carving.origin, *next_coords)
             {
-                self.frontier.push(next_coords);
+                carving
+                    .frontier_positions
+                    .insert(*next_coords, carving.frontier.len());
+                carving.frontier.push(*next_coords);
             }
         }
-
-        Ok(())
     }
 
     /// Find the neighbours of this cell that have been visited
-    fn find_visited_neighbours(&mut self, maze: &mut Maze, current_coordinates: Coordinates) {
-        self.neighbours.clear(); // Clear the current neighbour list
-
-        // Look all around, add any visited neighbours to the list
-        for i_dir in Direction::all().iter() {
-            let next_coords = current_coordinates.next(i_dir);
-            if maze.are_coordinates_inside(&next_coords) && self.visited.contains(&next_coords) {
-                self.neighbours.push(next_coords);
-            }
-        }
+    fn find_visited_neighbours(
+        &mut self,
+        maze: &Maze,
+        origin: Coordinates,
+        current_coordinates: Coordinates,
+    ) {
+        // Look all around, keep only the visited neighbours in the list
+        maze.topology()
+            .neighbours_into(&current_coordinates, &mut self.neighbours);
+        self.neighbours
+            .retain(|next_coords| is_visited(maze, origin, *next_coords));
     }
+}
 
-    /// Do breadth-first search for the field which has the most distance
-    // Cloned from ellers_algorithm, but passing in maze rather than using the self.graph element (which we don't have here)
-    fn find_suitable_goal(&self, maze: &mut Maze, start: Coordinates) -> Coordinates {
-        let mut already_visited = HashSet::new();
-        let mut queue: VecDeque<Coordinates> = maze.graph.neighbors(start).collect();
-        let mut last_coords = start;
-
-        while let Some(i_coords) = queue.pop_front() {
-            queue.extend(
-                maze.graph
-                    .neighbors(i_coords)
-                    .filter(|c| !already_visited.contains(c)),
-            );
-            already_visited.insert(i_coords);
-            last_coords = i_coords;
-        }
-
-        last_coords
-    }
+/// Whether carving which started at `origin` already reached `coordinates`
+fn is_visited(maze: &Maze, origin: Coordinates, coordinates: Coordinates) -> bool {
+    coordinates == origin || maze.passage_neighbours(coordinates).next().is_some()
 }
 
 impl Generator for PrimsGenerator {
-    fn generate(&mut self, width: i32, height: i32) -> Result<Maze> {
-        let start = (0, 0).into();
-        let mut maze = Maze::new(width, height, start, (0, 0).into());
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        self.generate_into(&mut maze)?;
+        Ok(maze)
+    }
+
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        ensure_valid_size(maze.size.0, maze.size.1)?;
+        maze.reset();
+        maze.provenance = Some(self.provenance(None));
+        let carving_start = self.carving_start(maze);
+        let mut carving = self.begin_carving(maze, carving_start);
+        self.carve(maze, &mut carving, usize::MAX)?;
+        self.place_goal(maze, carving);
+
+        Ok(())
+    }
 
-        self.carve_passages_from(&mut maze, start)
-            .with_context(|| "Could not generate maze")?;
-        maze.goal = self.find_suitable_goal(&mut maze, start);
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        ensure_start_inside(width, height, start)?;
+        let mut maze = Maze::new(width, height, start, start);
+        maze.provenance = Some(self.provenance(Some(start)));
+        let carving_start = self.carving_start(&maze);
+        let mut carving = self.begin_carving(&maze, carving_start);
+        self.carve(&mut maze, &mut carving, usize::MAX)?;
+        self.keep_buffers(carving);
+        self.goal_placement.apply_keeping_start(&mut maze);
 
         Ok(maze)
     }
+
+    fn info(&self) -> AlgorithmInfo {
+        AlgorithmInfo::new(
+            "Prim's",
+            "O(n)",
+            "Visited fields and the frontier around them, i.e. O(n)",
+            "Many short dead ends radiating from the start with rather short solutions",
+        )
+    }
 }
 
 #[cfg(test)]
@@ -180,4 +423,107 @@ mod test {
     test_route_from_start_to_goal_exists!(super::PrimsGenerator);
     test_all_fields_connected!(super::PrimsGenerator);
     test_generation_is_deterministic!(super::PrimsGenerator);
+    test_generate_into_matches_generate!(super::PrimsGenerator);
+    test_random_carving_start!(super::PrimsGenerator);
+    test_generate_from_keeps_start!(super::PrimsGenerator);
+    test_passages_are_consistent!(super::PrimsGenerator);
+    test_resumed_generation_matches_generate!(super::PrimsGenerator);
+
+    #[test]
+    fn test_goal_differs_from_start_in_two_field_mazes() -> anyhow::Result<()> {
+        use crate::prelude::Generator;
+
+        for &(width, height) in [(2, 1), (1, 2)].iter() {
+            let maze = super::PrimsGenerator::new(Some([0; 32])).generate(width, height)?;
+            assert_ne!(maze.start, maze.goal);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_frontier_holds_the_unvisited_neighbours_of_visited_fields() -> anyhow::Result<()> {
+        use std::collections::HashSet;
+
+        use crate::prelude::Direction;
+
+        let mut generator = super::PrimsGenerator::new(Some([2; 32]));
+        let mut state = generator.start(7, 5)?;
+        loop {
+            let (maze, carving) = (&state.maze, &state.carving);
+            let frontier: HashSet<_> = carving.frontier.iter().copied().collect();
+            assert_eq!(frontier.len(), carving.frontier.len());
+            assert_eq!(carving.frontier_positions.len(), carving.frontier.len());
+            for (idx, coordinates) in carving.frontier.iter().enumerate() {
+                assert_eq!(carving.frontier_positions[coordinates], idx);
+            }
+
+            let expected: HashSet<_> = maze
+                .coordinates()
+                .filter(|c| super::is_visited(maze, carving.origin, *c))
+                .flat_map(|c| {
+                    Direction::all()
+                        .to_vec()
+                        .into_iter()
+                        .map(move |d| c.next(&d))
+                })
+                .filter(|c| {
+                    maze.are_coordinates_inside(c) && !super::is_visited(maze, carving.origin, *c)
+                })
+                .collect();
+            assert_eq!(frontier, expected);
+
+            if generator.resume(&mut state, 1)? {
+                break;
+            }
+        }
+        assert!(state.carving.frontier.is_empty());
+        assert_eq!(state.maze.passages.passage_count(), 7 * 5 - 1);
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tampered_state_is_rejected() -> anyhow::Result<()> {
+        use crate::prelude::MazeError;
+
+        let mut generator = super::PrimsGenerator::new(Some([1; 32]));
+        let mut state = generator.start(6, 6)?;
+        generator.resume(&mut state, 10)?;
+        assert_eq!(state.maze().passages.passage_count(), 10);
+        let json = serde_json::to_value(&state)?;
+
+        let mut outside = json.clone();
+        outside["carving"]["frontier"][0]["x"] = 6.into();
+        let mut outside: super::PrimsState = serde_json::from_value(outside)?;
+        assert_eq!(
+            generator.resume(&mut outside, 1),
+            Err(MazeError::InvalidState)
+        );
+
+        let mut truncated = json;
+        truncated["maze"]["passages"]["blocks"] = serde_json::Value::Array(vec![]);
+        assert!(serde_json::from_value::<super::PrimsState>(truncated).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_weight_map_orders_growth() -> anyhow::Result<()> {
+        use crate::prelude::{Coordinates, WeightMap};
+
+        // fields in the eastern half are only connected once the western half is complete
+        let map = WeightMap::from_fn(10, 6, |c| if c.x < 5 { 1.0 } else { 0.0 });
+        let mut generator = super::PrimsGenerator::new(Some([2; 32])).with_weight_map(map);
+        let mut state = generator.start(10, 6)?;
+        generator.resume(&mut state, 5 * 6 - 1)?;
+        for x in 0..10 {
+            for y in 0..6 {
+                let carved = state.maze().passage_count(Coordinates::new(x, y)) > 0;
+                assert_eq!(carved, x < 5);
+            }
+        }
+
+        let maze = generator.finish(state)?;
+        assert!(maze.unreachable_cells().is_empty());
+        Ok(())
+    }
 }
@@ -0,0 +1,315 @@
+//! Key-and-door puzzles
+//!
+//! A [`PuzzleMaze`] locks the route from start to goal behind doors.
+//! The key for every door lies in a side branch which can only be reached after opening the
+//! previous door, so all doors have to be opened in a fixed order and the player is forced to
+//! explore the maze instead of walking straight to the goal.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::prelude::*;
+//! use maze_generator::puzzle::PuzzleMaze;
+//! use maze_generator::recursive_backtracking::RbGenerator;
+//!
+//! let maze = RbGenerator::new(Some([42; 32])).generate(10, 10).unwrap();
+//! let puzzle = PuzzleMaze::new(maze, 3).unwrap();
+//!
+//! assert_eq!(puzzle.doors.len(), 3);
+//! assert_eq!(puzzle.solve(), Some(vec![0, 1, 2]));
+//! assert!(puzzle.validate().is_ok());
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Errors which can occur while creating or validating a [`PuzzleMaze`]
+#[derive(Error, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PuzzleError {
+    /// The maze does not have enough side branches along its solution to hide all keys
+    #[error("Only {possible} of the {requested} requested doors can be placed in this maze")]
+    NotEnoughSideBranches {
+        /// Number of doors which were requested
+        requested: usize,
+        /// Maximum number of doors which can be placed
+        possible: usize,
+    },
+    /// A door is placed where there is no passage
+    #[error("There is no passage for the door between {0} and {1}")]
+    DoorWithoutPassage(Coordinates, Coordinates),
+    /// A key lies outside of the maze
+    #[error("The key at {0} lies outside of the maze")]
+    KeyOutside(Coordinates),
+    /// No key opens the door with the given index
+    #[error("There is no key for door {0}")]
+    MissingKey(usize),
+    /// The goal can not be reached even when collecting all reachable keys
+    #[error("The goal can not be reached")]
+    Unsolvable,
+}
+
+/// A locked passage
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Door {
+    /// The two adjacent fields between which the door is placed
+    pub between: (Coordinates, Coordinates),
+}
+
+/// A key which opens exactly one door
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Key {
+    /// Field on which the key lies
+    pub position: Coordinates,
+    /// Index of the door in [`PuzzleMaze::doors`] which this key opens
+    pub door: usize,
+}
+
+/// A maze with doors along its solution whose keys have to be collected first
+#[derive(Debug, Clone)]
+pub struct PuzzleMaze {
+    /// The underlying maze
+    pub maze: Maze,
+    /// All doors, ordered from the start to the goal
+    pub doors: Vec<Door>,
+    /// All keys, ordered like the doors which they open
+    pub keys: Vec<Key>,
+}
+
+impl PuzzleMaze {
+    /// Place `door_count` doors and their keys into `maze`.
+    ///
+    /// The doors are spread evenly along the route from start to goal.
+    /// Every key is placed at the deepest point of a side branch between the previous door and its
+    /// own door.
+    /// `maze` should be a perfect maze, as produced by all generators, because loops could lead
+    /// around the doors.
+    pub fn new(maze: Maze, door_count: usize) -> Result<Self, PuzzleError> {
+        let path = maze
+            .find_path(maze.start, maze.goal)
+            .ok_or(PuzzleError::Unsolvable)?;
+        let branches = side_branches(&maze, &path);
+
+        // maximum number of doors which can still be placed when the current section of the path
+        // starts at each index
+        let capacity: Vec<usize> = (0..=path.len())
+            .map(|i| {
+                let mut count = 0;
+                let mut section_start = i;
+                while let Some(door) = first_possible_door(&branches, section_start) {
+                    count += 1;
+                    section_start = door + 1;
+                }
+                count
+            })
+            .collect();
+        if door_count > capacity[0] {
+            return Err(PuzzleError::NotEnoughSideBranches {
+                requested: door_count,
+                possible: capacity[0],
+            });
+        }
+
+        let mut doors = Vec::with_capacity(door_count);
+        let mut keys = Vec::with_capacity(door_count);
+        let mut section_start = 0;
+        for i_door in 0..door_count {
+            let remaining = door_count - i_door - 1;
+            let target = (i_door + 1) * (path.len() - 1) / (door_count + 1);
+            // the door is placed between path[door] and path[door + 1], as close to its evenly
+            // spaced target as possible while leaving room for the remaining doors
+            let earliest =
+                first_possible_door(&branches, section_start).ok_or(PuzzleError::Unsolvable)?;
+            let latest = (earliest..path.len() - 1)
+                .rev()
+                .find(|door| capacity[door + 1] >= remaining)
+                .unwrap_or(earliest);
+            let door = target.clamp(earliest, latest);
+
+            let position = branches[section_start..=door]
+                .iter()
+                .flatten()
+                .max_by_key(|(_, depth)| *depth)
+                .map(|(position, _)| *position)
+                .ok_or(PuzzleError::Unsolvable)?;
+
+            doors.push(Door {
+                between: (path[door], path[door + 1]),
+            });
+            keys.push(Key {
+                position,
+                door: i_door,
+            });
+            section_start = door + 1;
+        }
+
+        Ok(PuzzleMaze { maze, doors, keys })
+    }
+
+    /// Play through the puzzle by repeatedly collecting all reachable keys and opening the
+    /// matching doors.
+    ///
+    /// Returns the indices of the doors in the order in which they can be opened or `None` if
+    /// the goal can not be reached.
+    /// Doors which can be opened at the same time are ordered by their index.
+    #[must_use]
+    pub fn solve(&self) -> Option<Vec<usize>> {
+        let mut opened = Vec::new();
+        let mut is_open = vec![false; self.doors.len()];
+        let locked = |is_open: &[bool]| -> HashSet<(Coordinates, Coordinates)> {
+            self.doors
+                .iter()
+                .zip(is_open)
+                .filter(|(_, open)| !**open)
+                .flat_map(|(door, _)| {
+                    let (a, b) = door.between;
+                    vec![(a, b), (b, a)]
+                })
+                .collect()
+        };
+
+        loop {
+            let reachable = self.reachable(&locked(&is_open));
+            if reachable.contains(&self.maze.goal) {
+                return Some(opened);
+            }
+
+            let mut newly_opened: Vec<usize> = self
+                .keys
+                .iter()
+                .filter(|key| reachable.contains(&key.position))
+                .map(|key| key.door)
+                .filter(|door| is_open.get(*door) == Some(&false))
+                .collect();
+            if newly_opened.is_empty() {
+                return None;
+            }
+            newly_opened.sort_unstable();
+            newly_opened.dedup();
+            for door in newly_opened {
+                is_open[door] = true;
+                opened.push(door);
+            }
+        }
+    }
+
+    /// All fields which can be reached from the start without passing a locked passage
+    fn reachable(&self, locked: &HashSet<(Coordinates, Coordinates)>) -> HashSet<Coordinates> {
+        let mut reachable = HashSet::new();
+        let mut queue = VecDeque::new();
+        reachable.insert(self.maze.start);
+        queue.push_back(self.maze.start);
+
+        while let Some(i_coords) = queue.pop_front() {
+            for i_next in self.maze.passage_neighbours(i_coords) {
+                if !locked.contains(&(i_coords, i_next)) && reachable.insert(i_next) {
+                    queue.push_back(i_next);
+                }
+            }
+        }
+
+        reachable
+    }
+
+    /// Check that all doors and keys are placed sensibly and that the puzzle can be solved
+    pub fn validate(&self) -> Result<(), PuzzleError> {
+        for door in self.doors.iter() {
+            let (a, b) = door.between;
+            if !self.maze.has_passage_between(a, b) {
+                return Err(PuzzleError::DoorWithoutPassage(a, b));
+            }
+        }
+        for key in self.keys.iter() {
+            if !self.maze.are_coordinates_inside(&key.position) {
+                return Err(PuzzleError::KeyOutside(key.position));
+            }
+        }
+        if let Some(door) =
+            (0..self.doors.len()).find(|door| !self.keys.iter().any(|key| key.door == *door))
+        {
+            return Err(PuzzleError::MissingKey(door));
+        }
+
+        self.solve().map(|_| ()).ok_or(PuzzleError::Unsolvable)
+    }
+}
+
+/// For every field of `path`, all fields of the side branches leaving it together with their
+/// distance from the path
+fn side_branches(maze: &Maze, path: &[Coordinates]) -> Vec<Vec<(Coordinates, usize)>> {
+    let on_path: HashSet<_> = path.iter().copied().collect();
+    let mut visited = HashSet::new();
+
+    path.iter()
+        .map(|root| {
+            let mut branch = Vec::new();
+            let mut queue = VecDeque::new();
+            queue.push_back((*root, 0));
+            while let Some((i_coords, depth)) = queue.pop_front() {
+                for i_next in maze.passage_neighbours(i_coords) {
+                    if !on_path.contains(&i_next) && visited.insert(i_next) {
+                        branch.push((i_next, depth + 1));
+                        queue.push_back((i_next, depth + 1));
+                    }
+                }
+            }
+            branch
+        })
+        .collect()
+}
+
+/// The earliest index `door` so that a door between `path[door]` and `path[door + 1]` has a side
+/// branch for its key in the section starting at `section_start`
+fn first_possible_door(
+    branches: &[Vec<(Coordinates, usize)>],
+    section_start: usize,
+) -> Option<usize> {
+    // the last field of the path is the goal which can not be followed by a door
+    (section_start..branches.len().saturating_sub(1)).find(|i| !branches[*i].is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PuzzleError, PuzzleMaze};
+    use crate::prelude::Generator;
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_doors_open_in_order() -> anyhow::Result<()> {
+        for seed in 0..10 {
+            let maze = PrimsGenerator::new(Some([seed; 32])).generate(12, 12)?;
+            let puzzle = PuzzleMaze::new(maze, 4)?;
+
+            puzzle.validate()?;
+            assert_eq!(puzzle.solve(), Some(vec![0, 1, 2, 3]));
+            // no key lies on the direct route
+            let path = puzzle.maze.find_path(puzzle.maze.start, puzzle.maze.goal);
+            assert!(path
+                .is_some_and(|path| puzzle.keys.iter().all(|key| !path.contains(&key.position))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_puzzles_are_detected() -> anyhow::Result<()> {
+        let maze = PrimsGenerator::new(Some([1; 32])).generate(8, 8)?;
+        let mut puzzle = PuzzleMaze::new(maze.clone(), 2)?;
+
+        // a key behind its own door can never be collected
+        puzzle.keys[1].position = puzzle.maze.goal;
+        assert_eq!(puzzle.validate(), Err(PuzzleError::Unsolvable));
+
+        puzzle.keys.pop();
+        assert_eq!(puzzle.validate(), Err(PuzzleError::MissingKey(1)));
+
+        assert!(matches!(
+            PuzzleMaze::new(maze, 1000).err(),
+            Some(PuzzleError::NotEnoughSideBranches {
+                requested: 1000,
+                ..
+            })
+        ));
+        Ok(())
+    }
+}
@@ -1,26 +1,54 @@
 //! Recursive-Backtracking algorithm implementation
 //!
 //! Recursive backtracking is fast, easy to understand and straightforward.
-//! Its downsides are relatively large memory and stack size requirements.
+//! Its downside is that it remembers the whole path back to the starting point, which can
+//! contain every field of the maze.
+//! This path is kept on an explicit stack rather than the call stack, so even very large mazes
+//! can be generated without overflowing it.
 //!
 //! The algorithm works as follows:
 //!
-//! 1. Choose a starting point in the field (in this implementation 0,0) and make it the current cell
+//! 1. Choose a starting point in the field (the start field, or a random field if
+//!    [`random_carving_start`](RbGenerator::random_carving_start) is set) and make it the
+//!    current cell
 //! 2. Randomly choose a direction, check if the field in that direction has not yet been visited.
-//!     If that is the case, make the cell in that direction the new current cell and carve a passage between the two.
+//!    If that is the case, make the cell in that direction the new current cell and carve a passage between the two.
 //! 3. If all adjacent fields have been visited, back up to the last field with unvisited neighbors.
 //! 4. The algorithm terminates when it has backed up all the way to the starting point.
 
-use anyhow::Result;
+use std::collections::HashSet;
+use std::fmt;
+
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
 
+use crate::builder::Algorithm;
 use crate::prelude::*;
 
 /// [`Generator`] implementation which uses the recursive-backtracking algorithm.
 #[derive(Debug, Clone)]
 pub struct RbGenerator {
+    seed: Seed,
     rng: ChaChaRng,
+    /// Where start and goal are placed, default: [`GoalPlacement::Anywhere`]
+    pub goal_placement: GoalPlacement,
+    /// Start carving at a random field instead of the start field, default: false
+    ///
+    /// This removes the bias of the maze texture towards the start field which otherwise lies in
+    /// the north-western corner.
+    /// The position of the start field itself is not affected.
+    pub random_carving_start: bool,
+    /// Preference for horizontal or vertical corridors, default: no preference
+    pub direction_bias: DirectionBias,
+    /// How strongly to prefer turning over continuing straight ahead, in range `0..=1`,
+    /// default: `None`
+    ///
+    /// `0` carves corridors which run straight for as long as possible while `1` turns at almost
+    /// every field.
+    /// `None` chooses every direction with the same probability, which is similar to `0.5`.
+    /// The [`direction_bias`](RbGenerator::direction_bias) is applied on top of it.
+    pub windiness: Option<f64>,
+    weight_map: Option<WeightMap>,
 }
 
 impl RbGenerator {
@@ -29,67 +57,400 @@ impl RbGenerator {
     /// Optionally a 32 bit seed can be provided to seed the internal random generator.
     /// Giving a seed results in identical mazes being generated which omitting it sources the
     /// random generator from entropy.
-    pub fn new(seed: Option<[u8; 32]>) -> RbGenerator {
+    #[must_use]
+    pub fn new(seed: Option<Seed>) -> RbGenerator {
+        // the seed is remembered so that it can be recorded in the provenance of the mazes
+        let seed = seed.unwrap_or_else(random);
         RbGenerator {
-            rng: match seed {
-                None => ChaChaRng::from_entropy(),
-                Some(seed) => ChaChaRng::from_seed(seed),
-            },
+            seed,
+            rng: RngCheckpoint::seeded(seed),
+            goal_placement: GoalPlacement::default(),
+            random_carving_start: false,
+            direction_bias: DirectionBias::default(),
+            windiness: None,
+            weight_map: None,
         }
     }
 
+    /// Prefer carving into fields with a higher weight in `map`, on top of the
+    /// [`windiness`](RbGenerator::windiness) and [`direction_bias`](RbGenerator::direction_bias).
+    ///
+    /// The map is ignored by [`carve()`](RbGenerator::carve) and
+    /// [`generate_with_obstacles()`](RbGenerator::generate_with_obstacles).
+    #[must_use]
+    pub fn with_weight_map(mut self, map: WeightMap) -> Self {
+        self.weight_map = Some(map);
+        self
+    }
+
+    /// Provenance of a maze which is about to be generated, `placement` being what the maze is
+    /// generated around, like the start given to [`Generator::generate_from()`]
+    fn provenance(&self, placement: &impl fmt::Debug) -> Provenance {
+        let options = (
+            self.goal_placement,
+            self.random_carving_start,
+            self.direction_bias,
+            self.windiness,
+            self.weight_map.as_ref().map(WeightMap::fingerprint),
+            placement,
+        );
+        Provenance::new(
+            Algorithm::RecursiveBacktracking,
+            self.seed,
+            &self.rng,
+            &options,
+        )
+    }
+
     /// Core algorithm implementation
     ///
     /// Carves passages in all directions in random order from the current coordinates but only
     /// if the field in that direction has not yet been processed.
+    /// The backtracking is done with an explicit stack so that the size of the maze is not
+    /// limited by the size of the call stack, drawing the random numbers in the same order as
+    /// a recursive implementation would.
+    ///
+    /// `heading` is the direction in which `start` was entered, if any.
     ///
-    /// Returns coordinates of the goal field
+    /// Returns coordinates of the goal field, which is the first dead end that is reached
     fn carve_passages_from(
         &mut self,
         maze: &mut Maze,
-        current_coordinates: Coordinates,
+        start: Coordinates,
+        heading: Option<Direction>,
     ) -> Coordinates {
-        let mut goal_coords = maze.start;
-        for i_dir in Direction::gen_random_order(&mut self.rng).iter() {
-            let next_coords = current_coordinates.next(i_dir);
-
-            if maze.are_coordinates_inside(&next_coords)
-                && maze.graph.neighbors(next_coords).count() == 0
-            {
-                maze.graph.add_edge(current_coordinates, next_coords, ());
-                if goal_coords == maze.start {
-                    goal_coords = self.carve_passages_from(maze, next_coords);
-                } else {
-                    self.carve_passages_from(maze, next_coords);
+        let mut goal = None;
+        // every entry holds a field, the order in which to try its directions and how many of
+        // them have been tried
+        let mut stack = vec![(start, self.gen_direction_order(start, heading), 0)];
+
+        while let Some(&(current_coordinates, directions, i_dir)) = stack.last() {
+            let direction = match directions.get(i_dir) {
+                Some(direction) => *direction,
+                None => {
+                    goal.get_or_insert(current_coordinates);
+                    stack.pop();
+                    continue;
                 }
+            };
+            if let Some(top) = stack.last_mut() {
+                top.2 += 1;
+            }
+
+            let next_coords = current_coordinates.next(&direction);
+            if maze.are_coordinates_inside(&next_coords) && maze.passage_count(next_coords) == 0 {
+                maze.add_passage(current_coordinates, next_coords);
+                let directions = self.gen_direction_order(next_coords, Some(direction));
+                stack.push((next_coords, directions, 0));
             }
         }
 
-        if goal_coords == maze.start {
-            current_coordinates
-        } else {
-            goal_coords
+        goal.unwrap_or(start)
+    }
+
+    /// The order in which to try the directions from `current`, which was entered into
+    /// `heading`, taking the windiness, direction bias and weight map into account
+    fn gen_direction_order(
+        &mut self,
+        current: Coordinates,
+        heading: Option<Direction>,
+    ) -> [Direction; 4] {
+        let turns = match (self.windiness, heading) {
+            (Some(windiness), Some(heading)) => Some((windiness.clamp(0.0, 1.0), heading)),
+            _ => None,
+        };
+        let weight_map = match (turns, &self.weight_map) {
+            (None, None) => return self.direction_bias.gen_random_order(&mut self.rng),
+            (_, weight_map) => weight_map,
+        };
+        let bias = self.direction_bias;
+        Direction::gen_weighted_order(&mut self.rng, |direction| {
+            let turn_weight = match turns {
+                Some((windiness, heading)) if *direction == heading => 1.0 - windiness,
+                Some((windiness, _)) => windiness,
+                None => 1.0,
+            };
+            let field_weight = weight_map
+                .as_ref()
+                .map_or(1.0, |map| map.weight(&current.next(direction)));
+            turn_weight * bias.weight(direction) * field_weight
+        })
+    }
+
+    /// Run the algorithm on an arbitrary [`Topology`] starting at `start`.
+    ///
+    /// This is how non-rectangular mazes like [`ThetaMaze`](crate::theta::ThetaMaze) are
+    /// generated.
+    /// Like [`generate()`](Generator::generate) the backtracking is done with an explicit stack
+    /// so the size of the topology is not limited by the size of the call stack, but the random
+    /// numbers are drawn differently so that the same seed results in a different maze.
+    ///
+    /// Returns every carved passage in the order in which it was carved.
+    /// Together they form a spanning tree of all fields reachable from `start`.
+    ///
+    /// # Examples
+    /// ```
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let topology = RectangularTopology::new(4, 3);
+    /// let passages = RbGenerator::new(Some([42; 32])).carve(&topology, Coordinates::new(0, 0));
+    /// assert_eq!(passages.len(), topology.cell_count() - 1);
+    /// ```
+    pub fn carve<T: Topology>(&mut self, topology: &T, start: T::Cell) -> Vec<(T::Cell, T::Cell)> {
+        let mut passages = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![start];
+        visited.insert(start);
+
+        while let Some(&current) = stack.last() {
+            let unvisited: Vec<_> = topology
+                .neighbours(&current)
+                .into_iter()
+                .filter(|next| !visited.contains(next))
+                .collect();
+            match unvisited.choose(&mut self.rng) {
+                Some(&next) => {
+                    passages.push((current, next));
+                    visited.insert(next);
+                    stack.push(next);
+                }
+                None => {
+                    stack.pop();
+                }
+            }
         }
+
+        passages
+    }
+
+    /// Generate a maze which leaves the fields in `obstacles` untouched, e.g. to carve a maze
+    /// around fixed features of a map like lakes or buildings.
+    ///
+    /// Blocked fields are still part of the returned maze but have no passages.
+    /// The start is the first free field in row-major order and the goal is the free field which
+    /// is farthest away from it.
+    ///
+    /// All free fields need to be connected to each other, otherwise
+    /// [`ObstacleError::Disconnected`] lists the parts into which the obstacles split the maze.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use maze_generator::prelude::*;
+    /// use maze_generator::recursive_backtracking::RbGenerator;
+    ///
+    /// let lake: HashSet<_> = [(1, 1), (2, 1), (1, 2)].iter().map(|c| Coordinates::from(*c)).collect();
+    /// let maze = RbGenerator::new(Some([42; 32])).generate_with_obstacles(4, 4, &lake).unwrap();
+    ///
+    /// assert!(!maze.has_passage(&Coordinates::new(1, 1), &Direction::North));
+    /// ```
+    pub fn generate_with_obstacles(
+        &mut self,
+        width: i32,
+        height: i32,
+        obstacles: &HashSet<Coordinates>,
+    ) -> Result<Maze, ObstacleError> {
+        let topology = ObstacleTopology::new(width, height, obstacles);
+        let mut components = topology.components();
+        let start = match components.len() {
+            0 => return Err(ObstacleError::NoFreeFields),
+            1 => components.remove(0)[0],
+            _ => return Err(ObstacleError::Disconnected(components)),
+        };
+
+        let mut maze = Maze::new(width, height, start, start);
+        let mut sorted_obstacles: Vec<_> = obstacles.iter().collect();
+        sorted_obstacles.sort();
+        maze.provenance = Some(self.provenance(&sorted_obstacles));
+        for (a, b) in self.carve(&topology, start) {
+            maze.add_passage(a, b);
+        }
+        maze.goal = maze.farthest_from(start).0;
+
+        Ok(maze)
     }
 }
 
 impl Generator for RbGenerator {
-    fn generate(&mut self, width: i32, height: i32) -> Result<Maze> {
-        let start = (0, 0).into();
-        let mut maze = Maze::new(width, height, start, (0, 0).into());
-        maze.graph.add_node(start);
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        ensure_valid_size(width, height)?;
+        let mut maze = Maze::new(width, height, (0, 0).into(), (0, 0).into());
+        self.generate_into(&mut maze)?;
+        Ok(maze)
+    }
+
+    fn generate_into(&mut self, maze: &mut Maze) -> Result<(), MazeError> {
+        ensure_valid_size(maze.size.0, maze.size.1)?;
+        maze.reset();
+        maze.provenance = Some(self.provenance(&()));
+        let start = maze.start;
+        let carving_start = if self.random_carving_start {
+            maze.random_coordinates(&mut self.rng)
+        } else {
+            start
+        };
+
+        let goal = self.carve_passages_from(maze, carving_start, None);
+        maze.goal = if carving_start == start {
+            goal
+        } else {
+            maze.farthest_from(start).0
+        };
+        self.goal_placement.apply(maze);
 
-        let goal = self.carve_passages_from(&mut maze, start);
-        maze.goal = goal;
+        Ok(())
+    }
+
+    fn generate_from(
+        &mut self,
+        width: i32,
+        height: i32,
+        start: Coordinates,
+    ) -> Result<Maze, MazeError> {
+        ensure_start_inside(width, height, start)?;
+        let mut maze = Maze::new(width, height, start, start);
+        maze.provenance = Some(self.provenance(&start));
+        let carving_start = if self.random_carving_start {
+            maze.random_coordinates(&mut self.rng)
+        } else {
+            start
+        };
+
+        self.carve_passages_from(&mut maze, carving_start, None);
+        self.goal_placement.apply_keeping_start(&mut maze);
 
         Ok(maze)
     }
+
+    fn info(&self) -> AlgorithmInfo {
+        AlgorithmInfo::new(
+            "Recursive backtracking",
+            "O(n)",
+            "The current path on an explicit stack, up to O(n) deep",
+            "Long winding passages with few dead ends and long solutions",
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashSet;
+
+    use crate::prelude::{Coordinates, Direction, DirectionBias, Generator, ObstacleError};
+
     test_all_coordinates_have_fields!(super::RbGenerator);
     test_route_from_start_to_goal_exists!(super::RbGenerator);
     test_all_fields_connected!(super::RbGenerator);
     test_generation_is_deterministic!(super::RbGenerator);
+    test_generate_into_matches_generate!(super::RbGenerator);
+    test_random_carving_start!(super::RbGenerator);
+    test_generate_from_keeps_start!(super::RbGenerator);
+    test_passages_are_consistent!(super::RbGenerator);
+
+    #[test]
+    fn test_obstacles_stay_untouched() -> anyhow::Result<()> {
+        let obstacles: HashSet<_> = [(0, 0), (3, 1), (3, 2), (4, 2)]
+            .iter()
+            .map(|c| Coordinates::from(*c))
+            .collect();
+        let maze =
+            super::RbGenerator::new(Some([5; 32])).generate_with_obstacles(6, 4, &obstacles)?;
+
+        assert_eq!(maze.start, Coordinates::new(1, 0));
+        assert!(!obstacles.contains(&maze.goal));
+        for coordinates in obstacles.iter() {
+            assert_eq!(maze.passage_count(*coordinates), 0);
+        }
+        // the free fields form a perfect maze
+        assert_eq!(maze.breadth_first_search(maze.start).len(), 6 * 4 - 4);
+        assert_eq!(maze.passages.passage_count(), 6 * 4 - 4 - 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_obstacles_report_components() {
+        let wall: HashSet<_> = (0..3).map(|iy| Coordinates::new(1, iy)).collect();
+        let mut generator = super::RbGenerator::new(Some([5; 32]));
+
+        // both parts are listed starting with their north-western field
+        assert!(matches!(
+            generator.generate_with_obstacles(3, 3, &wall),
+            Err(ObstacleError::Disconnected(components))
+                if components.len() == 2 && components[1][0] == Coordinates::new(2, 0)
+        ));
+        assert_eq!(
+            generator.generate_with_obstacles(
+                1,
+                1,
+                &[Coordinates::new(0, 0)].iter().copied().collect()
+            ),
+            Err(ObstacleError::NoFreeFields)
+        );
+    }
+
+    #[test]
+    fn test_windiness_controls_turns() -> anyhow::Result<()> {
+        let mut generator = super::RbGenerator::new(Some([17; 32]));
+        let mut turns = Vec::new();
+        for windiness in [0.0, 0.5, 1.0].iter() {
+            generator.windiness = Some(*windiness);
+            let maze = generator.generate(30, 30)?;
+            // fields with two passages which do not lie opposite of each other
+            let count = maze
+                .coordinates()
+                .filter(|c| maze.passage_count(*c) == 2)
+                .filter(|c| {
+                    !maze.has_passage(c, &Direction::North)
+                        || !maze.has_passage(c, &Direction::South)
+                })
+                .filter(|c| {
+                    !maze.has_passage(c, &Direction::East) || !maze.has_passage(c, &Direction::West)
+                })
+                .count();
+            turns.push(count);
+        }
+        assert!(turns[0] * 2 < turns[1]);
+        assert!(turns[1] < turns[2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_direction_bias_favours_corridors() -> anyhow::Result<()> {
+        let mut generator = super::RbGenerator::new(Some([13; 32]));
+        for (bias, horizontal_wins) in [
+            (DirectionBias::new(8.0, 1.0), true),
+            (DirectionBias::new(1.0, 8.0), false),
+        ]
+        .iter()
+        {
+            generator.direction_bias = *bias;
+            let maze = generator.generate(30, 30)?;
+            let count = |direction: &Direction| {
+                maze.coordinates()
+                    .filter(|c| maze.has_passage(c, direction))
+                    .count()
+            };
+            let (horizontal, vertical) = (count(&Direction::East), count(&Direction::South));
+            assert_eq!(horizontal > 2 * vertical, *horizontal_wins);
+            assert_eq!(vertical > 2 * horizontal, !*horizontal_wins);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_weight_map_attracts_corridors() -> anyhow::Result<()> {
+        use crate::prelude::WeightMap;
+
+        // weights growing steeply towards the east make the first row a single corridor
+        let map = WeightMap::from_fn(12, 8, |c| 1000_f64.powi(c.x));
+        for seed in 0..3 {
+            let mut generator =
+                super::RbGenerator::new(Some([seed; 32])).with_weight_map(map.clone());
+            let maze = generator.generate(12, 8)?;
+            assert_eq!(maze.corridor_histogram().longest_run(), 11);
+            assert!((0..11).all(|x| maze.has_passage(&(x, 0).into(), &Direction::East)));
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,298 @@
+//! Mazes as environments for reinforcement learning
+//!
+//! [`MazeEnv`] follows the step/reset interface which is common among reinforcement learning
+//! libraries: an agent starts every episode at the start field of a newly generated maze and
+//! moves through it one field per step until it reaches the goal.
+//! Observations are flat vectors of `f32` values whose layout is chosen via
+//! [`ObservationEncoding`] and whose length stays the same for all steps and episodes, so they
+//! can be fed directly into neural networks.
+//!
+//! The four actions are the [`Direction`]s, which can be mapped from and to discrete action
+//! indices via [`Direction::all()`] and [`Direction::index()`].
+//!
+//! # Examples
+//! ```
+//! use maze_generator::prelude::*;
+//! use maze_generator::prims_algorithm::PrimsGenerator;
+//! use maze_generator::rl::MazeEnv;
+//!
+//! let mut env = MazeEnv::new(PrimsGenerator::new(Some([42; 32])), 5, 5).unwrap();
+//! let mut observation = env.reset().unwrap();
+//! let mut total_reward = 0.0;
+//! for i_action in [0, 1, 2, 3].iter().cycle().take(100) {
+//!     let (next, reward, done) = env.step(Direction::all()[*i_action]);
+//!     assert_eq!(next.len(), observation.len());
+//!     observation = next;
+//!     total_reward += reward;
+//!     if done {
+//!         break;
+//!     }
+//! }
+//! ```
+
+use crate::prelude::*;
+
+/// How the state of a [`MazeEnv`] is encoded as observation
+///
+/// Every encoded field consists of [`ObservationEncoding::CHANNELS`] consecutive values which
+/// are `1.0` or `0.0`: whether a wall exists to the north, east, south and west of the field,
+/// whether the agent is located at the field and whether it is the goal.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum ObservationEncoding {
+    /// All fields of the maze in row-major order, so the agent sees the whole maze
+    #[default]
+    WallBitmap,
+    /// Only the square of fields within `radius` steps in x and y direction of the agent in
+    /// row-major order, so the agent only sees its surroundings
+    ///
+    /// Fields outside of the maze are encoded as enclosed by walls.
+    LocalWindow {
+        /// Number of fields which are visible into every direction
+        radius: usize,
+    },
+}
+
+impl ObservationEncoding {
+    /// Number of values which encode a single field
+    pub const CHANNELS: usize = 6;
+
+    /// Number of values of every observation of a maze of `width` x `height` fields
+    #[must_use]
+    pub fn len(&self, width: i32, height: i32) -> usize {
+        let fields = match self {
+            ObservationEncoding::WallBitmap => width.max(0) as usize * height.max(0) as usize,
+            ObservationEncoding::LocalWindow { radius } => (2 * radius + 1).pow(2),
+        };
+        fields * ObservationEncoding::CHANNELS
+    }
+}
+
+/// Rewards which a [`MazeEnv`] hands out for the actions of the agent
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rewards {
+    /// Reward for the step which reaches the goal, default: `1.0`
+    pub goal: f32,
+    /// Reward for every other step which moved the agent, default: `-0.01`
+    pub step: f32,
+    /// Reward for trying to move through a wall, default: `-0.05`
+    pub blocked: f32,
+}
+
+impl Default for Rewards {
+    fn default() -> Self {
+        Rewards {
+            goal: 1.0,
+            step: -0.01,
+            blocked: -0.05,
+        }
+    }
+}
+
+/// A maze which an agent walks through step by step, see the [module documentation](self)
+#[derive(Debug, Clone)]
+pub struct MazeEnv<G: Generator> {
+    generator: G,
+    /// How observations are encoded, default: [`ObservationEncoding::WallBitmap`]
+    pub encoding: ObservationEncoding,
+    /// What the agent is rewarded with
+    pub rewards: Rewards,
+    /// Number of steps after which an episode ends even if the goal was not reached, default:
+    /// `None`
+    pub max_steps: Option<usize>,
+    maze: Maze,
+    walker: Walker,
+    steps: usize,
+    done: bool,
+}
+
+impl<G: Generator> MazeEnv<G> {
+    /// Create an environment whose episodes take place in mazes of `width` x `height` fields
+    /// which `generator` generates
+    ///
+    /// The first maze is generated right away so that the environment can be stepped through
+    /// without calling [`reset()`](MazeEnv::reset) first.
+    pub fn new(mut generator: G, width: i32, height: i32) -> Result<Self, MazeError> {
+        let maze = generator.generate(width, height)?;
+        Ok(MazeEnv {
+            generator,
+            encoding: ObservationEncoding::default(),
+            rewards: Rewards::default(),
+            max_steps: None,
+            walker: Walker::at_start(&maze),
+            maze,
+            steps: 0,
+            done: false,
+        })
+    }
+
+    /// Start a new episode in a newly generated maze and return the first observation
+    pub fn reset(&mut self) -> Result<Vec<f32>, MazeError> {
+        self.generator.generate_into(&mut self.maze)?;
+        self.walker = Walker::at_start(&self.maze);
+        self.steps = 0;
+        self.done = false;
+        Ok(self.observation())
+    }
+
+    /// Let the agent try to move one field into `action`.
+    ///
+    /// Returns the observation after the move, the reward for it and whether the episode is
+    /// done, either because the goal was reached or because [`max_steps`](MazeEnv::max_steps)
+    /// were taken.
+    /// Once the episode is done, further steps do not change anything and are rewarded with
+    /// `0`.
+    pub fn step(&mut self, action: Direction) -> (Vec<f32>, f32, bool) {
+        if self.done {
+            return (self.observation(), 0.0, true);
+        }
+
+        self.steps += 1;
+        let reward = match self.walker.step(&self.maze, action) {
+            MoveResult::Moved(_) if self.walker.has_reached_goal(&self.maze) => {
+                self.done = true;
+                self.rewards.goal
+            }
+            MoveResult::Moved(_) => self.rewards.step,
+            MoveResult::BlockedByWall | MoveResult::OutOfBounds => self.rewards.blocked,
+        };
+        if self
+            .max_steps
+            .is_some_and(|max_steps| self.steps >= max_steps)
+        {
+            self.done = true;
+        }
+        (self.observation(), reward, self.done)
+    }
+
+    /// Encode the current state as observation, see [`ObservationEncoding`]
+    #[must_use]
+    pub fn observation(&self) -> Vec<f32> {
+        let mut observation = Vec::with_capacity(self.observation_len());
+        match self.encoding {
+            ObservationEncoding::WallBitmap => {
+                for coordinates in self.maze.coordinates() {
+                    self.encode_field(&coordinates, &mut observation);
+                }
+            }
+            ObservationEncoding::LocalWindow { radius } => {
+                let radius = radius as i32;
+                let center = self.walker.position;
+                for y in center.y - radius..=center.y + radius {
+                    for x in center.x - radius..=center.x + radius {
+                        self.encode_field(&Coordinates::new(x, y), &mut observation);
+                    }
+                }
+            }
+        }
+        observation
+    }
+
+    /// Number of values of every observation
+    #[must_use]
+    pub fn observation_len(&self) -> usize {
+        self.encoding.len(self.maze.size.0, self.maze.size.1)
+    }
+
+    /// The maze of the current episode
+    #[must_use]
+    pub fn maze(&self) -> &Maze {
+        &self.maze
+    }
+
+    /// Where the agent currently is
+    #[must_use]
+    pub fn position(&self) -> Coordinates {
+        self.walker.position
+    }
+
+    /// Number of steps taken in the current episode, including those which were blocked
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Append the values of the field at `coordinates` to `observation`
+    fn encode_field(&self, coordinates: &Coordinates, observation: &mut Vec<f32>) {
+        let flag = |value: bool| if value { 1.0 } else { 0.0 };
+        for direction in Direction::iter() {
+            observation.push(flag(!self.maze.has_passage(coordinates, &direction)));
+        }
+        observation.push(flag(*coordinates == self.walker.position));
+        observation.push(flag(*coordinates == self.maze.goal));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MazeEnv, ObservationEncoding, Rewards};
+    use crate::prelude::{Coordinates, Direction};
+    use crate::prims_algorithm::PrimsGenerator;
+
+    #[test]
+    fn test_following_the_solution_reaches_goal() -> anyhow::Result<()> {
+        let mut env = MazeEnv::new(PrimsGenerator::new(Some([33; 32])), 6, 5)?;
+        let observation = env.reset()?;
+        assert_eq!(observation.len(), 6 * 5 * ObservationEncoding::CHANNELS);
+        assert_eq!(env.position(), env.maze().start);
+
+        let maze = env.maze().clone();
+        let path = maze
+            .find_path(maze.start, maze.goal)
+            .ok_or_else(|| anyhow::anyhow!("goal not reachable"))?;
+        let blocked = Direction::iter()
+            .find(|direction| !maze.has_passage(&maze.start, direction))
+            .ok_or_else(|| anyhow::anyhow!("start has no walls"))?;
+        let (_, reward, done) = env.step(blocked);
+        assert_eq!((reward, done), (Rewards::default().blocked, false));
+        assert_eq!(env.position(), maze.start);
+
+        let mut total = 0.0;
+        for step in path.windows(2) {
+            let direction = Direction::iter()
+                .find(|direction| step[0].next(direction) == step[1])
+                .ok_or_else(|| anyhow::anyhow!("fields are not adjacent"))?;
+            let (observation, reward, done) = env.step(direction);
+            total += reward;
+            assert_eq!(done, step[1] == maze.goal);
+
+            // the agent channel of its field is set
+            let i_field = (step[1].y * 6 + step[1].x) as usize;
+            assert_eq!(
+                observation[i_field * ObservationEncoding::CHANNELS + 4],
+                1.0
+            );
+        }
+        let expected = Rewards::default().goal + (path.len() - 2) as f32 * Rewards::default().step;
+        assert!((total - expected).abs() < 1e-5);
+        assert_eq!(env.steps(), path.len());
+
+        // nothing happens after the episode is done
+        assert_eq!(env.step(Direction::North).1, 0.0);
+        assert_eq!(env.position(), maze.goal);
+        Ok(())
+    }
+
+    #[test]
+    fn test_local_window_and_truncation() -> anyhow::Result<()> {
+        let mut env = MazeEnv::new(PrimsGenerator::new(Some([34; 32])), 4, 4)?;
+        env.encoding = ObservationEncoding::LocalWindow { radius: 1 };
+        env.max_steps = Some(3);
+        env.reset()?;
+        env.walker.position = Coordinates::new(0, 0);
+
+        let observation = env.observation();
+        assert_eq!(observation.len(), 9 * ObservationEncoding::CHANNELS);
+        assert_eq!(observation.len(), env.observation_len());
+        // the row above the agent lies outside of the maze and is enclosed by walls
+        assert_eq!(observation[..4], [1.0; 4]);
+        assert_eq!(observation[4 * ObservationEncoding::CHANNELS + 4], 1.0);
+
+        let mut done = false;
+        for _ in 0..3 {
+            done = env.step(Direction::North).2;
+        }
+        assert!(done);
+        assert_eq!(env.steps(), 3);
+        Ok(())
+    }
+}
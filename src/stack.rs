@@ -0,0 +1,263 @@
+//! Multiple levels of mazes connected by stairs
+//!
+//! A [`MazeStack`] lies between a single two-dimensional maze and a fully three-dimensional
+//! one, like the dungeon levels of many roguelikes.
+//! Every level is a regular [`Maze`] and every pair of adjacent levels is connected by a
+//! [`Stair`].
+//! The route from the start on the first level to the goal on the last level therefore
+//! crosses every level, and the start and goal of each level mark where it is entered and
+//! where the stair to the next level lies.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::stack::MazeStack;
+//! use maze_generator::Algorithm;
+//!
+//! let stack = MazeStack::generate(3, 10, 10, Algorithm::Prims, Some([42; 32])).unwrap();
+//! assert_eq!(stack.levels.len(), 3);
+//! assert_eq!(stack.stairs.len(), 2);
+//!
+//! let route = stack.find_path(stack.start, stack.goal).unwrap();
+//! assert_eq!(route.last().map(|step| step.level), Some(2));
+//! ```
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+
+use crate::builder::Algorithm;
+use crate::prelude::*;
+
+/// A field on one of the levels of a [`MazeStack`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LevelCoordinates {
+    /// Index of the level, starting at 0 for the first one
+    pub level: usize,
+    /// Position of the field inside of its level
+    pub coordinates: Coordinates,
+}
+
+impl LevelCoordinates {
+    /// Create a new instance from the index of the level and the position inside of it
+    #[must_use]
+    pub fn new(level: usize, coordinates: Coordinates) -> Self {
+        LevelCoordinates { level, coordinates }
+    }
+}
+
+/// Stair which connects a field of one level with the field at the same position on the next
+/// level
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Stair {
+    /// Index of the upper level, the stair leads down from it to the level `level + 1`
+    pub level: usize,
+    /// Position of the stair on both levels
+    pub position: Coordinates,
+}
+
+/// Levels of mazes of the same size which are connected by stairs, see the
+/// [module documentation](self)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MazeStack {
+    /// All levels from top to bottom
+    pub levels: Vec<Maze>,
+    /// One stair between every pair of adjacent levels, ordered from top to bottom
+    pub stairs: Vec<Stair>,
+    /// Where the route through all levels begins, on the first level
+    pub start: LevelCoordinates,
+    /// Where the route through all levels ends, on the last level
+    pub goal: LevelCoordinates,
+}
+
+impl MazeStack {
+    /// Generate `levels` mazes of the given size with `algorithm` and connect them by stairs.
+    ///
+    /// Every level is entered at its start, which lies below the stair of the previous level,
+    /// and the stair to the next level is placed on the field which is farthest away from it,
+    /// which then becomes the goal of the level.
+    /// The global goal is the goal of the last level.
+    ///
+    /// The seeds of the levels are derived from `seed` so that the whole stack can be
+    /// generated again.
+    pub fn generate(
+        levels: usize,
+        width: i32,
+        height: i32,
+        algorithm: Algorithm,
+        seed: Option<Seed>,
+    ) -> Result<Self, MazeError> {
+        if levels == 0 {
+            return Err(MazeError::NoLevels);
+        }
+        ensure_valid_size(width, height)?;
+
+        let mut rng = ChaChaRng::from_seed(seed.unwrap_or_else(random));
+        let mut mazes = Vec::with_capacity(levels);
+        let mut stairs: Vec<Stair> = Vec::with_capacity(levels - 1);
+        for i_level in 0..levels {
+            let level_seed: Seed = rng.gen();
+            let mut maze = algorithm
+                .generator(Some(level_seed))
+                .generate(width, height)?;
+            if let Some(stair) = stairs.last() {
+                maze.start = stair.position;
+            }
+            maze.goal = maze.farthest_from(maze.start).0;
+            if i_level + 1 < levels {
+                stairs.push(Stair {
+                    level: i_level,
+                    position: maze.goal,
+                });
+            }
+            mazes.push(maze);
+        }
+
+        let start = LevelCoordinates::new(0, mazes[0].start);
+        let goal = LevelCoordinates::new(levels - 1, mazes[levels - 1].goal);
+        Ok(MazeStack {
+            levels: mazes,
+            stairs,
+            start,
+            goal,
+        })
+    }
+
+    /// All fields which can be reached from `at` in a single step, i.e. through a passage or
+    /// over a stair
+    #[must_use]
+    pub fn neighbours(&self, at: LevelCoordinates) -> Vec<LevelCoordinates> {
+        let maze = match self.levels.get(at.level) {
+            Some(maze) if maze.are_coordinates_inside(&at.coordinates) => maze,
+            _ => return Vec::new(),
+        };
+
+        let mut neighbours: Vec<LevelCoordinates> = maze
+            .passage_neighbours(at.coordinates)
+            .map(|coordinates| LevelCoordinates::new(at.level, coordinates))
+            .collect();
+        for stair in self.stairs.iter() {
+            if stair.position != at.coordinates || stair.level + 1 >= self.levels.len() {
+                continue;
+            }
+            if stair.level == at.level {
+                neighbours.push(LevelCoordinates::new(at.level + 1, stair.position));
+            } else if stair.level + 1 == at.level {
+                neighbours.push(LevelCoordinates::new(stair.level, stair.position));
+            }
+        }
+
+        neighbours
+    }
+
+    /// Find the shortest route from `from` to `to` across all levels, including both ends.
+    ///
+    /// Using a stair counts as a single step.
+    /// Returns `None` if `to` can not be reached.
+    #[must_use]
+    pub fn find_path(
+        &self,
+        from: LevelCoordinates,
+        to: LevelCoordinates,
+    ) -> Option<Vec<LevelCoordinates>> {
+        let parents = self.search(from);
+        let mut path = vec![to];
+        let mut current = parents.get(&to)?;
+        while let Some(previous) = current {
+            path.push(*previous);
+            current = parents.get(previous)?;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Breadth-first search starting at `from`, mapping every reachable field to the field
+    /// through which it was reached
+    fn search(
+        &self,
+        from: LevelCoordinates,
+    ) -> HashMap<LevelCoordinates, Option<LevelCoordinates>> {
+        let mut parents = HashMap::new();
+        let inside = self
+            .levels
+            .get(from.level)
+            .is_some_and(|maze| maze.are_coordinates_inside(&from.coordinates));
+        if !inside {
+            return parents;
+        }
+
+        let mut queue = VecDeque::new();
+        parents.insert(from, None);
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            for next in self.neighbours(current) {
+                if let Entry::Vacant(entry) = parents.entry(next) {
+                    entry.insert(Some(current));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        parents
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LevelCoordinates, MazeStack};
+    use crate::builder::Algorithm;
+    use crate::prelude::{Coordinates, MazeError};
+
+    #[test]
+    fn test_levels_are_connected() -> anyhow::Result<()> {
+        for algorithm in Algorithm::ALL.iter() {
+            let stack = MazeStack::generate(4, 6, 5, *algorithm, Some([12; 32]))?;
+            assert_eq!(stack.levels.len(), 4);
+            assert_eq!(stack.start.level, 0);
+            assert_eq!(stack.goal.level, 3);
+
+            // every field of every level is reachable from the start
+            assert_eq!(stack.search(stack.start).len(), 4 * 6 * 5);
+
+            let route = stack
+                .find_path(stack.start, stack.goal)
+                .ok_or_else(|| anyhow::anyhow!("goal not reachable"))?;
+            for (i_level, stair) in stack.stairs.iter().enumerate() {
+                assert_eq!(stair.level, i_level);
+                assert_eq!(stack.levels[i_level].goal, stair.position);
+                assert_eq!(stack.levels[i_level + 1].start, stair.position);
+                assert!(route.contains(&LevelCoordinates::new(i_level, stair.position)));
+            }
+
+            let again = MazeStack::generate(4, 6, 5, *algorithm, Some([12; 32]))?;
+            assert_eq!(again, stack);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_stacks() -> anyhow::Result<()> {
+        assert_eq!(
+            MazeStack::generate(0, 5, 5, Algorithm::Prims, None),
+            Err(MazeError::NoLevels)
+        );
+        assert_eq!(
+            MazeStack::generate(2, 5, 0, Algorithm::Prims, None),
+            Err(MazeError::InvalidDimensions {
+                width: 5,
+                height: 0
+            })
+        );
+
+        let single = MazeStack::generate(1, 4, 4, Algorithm::Ellers, Some([2; 32]))?;
+        assert!(single.stairs.is_empty());
+        assert_eq!(single.goal.coordinates, single.levels[0].goal);
+
+        let outside = LevelCoordinates::new(1, Coordinates::new(0, 0));
+        assert!(single.neighbours(outside).is_empty());
+        assert_eq!(single.find_path(outside, single.goal), None);
+        Ok(())
+    }
+}
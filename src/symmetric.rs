@@ -0,0 +1,281 @@
+//! Symmetric mazes
+//!
+//! A [`SymmetricGenerator`] wraps another [`Generator`] and produces mazes whose walls look the
+//! same after mirroring or rotating the maze, e.g. for competitive multiplayer maps where every
+//! player should face the same maze.
+//!
+//! The wrapped generator only generates one half or quadrant of the maze which is then copied
+//! onto the remaining parts.
+//! Finally the parts are stitched together with passages that are placed symmetrically as well.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::prelude::*;
+//! use maze_generator::prims_algorithm::PrimsGenerator;
+//! use maze_generator::symmetric::{Symmetry, SymmetricGenerator};
+//!
+//! let mut generator = SymmetricGenerator::new(
+//!     PrimsGenerator::new(Some([42; 32])),
+//!     Symmetry::QuarterTurn,
+//!     Some([42; 32]),
+//! );
+//! let maze = generator.generate(8, 8).unwrap();
+//!
+//! // the maze looks the same after rotating it by 90°
+//! assert_eq!(
+//!     maze.has_passage(&Coordinates::new(2, 1), &Direction::East),
+//!     maze.has_passage(&Coordinates::new(6, 2), &Direction::South),
+//! );
+//! ```
+
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+
+use crate::prelude::*;
+
+/// The transformations under which a maze generated by [`SymmetricGenerator`] stays the same
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Symmetry {
+    /// Mirroring the maze at its vertical center line
+    Mirror,
+    /// Rotating the maze by 180° around its center
+    HalfTurn,
+    /// Rotating the maze by 90° around its center, which requires a square maze
+    QuarterTurn,
+}
+
+impl Symmetry {
+    /// The fields onto which `coordinates` is mapped by all transformations of this symmetry,
+    /// starting with `coordinates` itself
+    fn images(self, coordinates: Coordinates, (width, height): (i32, i32)) -> Vec<Coordinates> {
+        let Coordinates { x, y } = coordinates;
+        match self {
+            Symmetry::Mirror => vec![coordinates, Coordinates::new(width - 1 - x, y)],
+            Symmetry::HalfTurn => {
+                vec![coordinates, Coordinates::new(width - 1 - x, height - 1 - y)]
+            }
+            Symmetry::QuarterTurn => vec![
+                coordinates,
+                Coordinates::new(width - 1 - y, x),
+                Coordinates::new(width - 1 - x, height - 1 - y),
+                Coordinates::new(y, height - 1 - x),
+            ],
+        }
+    }
+
+    /// Size of the part of a maze from which the whole maze can be reconstructed by applying
+    /// all transformations
+    fn domain_size(self, (width, height): (i32, i32)) -> (i32, i32) {
+        match self {
+            Symmetry::Mirror => ((width + 1) / 2, height),
+            Symmetry::HalfTurn => (width, (height + 1) / 2),
+            Symmetry::QuarterTurn => ((width + 1) / 2, (height + 1) / 2),
+        }
+    }
+}
+
+/// [`Generator`] which makes the mazes of another generator symmetric
+///
+/// The start is taken from the maze of the wrapped generator and the goal is placed on the
+/// opposite side of the maze, so that both lie at symmetric positions.
+///
+/// Depending on the size of the maze it can be impossible to connect all parts with
+/// symmetrically placed passages without creating a loop, e.g. for a half turn symmetry of a
+/// maze with an even width and height.
+/// Loops are only created when all parts can not be connected otherwise.
+#[derive(Debug, Clone)]
+pub struct SymmetricGenerator<G> {
+    generator: G,
+    /// The symmetry of the generated mazes
+    pub symmetry: Symmetry,
+    rng: ChaChaRng,
+}
+
+impl<G: Generator> SymmetricGenerator<G> {
+    /// Create a new instance which uses `generator` to generate one part of every maze.
+    ///
+    /// Optionally a 32 bit seed can be provided to seed the random generator which chooses the
+    /// passages connecting the parts.
+    #[must_use]
+    pub fn new(generator: G, symmetry: Symmetry, seed: Option<Seed>) -> Self {
+        SymmetricGenerator {
+            generator,
+            symmetry,
+            rng: match seed {
+                None => ChaChaRng::from_entropy(),
+                Some(seed) => ChaChaRng::from_seed(seed),
+            },
+        }
+    }
+
+    /// All passages onto which the passage between `a` and `b` is mapped by the symmetry
+    fn passage_images(
+        &self,
+        a: Coordinates,
+        b: Coordinates,
+        size: (i32, i32),
+    ) -> Vec<(Coordinates, Coordinates)> {
+        let mut images: Vec<_> = self
+            .symmetry
+            .images(a, size)
+            .into_iter()
+            .zip(self.symmetry.images(b, size))
+            .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+            .collect();
+        images.sort_unstable();
+        images.dedup();
+        images
+    }
+}
+
+/// Whether adding all `passages` at once would close a loop between the fields in `sets`
+fn closes_loop(
+    sets: &mut DisjointSets,
+    passages: &[(Coordinates, Coordinates)],
+    width: i32,
+) -> bool {
+    let index = |c: Coordinates| c.y as usize * width as usize + c.x as usize;
+    let roots: Vec<(usize, usize)> = passages
+        .iter()
+        .map(|(a, b)| (sets.find(index(*a)), sets.find(index(*b))))
+        .collect();
+    let mut ids: Vec<usize> = roots.iter().flat_map(|(a, b)| vec![*a, *b]).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut local = DisjointSets::new(ids.len());
+    let position = |id: usize| ids.binary_search(&id).unwrap_or_else(|i| i);
+    !roots
+        .iter()
+        .all(|(a, b)| local.union(position(*a), position(*b)))
+}
+
+impl<G: Generator> Generator for SymmetricGenerator<G> {
+    fn generate(&mut self, width: i32, height: i32) -> Result<Maze, MazeError> {
+        ensure_valid_size(width, height)?;
+        // a maze with a quarter turn symmetry needs to be square
+        if self.symmetry == Symmetry::QuarterTurn && width != height {
+            return Err(MazeError::InvalidDimensions { width, height });
+        }
+        let size = (width, height);
+        let (domain_width, domain_height) = self.symmetry.domain_size(size);
+        let domain = self.generator.generate(domain_width, domain_height)?;
+
+        let start = domain.start;
+        let mut maze = Maze::new(width, height, start, start);
+        let mut sets = DisjointSets::new(maze.topology().cell_count());
+        let mut component_count = maze.topology().cell_count();
+        let index = |c: &Coordinates| c.y as usize * width as usize + c.x as usize;
+
+        // copy the passages of the generated part everywhere as long as the copies don't overlap
+        // in a way that creates loops, then connect the parts with as few loops as possible
+        let mut candidates = Vec::new();
+        for coordinates in maze.coordinates() {
+            for direction in [Direction::East, Direction::South].iter() {
+                let next = coordinates.next(direction);
+                if maze.are_coordinates_inside(&next) {
+                    candidates.push((coordinates, next));
+                }
+            }
+        }
+        let mut stitches = candidates.clone();
+        stitches.shuffle(&mut self.rng);
+        candidates.retain(|(a, b)| domain.has_passage_between(*a, *b));
+
+        for allow_loops in [false, true].iter() {
+            for (a, b) in candidates.iter().chain(stitches.iter()) {
+                if component_count == 1 {
+                    break;
+                }
+                let images = self.passage_images(*a, *b, size);
+                let joins_parts = images
+                    .iter()
+                    .any(|(a, b)| sets.find(index(a)) != sets.find(index(b)));
+                if !joins_parts || (!*allow_loops && closes_loop(&mut sets, &images, width)) {
+                    continue;
+                }
+                for (a, b) in images.iter() {
+                    if sets.union(index(a), index(b)) {
+                        component_count -= 1;
+                    }
+                    maze.add_passage(*a, *b);
+                }
+            }
+            candidates.clear();
+        }
+
+        let opposite = self.symmetry.images(start, size);
+        maze.goal = opposite[opposite.len() / 2];
+        if maze.goal == start {
+            maze.goal = maze.farthest_from(start).0;
+        }
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SymmetricGenerator, Symmetry};
+    use crate::prelude::{Coordinates, Direction, Generator, Maze};
+    use crate::recursive_backtracking::RbGenerator;
+
+    fn is_symmetric(maze: &Maze, symmetry: Symmetry) -> bool {
+        maze.coordinates().all(|coordinates| {
+            [Direction::East, Direction::South].iter().all(|direction| {
+                let next = coordinates.next(direction);
+                let images = symmetry
+                    .images(coordinates, maze.size)
+                    .into_iter()
+                    .zip(symmetry.images(next, maze.size));
+                images
+                    .map(|(a, b)| maze.has_passage_between(a, b))
+                    .all(|passage| passage == maze.has_passage_between(coordinates, next))
+            })
+        })
+    }
+
+    #[test]
+    fn test_mazes_are_symmetric_and_connected() -> anyhow::Result<()> {
+        for symmetry in [Symmetry::Mirror, Symmetry::HalfTurn, Symmetry::QuarterTurn].iter() {
+            for size in 1..10 {
+                let mut generator = SymmetricGenerator::new(
+                    RbGenerator::new(Some([size as u8; 32])),
+                    *symmetry,
+                    Some([3; 32]),
+                );
+                let maze = generator.generate(size, size)?;
+                let field_count = (size * size) as usize;
+
+                assert!(is_symmetric(&maze, *symmetry));
+                assert_eq!(maze.breadth_first_search(maze.start).len(), field_count);
+                // at most a single loop is needed to connect all parts
+                assert!(maze.passages.passage_count() <= field_count);
+                assert!(symmetry.images(maze.start, maze.size).contains(&maze.goal) || size == 1);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_mirrored_mazes_are_perfect() -> anyhow::Result<()> {
+        let mut generator = SymmetricGenerator::new(
+            RbGenerator::new(Some([7; 32])),
+            Symmetry::Mirror,
+            Some([7; 32]),
+        );
+        for width in 1..12 {
+            let maze = generator.generate(width, 5)?;
+            assert!(is_symmetric(&maze, Symmetry::Mirror));
+            assert_eq!(maze.passages.passage_count(), (width * 5 - 1) as usize);
+        }
+
+        generator.symmetry = Symmetry::QuarterTurn;
+        assert!(generator.generate(4, 5).is_err());
+        assert_eq!(
+            Symmetry::QuarterTurn.images(Coordinates::new(1, 0), (4, 4))[1],
+            Coordinates::new(3, 1)
+        );
+        Ok(())
+    }
+}
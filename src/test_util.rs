@@ -1,4 +1,3 @@
-use anyhow::{ensure, Result};
 use petgraph::algo;
 use quickcheck::TestResult;
 
@@ -11,8 +10,7 @@ pub(crate) fn convert_seed(seed: u128) -> [u8; 32] {
     result
 }
 
-fn generate_maze(gen: &mut impl Generator, width: i32, height: i32) -> Result<Maze> {
-    ensure!(width > 0 && height > 0, "Invalid size");
+fn generate_maze(gen: &mut impl Generator, width: i32, height: i32) -> Result<Maze, MazeError> {
     gen.generate(width, height)
 }
 
@@ -33,9 +31,9 @@ pub(crate) fn test_all_coordinates_have_fields(
     height: i32,
 ) -> TestResult {
     match generate_maze(&mut gen, width, height) {
-        Err(e) => match e.downcast_ref::<GenericGeneratorError>() {
-            Some(_) => TestResult::failed(),
-            None => TestResult::discard(),
+        Err(e) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
         },
         Ok(maze) => {
             for ix in 0..maze.size.0 {
@@ -68,9 +66,9 @@ pub(crate) fn test_route_from_start_to_goal_exists(
     height: i32,
 ) -> TestResult {
     match generate_maze(&mut gen, width, height) {
-        Err(e) => match e.downcast_ref::<GenericGeneratorError>() {
-            Some(_) => TestResult::failed(),
-            None => TestResult::discard(),
+        Err(e) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
         },
         Ok(maze) => {
             let start = maze.start;
@@ -86,7 +84,7 @@ pub(crate) fn test_route_from_start_to_goal_exists(
 
             let graph: MazeGraph = maze.into();
 
-            quickcheck::TestResult::from_bool(algo::has_path_connecting(&graph, start, goal, None))
+            TestResult::from_bool(algo::has_path_connecting(&graph, start, goal, None))
         }
     }
 }
@@ -108,9 +106,9 @@ pub(crate) fn test_all_fields_connected(
     height: i32,
 ) -> TestResult {
     match generate_maze(&mut gen, width, height) {
-        Err(e) => match e.downcast_ref::<GenericGeneratorError>() {
-            Some(_) => TestResult::failed(),
-            None => TestResult::discard(),
+        Err(e) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
         },
         Ok(maze) => {
             let graph: MazeGraph = maze.into();
@@ -141,16 +139,187 @@ where
     T: Generator,
 {
     match generate_maze(&mut gen1, width, height) {
-        Err(e) => match e.downcast_ref::<GenericGeneratorError>() {
-            Some(_) => TestResult::failed(),
-            None => TestResult::discard(),
+        Err(e) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
         },
         Ok(maze1) => match generate_maze(&mut gen2, width, height) {
-            Err(e) => match e.downcast_ref::<GenericGeneratorError>() {
-                Some(_) => TestResult::failed(),
-                None => TestResult::discard(),
+            Err(e) => match e.is_bug() {
+                true => TestResult::failed(),
+                false => TestResult::discard(),
             },
             Ok(maze2) => TestResult::from_bool(maze1 == maze2),
         },
     }
 }
+
+macro_rules! test_generate_into_matches_generate {
+    ($generator_name:ty) => {
+        quickcheck! {
+            fn test_generate_into_matches_generate(seed: u128, width: i32, height: i32) -> quickcheck::TestResult {
+                let gen1 = <$generator_name>::new(Some(crate::test_util::convert_seed(seed)));
+                let gen2 = <$generator_name>::new(Some(crate::test_util::convert_seed(seed)));
+                let buffer_gen = <$generator_name>::new(Some(crate::test_util::convert_seed(!seed)));
+                crate::test_util::test_generate_into_matches_generate(gen1, gen2, buffer_gen, width, height)
+            }
+        }
+    };
+}
+
+pub(crate) fn test_generate_into_matches_generate<T>(
+    mut gen1: T,
+    mut gen2: T,
+    mut buffer_gen: T,
+    width: i32,
+    height: i32,
+) -> TestResult
+where
+    T: Generator,
+{
+    // the buffer intentionally has a different size than the maze generated into it
+    let buffer = generate_maze(&mut buffer_gen, 3, 2);
+    match (generate_maze(&mut gen1, width, height), buffer) {
+        (Err(e), _) | (_, Err(e)) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
+        },
+        (Ok(maze1), Ok(mut maze2)) => {
+            maze2.size = (width, height);
+            match gen2.generate_into(&mut maze2) {
+                Err(_) => TestResult::failed(),
+                Ok(()) => TestResult::from_bool(
+                    maze1.passages == maze2.passages
+                        && maze1.start == maze2.start
+                        && maze1.goal == maze2.goal,
+                ),
+            }
+        }
+    }
+}
+
+macro_rules! test_random_carving_start {
+    ($generator_name:ty) => {
+        quickcheck! {
+            fn test_random_carving_start(seed: u128, width: i32, height: i32) -> quickcheck::TestResult {
+                let mut gen = <$generator_name>::new(Some(crate::test_util::convert_seed(seed)));
+                gen.random_carving_start = true;
+                crate::test_util::test_all_fields_connected(gen, width, height)
+            }
+        }
+    };
+}
+
+macro_rules! test_generate_from_keeps_start {
+    ($generator_name:ty) => {
+        quickcheck! {
+            fn test_generate_from_keeps_start(seed: u128, width: i32, height: i32, x: i32, y: i32) -> quickcheck::TestResult {
+                let gen = <$generator_name>::new(Some(crate::test_util::convert_seed(seed)));
+                crate::test_util::test_generate_from_keeps_start(gen, width, height, x, y)
+            }
+        }
+    };
+}
+
+pub(crate) fn test_generate_from_keeps_start(
+    mut gen: impl Generator,
+    width: i32,
+    height: i32,
+    x: i32,
+    y: i32,
+) -> TestResult {
+    if width <= 0 || height <= 0 {
+        return TestResult::discard();
+    }
+    let start = Coordinates::new(x.rem_euclid(width), y.rem_euclid(height));
+    match gen.generate_from(width, height, start) {
+        Err(_) => TestResult::failed(),
+        Ok(maze) => {
+            let reachable = maze.breadth_first_search(start);
+            TestResult::from_bool(
+                maze.start == start
+                    && reachable.len() == (width * height) as usize
+                    && reachable.get(&maze.goal).map(|(distance, _)| *distance)
+                        == reachable.values().map(|(distance, _)| *distance).max()
+                    && gen.generate_from(width, height, (width, 0).into()).is_err(),
+            )
+        }
+    }
+}
+
+macro_rules! test_passages_are_consistent {
+    ($generator_name:ty) => {
+        quickcheck! {
+            fn test_passages_are_consistent(seed: u128, width: i32, height: i32) -> quickcheck::TestResult {
+                let gen = <$generator_name>::new(Some(crate::test_util::convert_seed(seed)));
+                crate::test_util::test_passages_are_consistent(gen, width, height)
+            }
+        }
+    };
+}
+
+pub(crate) fn test_passages_are_consistent(
+    mut gen: impl Generator,
+    width: i32,
+    height: i32,
+) -> TestResult {
+    match generate_maze(&mut gen, width, height) {
+        Err(e) => match e.is_bug() {
+            true => TestResult::failed(),
+            false => TestResult::discard(),
+        },
+        Ok(maze) => {
+            // look one field beyond the border to catch passages which leave the maze
+            let symmetric_and_inside = (-1..=width).all(|ix| {
+                (-1..=height).all(|iy| {
+                    let coordinates = Coordinates::new(ix, iy);
+                    Direction::iter().all(|direction| {
+                        let next = coordinates.next(&direction);
+                        let passage = maze.has_passage(&coordinates, &direction);
+                        passage == maze.has_passage(&next, &direction.opposite())
+                            && (!passage
+                                || (maze.are_coordinates_inside(&coordinates)
+                                    && maze.are_coordinates_inside(&next)))
+                    })
+                })
+            });
+            TestResult::from_bool(symmetric_and_inside && maze.validate().is_ok())
+        }
+    }
+}
+
+macro_rules! test_resumed_generation_matches_generate {
+    ($generator_name:ty) => {
+        quickcheck! {
+            fn test_resumed_generation_matches_generate(seed: u128, width: i32, height: i32, pauses: u8) -> quickcheck::TestResult {
+                use crate::prelude::{Generator, Maze};
+
+                if width <= 0 || height <= 0 {
+                    return quickcheck::TestResult::discard();
+                }
+                let seed = crate::test_util::convert_seed(seed);
+                let passages = (width * height) as usize / (usize::from(pauses % 8) + 1) + 1;
+
+                // every pause continues with a new generator and, if possible, a state which
+                // was serialized in between
+                let resumed = || -> anyhow::Result<Maze> {
+                    let mut state = <$generator_name>::new(Some(seed)).start(width, height)?;
+                    loop {
+                        #[cfg(feature = "serde")]
+                        {
+                            state = serde_json::from_str(&serde_json::to_string(&state)?)?;
+                        }
+                        if <$generator_name>::new(None).resume(&mut state, passages)? {
+                            break;
+                        }
+                    }
+                    Ok(<$generator_name>::new(None).finish(state)?)
+                };
+                let generated = <$generator_name>::new(Some(seed)).generate(width, height);
+                match (resumed(), generated) {
+                    (Ok(resumed), Ok(generated)) => quickcheck::TestResult::from_bool(resumed == generated),
+                    _ => quickcheck::TestResult::failed(),
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,487 @@
+//! Circular (theta) mazes
+//!
+//! A theta maze consists of concentric rings around a single center field.
+//! Every ring is subdivided into fields radially and outer rings are subdivided further whenever
+//! their fields would otherwise become much wider than they are high.
+//! Because such a layout can not be expressed by the rectangular [`Maze`],
+//! this module has its own [`PolarCoordinates`] and [`ThetaMaze`] types.
+//!
+//! Passages are carved with the recursive-backtracking algorithm starting at the center and the
+//! goal is placed on the outermost ring as far away from the center as possible.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::prelude::*;
+//! use maze_generator::theta::{PolarCoordinates, ThetaGenerator};
+//!
+//! let maze = ThetaGenerator::new(Some([42; 32])).generate(5).unwrap();
+//!
+//! assert_eq!(maze.rings(), 5);
+//! assert_eq!(maze.start, PolarCoordinates::new(0, 0));
+//! assert_eq!(maze.goal.ring, 4);
+//! ```
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter};
+#[cfg(feature = "svg")]
+use std::io;
+
+use crate::prelude::*;
+use crate::recursive_backtracking::RbGenerator;
+
+/// Coordinates of a field in a [`ThetaMaze`]
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct PolarCoordinates {
+    /// Ring in which the field lies, 0 being the center
+    pub ring: usize,
+    /// Index of the field inside of its ring, counted clockwise
+    pub cell: usize,
+}
+
+impl PolarCoordinates {
+    /// Create a new instance from the specified coordinate components
+    #[must_use]
+    pub fn new(ring: usize, cell: usize) -> Self {
+        PolarCoordinates { ring, cell }
+    }
+}
+
+impl Display for PolarCoordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(ring {}, cell {})", self.ring, self.cell)
+    }
+}
+
+/// Layout of a circular maze: concentric rings around a single center field
+///
+/// Every ring is divided into as many fields as are needed to keep them from becoming much wider
+/// than they are high.
+/// The number of fields of a ring is always a multiple of the number of fields of the ring inside
+/// of it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ThetaTopology {
+    /// Number of fields in every ring
+    ring_sizes: Vec<usize>,
+}
+
+impl ThetaTopology {
+    /// Create the layout for a maze with the given number of rings including the center field
+    #[must_use]
+    pub fn new(rings: usize) -> Self {
+        let mut ring_sizes = Vec::with_capacity(rings);
+        for ring in 0..rings {
+            // subdivide the fields of the previous ring if they would otherwise become more than
+            // about twice as wide as a ring is high
+            let size = match ring_sizes.last() {
+                None => 1,
+                Some(&previous) => {
+                    let ratio = (2.0 * PI * ring as f64 / previous as f64).round().max(1.0);
+                    previous * ratio as usize
+                }
+            };
+            ring_sizes.push(size);
+        }
+
+        ThetaTopology { ring_sizes }
+    }
+
+    /// Number of rings including the center
+    #[must_use]
+    pub fn rings(&self) -> usize {
+        self.ring_sizes.len()
+    }
+
+    /// Number of fields in `ring` or `None` if the ring does not exist
+    #[must_use]
+    pub fn ring_size(&self, ring: usize) -> Option<usize> {
+        self.ring_sizes.get(ring).copied()
+    }
+}
+
+impl Topology for ThetaTopology {
+    type Cell = PolarCoordinates;
+
+    fn cells(&self) -> Vec<PolarCoordinates> {
+        self.ring_sizes
+            .iter()
+            .enumerate()
+            .flat_map(|(ring, &size)| (0..size).map(move |cell| PolarCoordinates::new(ring, cell)))
+            .collect()
+    }
+
+    fn contains(&self, cell: &PolarCoordinates) -> bool {
+        self.ring_size(cell.ring)
+            .is_some_and(|size| cell.cell < size)
+    }
+
+    /// Neighbours are ordered clockwise, counter-clockwise, inwards and then outwards
+    fn neighbours(&self, coordinates: &PolarCoordinates) -> Vec<PolarCoordinates> {
+        if !self.contains(coordinates) {
+            return Vec::new();
+        }
+        let PolarCoordinates { ring, cell } = *coordinates;
+        let size = self.ring_sizes[ring];
+        let mut neighbours = Vec::new();
+
+        if ring > 0 {
+            // clockwise and counter-clockwise
+            neighbours.push(PolarCoordinates::new(ring, (cell + 1) % size));
+            neighbours.push(PolarCoordinates::new(ring, (cell + size - 1) % size));
+            // inwards
+            let inner_size = self.ring_sizes[ring - 1];
+            neighbours.push(PolarCoordinates::new(ring - 1, cell * inner_size / size));
+        }
+        // outwards
+        if let Some(&outer_size) = self.ring_sizes.get(ring + 1) {
+            let ratio = outer_size / size;
+            neighbours.extend(
+                (cell * ratio..(cell + 1) * ratio).map(|c| PolarCoordinates::new(ring + 1, c)),
+            );
+        }
+
+        neighbours
+    }
+
+    fn cell_count(&self) -> usize {
+        self.ring_sizes.iter().sum()
+    }
+
+    fn rendering_hint(&self) -> RenderingHint {
+        RenderingHint::Polar {
+            rings: self.rings(),
+        }
+    }
+}
+
+/// A circular maze made of concentric rings
+///
+/// Use a [`ThetaGenerator`] to create an instance of this type.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ThetaMaze {
+    topology: ThetaTopology,
+    /// Every passage stored with the smaller coordinates first
+    passages: HashSet<(PolarCoordinates, PolarCoordinates)>,
+    /// At which coordinates the start field lies
+    pub start: PolarCoordinates,
+    /// At which coordinates the goal field lies
+    pub goal: PolarCoordinates,
+}
+
+impl ThetaMaze {
+    /// Create a maze with the given layout and no passages
+    fn new(topology: ThetaTopology) -> Self {
+        ThetaMaze {
+            topology,
+            passages: HashSet::new(),
+            start: PolarCoordinates::default(),
+            goal: PolarCoordinates::default(),
+        }
+    }
+
+    /// The layout of this maze
+    #[must_use]
+    pub fn topology(&self) -> &ThetaTopology {
+        &self.topology
+    }
+
+    /// Number of rings including the center
+    #[must_use]
+    pub fn rings(&self) -> usize {
+        self.topology.rings()
+    }
+
+    /// Number of fields in `ring` or `None` if the ring does not exist
+    #[must_use]
+    pub fn ring_size(&self, ring: usize) -> Option<usize> {
+        self.topology.ring_size(ring)
+    }
+
+    /// Whether the coordinates address a field of this maze
+    #[must_use]
+    pub fn contains(&self, coordinates: PolarCoordinates) -> bool {
+        self.topology.contains(&coordinates)
+    }
+
+    /// All coordinates of the maze from the center outwards
+    #[must_use]
+    pub fn coordinates(&self) -> Vec<PolarCoordinates> {
+        self.topology.cells()
+    }
+
+    /// All fields which are adjacent to `coordinates`, regardless of walls between them
+    #[must_use]
+    pub fn neighbours(&self, coordinates: PolarCoordinates) -> Vec<PolarCoordinates> {
+        self.topology.neighbours(&coordinates)
+    }
+
+    fn passage_key(
+        a: PolarCoordinates,
+        b: PolarCoordinates,
+    ) -> (PolarCoordinates, PolarCoordinates) {
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    /// Whether a passage connects the two fields
+    #[must_use]
+    pub fn has_passage(&self, a: PolarCoordinates, b: PolarCoordinates) -> bool {
+        self.passages.contains(&Self::passage_key(a, b))
+    }
+
+    /// All fields which can be reached from `coordinates` through a passage
+    #[must_use]
+    pub fn passage_neighbours(&self, coordinates: PolarCoordinates) -> Vec<PolarCoordinates> {
+        self.neighbours(coordinates)
+            .into_iter()
+            .filter(|next| self.has_passage(coordinates, *next))
+            .collect()
+    }
+
+    fn add_passage(&mut self, a: PolarCoordinates, b: PolarCoordinates) {
+        self.passages.insert(Self::passage_key(a, b));
+    }
+
+    /// Distance of every field from `from` in steps
+    fn distances(&self, from: PolarCoordinates) -> HashMap<PolarCoordinates, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(from, 0);
+        queue.push_back(from);
+
+        while let Some(i_coords) = queue.pop_front() {
+            let distance = distances[&i_coords];
+            for i_next in self.passage_neighbours(i_coords) {
+                if let Entry::Vacant(entry) = distances.entry(i_next) {
+                    entry.insert(distance + 1);
+                    queue.push_back(i_next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Generate an SVG version of the maze using arcs for the walls between rings
+    ///
+    /// The size is derived from [`SvgOptions::height`] (the diameter of the maze) or
+    /// [`SvgOptions::cell_size`] (the height of a single ring).
+    /// Start and goal are marked with circles in their respective colours.
+    /// [`SvgOptions::show_coordinates`] and [`SvgOptions::show_grid`] are not supported.
+    #[cfg(feature = "svg")]
+    pub fn to_svg(&self, svgoptions: &SvgOptions) -> io::Result<String> {
+        let mut svg = Vec::new();
+        self.write_svg(&mut svg, svgoptions)?;
+        String::from_utf8(svg).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write an SVG version of the maze into `w`, see [`to_svg()`](ThetaMaze::to_svg)
+    #[cfg(feature = "svg")]
+    pub fn write_svg<W: io::Write>(&self, mut w: W, svgoptions: &SvgOptions) -> io::Result<()> {
+        let rings = self.rings() as f64;
+        let padding = f64::from(svgoptions.padding);
+        let ring_height = match (svgoptions.cell_size, svgoptions.height) {
+            (Some(cell_size), _) => cell_size,
+            (None, Some(height)) => f64::from(height) / 2.0 / rings,
+            (None, None) => 2.0 * padding,
+        };
+        let radius = ring_height * rings;
+        // the center of the maze lies at (radius, radius)
+        let point = |r: f64, angle: f64| (radius + r * angle.cos(), radius + r * angle.sin());
+        let angle = |ring: usize, cell: usize| {
+            2.0 * PI * cell as f64 / self.topology.ring_sizes[ring] as f64
+        };
+
+        writeln!(w, "<?xml version=\"1.0\" encoding=\"utf-8\"?>")?;
+        writeln!(w, "<svg xmlns=\"http://www.w3.org/2000/svg\"")?;
+        writeln!(
+            w,
+            "    width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+            2.0 * (radius + padding),
+            2.0 * (radius + padding),
+            -padding,
+            -padding,
+            2.0 * (radius + padding),
+            2.0 * (radius + padding)
+        )?;
+        writeln!(
+            w,
+            "<g fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"round\">",
+            svgoptions.strokecol, svgoptions.strokewidth
+        )?;
+
+        for coordinates in self.coordinates().into_iter().filter(|c| c.ring > 0) {
+            let PolarCoordinates { ring, cell } = coordinates;
+            let inner = ring as f64 * ring_height;
+            let outer = inner + ring_height;
+            let (start_angle, end_angle) = (angle(ring, cell), angle(ring, cell + 1));
+
+            let inner_neighbour = self.neighbours(coordinates)[2];
+            if !self.has_passage(coordinates, inner_neighbour) {
+                let (x1, y1) = point(inner, start_angle);
+                let (x2, y2) = point(inner, end_angle);
+                writeln!(
+                    w,
+                    "<path d=\"M {} {} A {} {} 0 0 1 {} {}\"/>",
+                    x1, y1, inner, inner, x2, y2
+                )?;
+            }
+
+            let clockwise_neighbour = self.neighbours(coordinates)[0];
+            if !self.has_passage(coordinates, clockwise_neighbour) {
+                let (x1, y1) = point(inner, end_angle);
+                let (x2, y2) = point(outer, end_angle);
+                writeln!(
+                    w,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"/>",
+                    x1, y1, x2, y2
+                )?;
+            }
+        }
+        writeln!(
+            w,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"/>",
+            radius, radius, radius
+        )?;
+        writeln!(w, "</g>")?;
+
+        let markers = [
+            (self.start, &svgoptions.startcol),
+            (self.goal, &svgoptions.goalcol),
+        ];
+        for (coordinates, markercol) in markers.iter() {
+            let (x, y) = if coordinates.ring == 0 {
+                (radius, radius)
+            } else {
+                let middle = (coordinates.ring as f64 + 0.5) * ring_height;
+                let middle_angle = (angle(coordinates.ring, coordinates.cell)
+                    + angle(coordinates.ring, coordinates.cell + 1))
+                    / 2.0;
+                point(middle, middle_angle)
+            };
+            writeln!(
+                w,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>",
+                x, y, svgoptions.markersize, markercol
+            )?;
+        }
+        writeln!(w, "</svg>")?;
+
+        Ok(())
+    }
+}
+
+/// Generator for [`ThetaMaze`]s which uses the recursive-backtracking algorithm.
+#[derive(Debug, Clone)]
+pub struct ThetaGenerator {
+    generator: RbGenerator,
+}
+
+impl ThetaGenerator {
+    /// Create a new instance.
+    ///
+    /// Optionally a 32 bit seed can be provided to seed the internal random generator.
+    /// Giving a seed results in identical mazes being generated which omitting it sources the
+    /// random generator from entropy.
+    #[must_use]
+    pub fn new(seed: Option<Seed>) -> ThetaGenerator {
+        ThetaGenerator {
+            generator: RbGenerator::new(seed),
+        }
+    }
+
+    /// Generate a circular maze with the given number of rings (including the center field)
+    pub fn generate(&mut self, rings: usize) -> Result<ThetaMaze, MazeError> {
+        if rings == 0 {
+            return Err(MazeError::NoRings);
+        }
+        let mut maze = ThetaMaze::new(ThetaTopology::new(rings));
+
+        for (a, b) in self.generator.carve(&maze.topology, maze.start) {
+            maze.add_passage(a, b);
+        }
+
+        let outermost = rings - 1;
+        let distances = maze.distances(maze.start);
+        maze.goal = maze
+            .coordinates()
+            .into_iter()
+            .filter(|c| c.ring == outermost)
+            .max_by_key(|c| {
+                (
+                    distances.get(c).copied().unwrap_or(0),
+                    std::cmp::Reverse(*c),
+                )
+            })
+            .unwrap_or(maze.start);
+
+        Ok(maze)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PolarCoordinates, ThetaGenerator};
+    use crate::prelude::Topology;
+
+    #[test]
+    fn test_ring_subdivision() -> anyhow::Result<()> {
+        let maze = ThetaGenerator::new(Some([1; 32])).generate(7)?;
+        let sizes: Vec<_> = (0..maze.rings())
+            .filter_map(|r| maze.ring_size(r))
+            .collect();
+        assert_eq!(sizes, vec![1, 6, 12, 24, 24, 24, 48]);
+
+        // a field whose outer neighbours are subdivided has two of them
+        assert_eq!(
+            maze.neighbours(PolarCoordinates::new(2, 11)),
+            vec![
+                PolarCoordinates::new(2, 0),
+                PolarCoordinates::new(2, 10),
+                PolarCoordinates::new(1, 5),
+                PolarCoordinates::new(3, 22),
+                PolarCoordinates::new(3, 23),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_fields_connected() -> anyhow::Result<()> {
+        for seed in 0..5 {
+            let maze = ThetaGenerator::new(Some([seed; 32])).generate(6)?;
+            let field_count = maze.topology().cell_count();
+
+            // a perfect maze is a spanning tree
+            assert_eq!(maze.passages.len(), field_count - 1);
+            assert_eq!(maze.distances(maze.start).len(), field_count);
+            assert_eq!(maze.goal.ring, 5);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_generation_is_deterministic() -> anyhow::Result<()> {
+        let maze1 = ThetaGenerator::new(Some([3; 32])).generate(8)?;
+        let maze2 = ThetaGenerator::new(Some([3; 32])).generate(8)?;
+        assert_eq!(maze1, maze2);
+        assert!(ThetaGenerator::new(None).generate(0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "svg")]
+    fn test_svg_uses_arcs() -> anyhow::Result<()> {
+        let maze = ThetaGenerator::new(Some([3; 32])).generate(4)?;
+        let svg = maze.to_svg(&crate::prelude::SvgOptions::new().padding(0).height(200))?;
+
+        assert!(svg.contains("width=\"200\" height=\"200\""));
+        assert!(svg.contains(" A "));
+        assert!(svg.ends_with("</svg>\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+//! Interactive terminal maze viewer
+//!
+//! Renders a maze into the terminal and lets the user walk a cursor from the start to the goal
+//! field.
+//! The arrow keys (or `w`, `a`, `s`, `d`) move the cursor, `space` toggles an overlay of the
+//! shortest route from the cursor to the goal and `q` or `Esc` quit the viewer.
+//!
+//! Besides being a nice demo, this is handy for manually inspecting the output of new generators.
+//!
+//! ```no_run
+//! use maze_generator::prelude::*;
+//! use maze_generator::recursive_backtracking::RbGenerator;
+//!
+//! let maze = RbGenerator::new(None).generate(20, 10).unwrap();
+//! maze_generator::tui::run(&maze).unwrap();
+//! ```
+
+use std::io::{self, Write};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+use crate::prelude::*;
+
+/// An input which changes the state of a [`MazeViewer`]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ViewerAction {
+    /// Move the cursor into a direction if no wall is in the way
+    Move(Direction),
+    /// Show or hide the route from the cursor to the goal
+    ToggleSolution,
+    /// Close the viewer
+    Quit,
+}
+
+impl ViewerAction {
+    /// Map a key to the action it triggers, if any
+    #[must_use]
+    pub fn from_key(key: KeyCode) -> Option<ViewerAction> {
+        match key {
+            KeyCode::Up | KeyCode::Char('w') => Some(ViewerAction::Move(Direction::North)),
+            KeyCode::Right | KeyCode::Char('d') => Some(ViewerAction::Move(Direction::East)),
+            KeyCode::Down | KeyCode::Char('s') => Some(ViewerAction::Move(Direction::South)),
+            KeyCode::Left | KeyCode::Char('a') => Some(ViewerAction::Move(Direction::West)),
+            KeyCode::Char(' ') => Some(ViewerAction::ToggleSolution),
+            KeyCode::Esc | KeyCode::Char('q') => Some(ViewerAction::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// State of the interactive viewer, independent of any terminal
+///
+/// [`run()`] drives this with keyboard input but it can also be embedded into other user
+/// interfaces.
+#[derive(Debug, Clone)]
+pub struct MazeViewer<'a> {
+    maze: &'a Maze,
+    /// Field on which the cursor currently is
+    pub cursor: Coordinates,
+    /// Whether the route from the cursor to the goal is shown
+    pub show_solution: bool,
+    /// Whether the user asked to close the viewer
+    pub quit: bool,
+}
+
+impl<'a> MazeViewer<'a> {
+    /// Create a viewer whose cursor is placed on the start field of `maze`
+    #[must_use]
+    pub fn new(maze: &'a Maze) -> Self {
+        MazeViewer {
+            maze,
+            cursor: maze.start,
+            show_solution: false,
+            quit: false,
+        }
+    }
+
+    /// Whether the cursor has reached the goal
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.cursor == self.maze.goal
+    }
+
+    /// Update the state according to `action`
+    pub fn apply(&mut self, action: ViewerAction) {
+        match action {
+            ViewerAction::Move(direction) => {
+                if let MoveResult::Moved(to) = self.maze.try_move(&self.cursor, &direction) {
+                    self.cursor = to;
+                }
+            }
+            ViewerAction::ToggleSolution => self.show_solution = !self.show_solution,
+            ViewerAction::Quit => self.quit = true,
+        }
+    }
+
+    /// Render the maze in the same style as its [`Debug`](std::fmt::Debug) output with the
+    /// cursor drawn as `@` and the route to the goal (if shown) drawn as `.`
+    #[must_use]
+    pub fn render(&self) -> Vec<String> {
+        let solution = if self.show_solution {
+            self.maze
+                .find_path(self.cursor, self.maze.goal)
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let (width, height) = self.maze.size;
+        let mut lines = Vec::with_capacity(2 * height as usize + 1);
+
+        for iy in 0..height {
+            let mut walls = String::new();
+            let mut fields = String::new();
+            for ix in 0..width {
+                let coordinates = Coordinates::new(ix, iy);
+                walls.push(if self.maze.is_inside_room(ix, iy) {
+                    ' '
+                } else {
+                    '·'
+                });
+                walls.push(if self.maze.has_passage(&coordinates, &Direction::North) {
+                    ' '
+                } else {
+                    '-'
+                });
+                fields.push(if self.maze.has_passage(&coordinates, &Direction::West) {
+                    ' '
+                } else {
+                    '|'
+                });
+                fields.push(if coordinates == self.cursor {
+                    '@'
+                } else if coordinates == self.maze.start {
+                    'S'
+                } else if coordinates == self.maze.goal {
+                    'G'
+                } else if solution.contains(&coordinates) {
+                    '.'
+                } else {
+                    ' '
+                });
+            }
+            walls.push('·');
+            fields.push('|');
+            lines.push(walls);
+            lines.push(fields);
+        }
+        lines.push(format!("{}·", "·-".repeat(width as usize)));
+
+        lines
+    }
+}
+
+/// Restores the terminal when the viewer is closed, even if an error occurred
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter(out: &mut impl Write) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // errors can not be reported from drop and the terminal is unusable anyway if these fail
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Show `maze` in the terminal until the user reaches the goal or quits
+pub fn run(maze: &Maze) -> io::Result<()> {
+    let mut out = io::stdout();
+    let _guard = TerminalGuard::enter(&mut out)?;
+    let mut viewer = MazeViewer::new(maze);
+
+    while !viewer.quit {
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        for (i_line, line) in viewer.render().iter().enumerate() {
+            queue!(out, cursor::MoveTo(0, i_line as u16), style::Print(line))?;
+        }
+        let status = if viewer.is_solved() {
+            "Goal reached! Press any key to exit."
+        } else {
+            "Arrow keys: move   Space: toggle solution   q: quit"
+        };
+        queue!(
+            out,
+            cursor::MoveTo(0, 2 * maze.size.1 as u16 + 2),
+            style::Print(status)
+        )?;
+        out.flush()?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+            if viewer.is_solved() {
+                break;
+            }
+            if let Some(action) = ViewerAction::from_key(key.code) {
+                viewer.apply(action);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MazeViewer, ViewerAction};
+    use crate::prelude::{Coordinates, Direction, Generator};
+    use crate::recursive_backtracking::RbGenerator;
+
+    #[test]
+    fn test_walk_to_goal() -> anyhow::Result<()> {
+        // ·-·-·-·
+        // |S|   |
+        // · ·-· ·
+        // |     |
+        // ·-·-· ·
+        // |G    |
+        // ·-·-·-·
+        let maze = RbGenerator::new(Some([42; 32])).generate(3, 3)?;
+        let mut viewer = MazeViewer::new(&maze);
+
+        // walls block the cursor
+        viewer.apply(ViewerAction::Move(Direction::East));
+        assert_eq!(viewer.cursor, maze.start);
+
+        viewer.apply(ViewerAction::ToggleSolution);
+        assert_eq!(viewer.render()[3], "|. . .|");
+
+        for direction in [
+            Direction::South,
+            Direction::East,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+            Direction::West,
+        ]
+        .iter()
+        {
+            assert!(!viewer.is_solved());
+            viewer.apply(ViewerAction::Move(*direction));
+        }
+        assert_eq!(viewer.cursor, Coordinates::new(0, 2));
+        assert!(viewer.is_solved());
+        assert_eq!(viewer.render()[5], "|@    |");
+        Ok(())
+    }
+}
@@ -0,0 +1,209 @@
+//! Infinite mazes which are generated chunk by chunk
+//!
+//! A [`ChunkedMazeWorld`] divides an endless plane into rectangular chunks and generates any of
+//! them on demand, so that games can explore an infinite maze while only keeping the chunks
+//! around the player in memory.
+//! Every chunk only depends on the seed of the world and its own position, so it is the same no
+//! matter in which order chunks are generated or how often they are generated again.
+//!
+//! Adjacent chunks are connected through exactly one opening in the border between them, whose
+//! position is derived from the seed as well.
+//! Every chunk is a perfect maze on its own, so all fields of the world are connected, but the
+//! world as a whole has loops between the chunks.
+//!
+//! # Examples
+//! ```
+//! use maze_generator::prelude::*;
+//! use maze_generator::world::ChunkedMazeWorld;
+//!
+//! let world = ChunkedMazeWorld::new([42; 32], 16, 16).unwrap();
+//! let here = world.chunk(0, 0).unwrap();
+//! let east = world.chunk(1, 0).unwrap();
+//!
+//! // the opening leading east lies in the same row as the one of the eastern chunk leading west
+//! assert_eq!(
+//!     here.opening(Direction::East).y,
+//!     east.opening(Direction::West).y
+//! );
+//! assert_eq!(world.locate(-1, 20), ((-1, 1), Coordinates::new(15, 4)));
+//! ```
+
+use crate::builder::Algorithm;
+use crate::prelude::stable_hash;
+use crate::prelude::*;
+
+/// A single generated chunk of a [`ChunkedMazeWorld`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Chunk {
+    /// Position of the chunk in the world, counted in chunks
+    pub position: (i64, i64),
+    /// The fields of the chunk, using coordinates relative to its north-western corner
+    ///
+    /// The openings into the neighbouring chunks are not part of the maze because they lead out
+    /// of it, see [`Chunk::opening()`].
+    pub maze: Maze,
+    /// Border field with the opening into the neighbouring chunk of every direction, indexed by
+    /// [`Direction::index()`]
+    pub openings: [Coordinates; 4],
+}
+
+impl Chunk {
+    /// The border field whose passage leads into the neighbouring chunk in `direction`
+    #[must_use]
+    pub fn opening(&self, direction: Direction) -> Coordinates {
+        self.openings[direction.index()]
+    }
+}
+
+/// Deterministic generator of an infinite maze, see the [module documentation](self)
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChunkedMazeWorld {
+    seed: Seed,
+    chunk_width: i32,
+    chunk_height: i32,
+    /// The algorithm which generates the chunks, default: [`Algorithm::RecursiveBacktracking`]
+    pub algorithm: Algorithm,
+    /// The texture of the chunks, see [`Algorithm::generator_with_texture()`]
+    pub texture: Texture,
+}
+
+impl ChunkedMazeWorld {
+    /// Create a world whose chunks consist of `chunk_width` x `chunk_height` fields and are
+    /// all derived from `seed`
+    ///
+    /// Fails with [`MazeError::InvalidDimensions`] unless both sizes are positive.
+    pub fn new(seed: Seed, chunk_width: i32, chunk_height: i32) -> Result<Self, MazeError> {
+        ensure_valid_size(chunk_width, chunk_height)?;
+        Ok(ChunkedMazeWorld {
+            seed,
+            chunk_width,
+            chunk_height,
+            algorithm: Algorithm::default(),
+            texture: Texture::default(),
+        })
+    }
+
+    /// Size of every chunk as `(width, height)`
+    #[must_use]
+    pub fn chunk_size(&self) -> (i32, i32) {
+        (self.chunk_width, self.chunk_height)
+    }
+
+    /// Generate the chunk at `(chunk_x, chunk_y)`, counted in chunks from the origin of the
+    /// world
+    ///
+    /// The start of the chunk maze lies at its western opening and the goal at its eastern one.
+    pub fn chunk(&self, chunk_x: i64, chunk_y: i64) -> Result<Chunk, MazeError> {
+        let mut seed = [0; 32];
+        for (i, part) in seed.chunks_mut(8).enumerate() {
+            part.copy_from_slice(&self.hash(b"chunk", chunk_x, chunk_y, i as u8).to_le_bytes());
+        }
+        let mut maze = self
+            .algorithm
+            .generator_with_texture(Some(seed), &self.texture)
+            .generate(self.chunk_width, self.chunk_height)?;
+
+        let (width, height) = (self.chunk_width, self.chunk_height);
+        let column = |chunk_y: i64| {
+            let offset = self.hash(b"south", chunk_x, chunk_y, 0) % width as u64;
+            offset as i32
+        };
+        let row = |chunk_x: i64| {
+            let offset = self.hash(b"east", chunk_x, chunk_y, 0) % height as u64;
+            offset as i32
+        };
+        let openings = [
+            Coordinates::new(column(chunk_y.wrapping_sub(1)), 0),
+            Coordinates::new(width - 1, row(chunk_x)),
+            Coordinates::new(column(chunk_y), height - 1),
+            Coordinates::new(0, row(chunk_x.wrapping_sub(1))),
+        ];
+        maze.start = openings[Direction::West.index()];
+        maze.goal = openings[Direction::East.index()];
+
+        Ok(Chunk {
+            position: (chunk_x, chunk_y),
+            maze,
+            openings,
+        })
+    }
+
+    /// Split global field coordinates into the position of their chunk and the coordinates
+    /// inside of it
+    #[must_use]
+    pub fn locate(&self, x: i64, y: i64) -> ((i64, i64), Coordinates) {
+        let (width, height) = (i64::from(self.chunk_width), i64::from(self.chunk_height));
+        (
+            (x.div_euclid(width), y.div_euclid(height)),
+            Coordinates::new(x.rem_euclid(width) as i32, y.rem_euclid(height) as i32),
+        )
+    }
+
+    /// Stable hash of the seed together with a position, `purpose` separating the values which
+    /// are derived for different uses
+    fn hash(&self, purpose: &[u8], chunk_x: i64, chunk_y: i64, i: u8) -> u64 {
+        let mut bytes = self.seed.to_vec();
+        bytes.extend_from_slice(purpose);
+        bytes.extend_from_slice(&chunk_x.to_le_bytes());
+        bytes.extend_from_slice(&chunk_y.to_le_bytes());
+        bytes.push(i);
+        stable_hash(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChunkedMazeWorld;
+    use crate::builder::Algorithm;
+    use crate::prelude::{Coordinates, Direction, MazeError};
+
+    #[test]
+    fn test_adjacent_chunks_connect() -> anyhow::Result<()> {
+        let mut world = ChunkedMazeWorld::new([40; 32], 7, 5)?;
+        world.algorithm = Algorithm::Prims;
+        for (chunk_x, chunk_y) in [(0, 0), (-3, 2), (i64::MAX, i64::MIN)].iter() {
+            let chunk = world.chunk(*chunk_x, *chunk_y)?;
+            assert!(chunk.maze.is_perfect());
+            for direction in Direction::iter() {
+                let opening = chunk.opening(direction);
+                assert!(chunk.maze.are_coordinates_inside(&opening));
+                assert!(!chunk.maze.are_coordinates_inside(&opening.next(&direction)));
+
+                // the neighbour has its opening right on the other side of the border
+                let (dx, dy) = direction.delta();
+                let neighbour = world.chunk(
+                    chunk_x.wrapping_add(i64::from(dx)),
+                    chunk_y.wrapping_add(i64::from(dy)),
+                )?;
+                let across = opening.next(&direction);
+                assert_eq!(
+                    neighbour.opening(direction.opposite()),
+                    Coordinates::new(across.x.rem_euclid(7), across.y.rem_euclid(5))
+                );
+            }
+        }
+
+        // chunks do not depend on what was generated before
+        let chunk = world.chunk(4, -1)?;
+        world.chunk(0, 0)?;
+        assert_eq!(world.chunk(4, -1)?, chunk);
+        assert_ne!(world.chunk(4, 0)?.maze.passages, chunk.maze.passages);
+        Ok(())
+    }
+
+    #[test]
+    fn test_locate_fields() -> anyhow::Result<()> {
+        let world = ChunkedMazeWorld::new([41; 32], 4, 3)?;
+        assert_eq!(world.locate(0, 0), ((0, 0), Coordinates::new(0, 0)));
+        assert_eq!(world.locate(9, -1), ((2, -1), Coordinates::new(1, 2)));
+        assert_eq!(world.locate(-4, -3), ((-1, -1), Coordinates::new(0, 0)));
+        assert_eq!(
+            ChunkedMazeWorld::new([41; 32], 0, 3),
+            Err(MazeError::InvalidDimensions {
+                width: 0,
+                height: 3
+            })
+        );
+        Ok(())
+    }
+}
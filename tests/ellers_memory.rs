@@ -0,0 +1,66 @@
+//! Guarantees that streaming Eller's algorithm needs memory proportional to the width of the
+//! maze only.
+//!
+//! This lives in its own test binary because it replaces the global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use maze_generator::ellers_algorithm::EllersGenerator;
+
+/// Allocator which keeps track of the highest amount of simultaneously allocated memory
+struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = self.current.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+        self.peak.fetch_max(current, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.current.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator {
+    current: AtomicUsize::new(0),
+    peak: AtomicUsize::new(0),
+};
+
+/// Peak heap usage while streaming a maze of the given size, without the rows themselves
+/// being kept
+fn streaming_peak(width: i32, height: i32) -> anyhow::Result<usize> {
+    let mut generator = EllersGenerator::new(Some([42; 32]));
+    let baseline = ALLOCATOR.current.load(Ordering::SeqCst);
+    ALLOCATOR.peak.store(baseline, Ordering::SeqCst);
+    let mut passages = 0;
+    generator.generate_streaming(width, height, |row| {
+        passages += row
+            .east
+            .iter()
+            .chain(row.south.iter())
+            .filter(|w| !**w)
+            .count();
+    })?;
+    // every maze without loops is a spanning tree
+    assert_eq!(passages, (width * height - 1) as usize);
+    Ok(ALLOCATOR.peak.load(Ordering::SeqCst) - baseline)
+}
+
+#[test]
+fn test_streaming_memory_does_not_grow_with_height() -> anyhow::Result<()> {
+    let short = streaming_peak(64, 10)?;
+    let tall = streaming_peak(64, 100_000)?;
+    // the rows are grouped into sets randomly so the allocations differ slightly
+    assert!(tall <= short * 2, "{} bytes vs. {} bytes", tall, short);
+
+    let wide = streaming_peak(640, 10)?;
+    assert!(wide > short * 5, "{} bytes vs. {} bytes", wide, short);
+    Ok(())
+}
@@ -0,0 +1,145 @@
+//! Pins the mazes generated from fixed seeds.
+//!
+//! Games rely on a seed producing the same maze on every platform and with every release of
+//! this crate.
+//! If any of these tests fail after an intentional change of a generator, the snapshots must be
+//! updated and `CRATE_MAZE_FORMAT_VERSION` must be bumped.
+
+use maze_generator::builder::MazeBuilder;
+use maze_generator::prelude::*;
+use maze_generator::{generate, Algorithm, CRATE_MAZE_FORMAT_VERSION};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+
+const SEED: Seed = [42; 32];
+
+const RECURSIVE_BACKTRACKING: &str = "\
+·-·-·-·-·-·-·
+|S|         |
+· ·-·-·-· · ·
+|         | |
+·-·-·-·-·-· ·
+| |       | |
+· · ·-·-· · ·
+|      G|   |
+·-·-·-·-·-·-·
+";
+
+const GROWING_TREE: &str = "\
+·-·-·-·-·-·-·
+|S          |
+·-· ·-· · ·-·
+| | |G| |   |
+· · · · ·-· ·
+|   | |   | |
+·-· · ·-· · ·
+|   |     | |
+·-·-·-·-·-·-·
+";
+
+const PRIMS: &str = "\
+·-·-·-·-·-·-·
+|           |
+· · ·-· ·-·-·
+| |   |    G|
+· · · ·-·-·-·
+| | |       |
+·-· · ·-·-· ·
+|   |     |S|
+·-·-·-·-·-·-·
+";
+
+const ELLERS: &str = "\
+·-·-·-·-·-·-·
+|     |   |S|
+·-· · · ·-· ·
+|   |   | | |
+· · · · · · ·
+| | | | |   |
+· ·-· ·-· · ·
+|  G|     | |
+·-·-·-·-·-·-·
+";
+
+#[test]
+fn test_format_version() {
+    assert_eq!(CRATE_MAZE_FORMAT_VERSION, 2);
+}
+
+#[test]
+fn test_rng_is_pinned() {
+    let mut rng = ChaChaRng::from_seed(SEED);
+    assert_eq!(rng.next_u64(), 0x1602_83e5_461f_1998);
+    assert_eq!(rng.next_u64(), 0x7a69_0388_9736_5444);
+}
+
+#[test]
+fn test_small_mazes() -> anyhow::Result<()> {
+    for (algorithm, expected) in [
+        (Algorithm::RecursiveBacktracking, RECURSIVE_BACKTRACKING),
+        (Algorithm::GrowingTree, GROWING_TREE),
+        (Algorithm::Prims, PRIMS),
+        (Algorithm::Ellers, ELLERS),
+    ]
+    .iter()
+    {
+        let maze = generate(*algorithm, 6, 4, Some(SEED))?;
+        assert_eq!(format!("{:?}", maze), *expected, "{:?}", algorithm);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_large_mazes() -> anyhow::Result<()> {
+    for (algorithm, expected) in [
+        (Algorithm::RecursiveBacktracking, 0x5b26_44a7_4783_6ed5),
+        (Algorithm::GrowingTree, 0x3efb_479d_bdc3_3572),
+        (Algorithm::Prims, 0xed9b_1e71_7435_3881),
+        (Algorithm::Ellers, 0x787b_8cac_3e3a_7c34),
+    ]
+    .iter()
+    {
+        let maze = generate(*algorithm, 40, 40, Some([7; 32]))?;
+        assert_eq!(maze.fingerprint(), *expected, "{:?}", algorithm);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_post_processing() -> anyhow::Result<()> {
+    for (algorithm, expected) in [
+        (Algorithm::RecursiveBacktracking, 0x10d6_16a6_e4c9_a70e),
+        (Algorithm::GrowingTree, 0x6d03_3faa_0311_2883),
+        (Algorithm::Prims, 0xd048_a288_844f_e7ab),
+        (Algorithm::Ellers, 0xe854_fa9a_ddbb_e8be),
+    ]
+    .iter()
+    {
+        let maze = MazeBuilder::new(20, 20)
+            .algorithm(*algorithm)
+            .seed([7; 32])
+            .goal(GoalPlacement::EdgesOnly)
+            .braid(0.5)
+            .build()?;
+        assert_eq!(maze.fingerprint(), *expected, "{:?}", algorithm);
+    }
+    Ok(())
+}
+
+/// Eller's algorithm draws its random numbers row by row and set by set, so mazes of extreme
+/// shapes pin the order in which the sets of a row are visited
+#[test]
+fn test_ellers_shapes() -> anyhow::Result<()> {
+    for ((width, height), expected) in [
+        ((1, 30), 0xcd6f_af5d_890b_8eff),
+        ((30, 1), 0xf661_579c_144c_5483),
+        ((64, 5), 0xff69_6370_1b15_0a45),
+        ((5, 64), 0xde06_0370_97a2_acdb),
+    ]
+    .iter()
+    {
+        let maze = generate(Algorithm::Ellers, *width, *height, Some([9; 32]))?;
+        assert_eq!(maze.fingerprint(), *expected, "{}x{}", width, height);
+    }
+    Ok(())
+}